@@ -51,6 +51,21 @@ pub fn surreal_object_trait_derive(input: TokenStream) -> TokenStream {
     models::object::generate_fields_getter_trait(input)
 }
 
+#[proc_macro_derive(NodeUnion)]
+pub fn node_union_trait_derive(input: TokenStream) -> TokenStream {
+    models::node_union::generate_node_union_trait(input)
+}
+
+#[proc_macro_derive(SurrealEnum, attributes(serde))]
+pub fn surreal_enum_trait_derive(input: TokenStream) -> TokenStream {
+    models::surreal_enum::generate_surreal_enum_trait(input)
+}
+
+#[proc_macro_derive(SurrealFieldType)]
+pub fn surreal_field_type_trait_derive(input: TokenStream) -> TokenStream {
+    models::surreal_field_type::generate_surreal_field_type_trait(input)
+}
+
 #[proc_macro_derive(TableResources, attributes(sorm))]
 pub fn surreal_table_resources_derive(input: TokenStream) -> TokenStream {
     migrations::table::generate_table_resources_trait(input)
@@ -7,4 +7,7 @@
 
 pub mod edge;
 pub mod node;
+pub mod node_union;
 pub mod object;
+pub mod surreal_enum;
+pub mod surreal_field_type;
@@ -0,0 +1,22 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use darling::FromDeriveInput;
+use quote::quote;
+use sorm_derive_helpers::models::SurrealFieldTypeToken;
+use syn::parse_macro_input;
+
+pub fn generate_surreal_field_type_trait(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input);
+    let output = match SurrealFieldTypeToken::from_derive_input(&input) {
+        Ok(out) => out,
+        Err(err) => return proc_macro::TokenStream::from(err.write_errors()),
+    };
+    quote!(#output).into()
+}
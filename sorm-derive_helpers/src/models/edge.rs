@@ -71,6 +71,8 @@ impl ToTokens for EdgeToken {
             _____struct_marker_ident,
             ..
         } = VariablesModelMacro::new();
+        let allow_destructive = self.allow_destructive();
+        let old_table_name = self.old_table_name_token();
         let table_definitions = match self.get_table_definition_token() {
             Ok(table_definitions) => table_definitions,
             Err(err) => return tokens.extend(err.write_errors()),
@@ -94,6 +96,8 @@ impl ToTokens for EdgeToken {
             record_link_fields_methods,
             schema_struct_fields_names_kv_empty,
             field_definitions,
+            index_definitions,
+            event_definitions,
             serialized_ident_struct_partial_init_fields,
             serialized_fmt_db_field_names_instance: serializable_fields,
             linked_fields,
@@ -101,6 +105,9 @@ impl ToTokens for EdgeToken {
             link_self_fields,
             link_one_and_self_fields,
             link_many_fields,
+            linked_models,
+            version_field,
+            soft_delete_field,
             struct_partial_fields,
             struct_partial_associated_functions,
             renamed_serialized_fields_kv,
@@ -109,6 +116,15 @@ impl ToTokens for EdgeToken {
             ..
         } = &code_gen;
 
+        let version_field = match version_field {
+            Some(version_field) => quote!(::std::option::Option::Some(#version_field)),
+            None => quote!(::std::option::Option::None),
+        };
+        let soft_delete_field = match soft_delete_field {
+            Some(soft_delete_field) => quote!(::std::option::Option::Some(#soft_delete_field)),
+            None => quote!(::std::option::Option::None),
+        };
+
         let has_in_and_out_fields = serialized_field_names_normalised
             .iter()
             .map(|this| {
@@ -265,6 +281,21 @@ impl ToTokens for EdgeToken {
                         return ::std::vec![#( #link_many_fields), *]
                     }
 
+                    fn get_linked_model(field: &str) -> ::std::option::Option<#crate_name::LinkedModel> {
+                        match field {
+                            #( #linked_models, )*
+                            _ => ::std::option::Option::None,
+                        }
+                    }
+
+                    fn version_field() -> ::std::option::Option<#crate_name::Field> {
+                        #version_field
+                    }
+
+                    fn soft_delete_field() -> ::std::option::Option<#crate_name::Field> {
+                        #soft_delete_field
+                    }
+
                     fn define_table() -> #crate_name::Raw{
                         #table_definitions
                     }
@@ -275,9 +306,29 @@ impl ToTokens for EdgeToken {
                         ]
                     }
 
+                    fn define_indexes() -> ::std::vec::Vec<#crate_name::Raw> {
+                        ::std::vec![
+                           #( #index_definitions), *
+                        ]
+                    }
+
+                    fn define_events() -> ::std::vec::Vec<#crate_name::Raw> {
+                        ::std::vec![
+                           #( #event_definitions), *
+                        ]
+                    }
+
                     fn get_field_meta() -> ::std::vec::Vec<#crate_name::FieldMetadata> {
                         return ::std::vec![#( #field_metadata), *]
                     }
+
+                    fn allow_destructive() -> bool {
+                        #allow_destructive
+                    }
+
+                    fn old_table_name() -> ::std::option::Option<#crate_name::Table> {
+                        #old_table_name
+                    }
                 }
 
                 #[allow(non_snake_case)]
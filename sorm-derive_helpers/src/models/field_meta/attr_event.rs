@@ -0,0 +1,89 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+/// An event declared on a struct e.g
+/// `#[sorm(event(name = "rank_changed", when = before.rank.not_equal(after.rank), then = log_rank_change))]`
+#[derive(Debug, Clone, FromMeta)]
+pub struct AttributeEvent {
+    pub name: String,
+    pub when: syn::Expr,
+    pub then: EventThen,
+}
+
+/// What an event runs when triggered. Either a query expression
+/// e.g `then = delete::<Log>(Log::table())` or the path of a function returning a query
+/// e.g `then = log_rank_change`
+#[derive(Debug, Clone)]
+pub struct EventThen(pub syn::Expr);
+
+impl FromMeta for EventThen {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        syn::Expr::from_expr(expr).map(Self)
+    }
+}
+
+impl ToTokens for EventThen {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let then = &self.0;
+        match then {
+            syn::Expr::Path(path) => tokens.extend(quote!(#path())),
+            _ => tokens.extend(quote!(#then)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_event_with_fn_path() {
+        let meta: syn::Meta = parse_quote!(event(
+            name = "rank_changed",
+            when = before.rank.not_equal(after.rank),
+            then = log_rank_change
+        ));
+        let event = AttributeEvent::from_meta(&meta).unwrap();
+
+        assert_eq!(event.name, "rank_changed");
+        assert_eq!(
+            event.when.to_token_stream().to_string(),
+            "before . rank . not_equal (after . rank)"
+        );
+        assert_eq!(
+            event.then.to_token_stream().to_string(),
+            "log_rank_change ()"
+        );
+    }
+
+    #[test]
+    fn test_parse_event_with_expression() {
+        let meta: syn::Meta = parse_quote!(event(
+            name = "pilot_removed",
+            when = event.eq("DELETE"),
+            then = delete::<Log>(Log::table())
+        ));
+        let event = AttributeEvent::from_meta(&meta).unwrap();
+
+        assert_eq!(
+            event.then.to_token_stream().to_string(),
+            "delete :: < Log > (Log :: table ())"
+        );
+    }
+
+    #[test]
+    fn test_parse_event_without_then_fails() {
+        let meta: syn::Meta = parse_quote!(event(name = "rank_changed", when = event.eq("UPDATE")));
+
+        assert!(AttributeEvent::from_meta(&meta).is_err());
+    }
+}
@@ -0,0 +1,213 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use darling::{ast::NestedMeta, FromMeta};
+use syn::{spanned::Spanned, Ident};
+
+/// An index declared on a field e.g `#[sorm(index)]`, `#[sorm(index(name = "email_idx", unique))]`
+/// or on a struct e.g `#[sorm(index(name = "species_speed_idx", fields = [species, velocity], unique))]`
+#[derive(Debug, Clone, Default)]
+pub struct AttributeIndex {
+    pub name: Option<String>,
+    pub fields: IndexFields,
+    pub unique: bool,
+    pub search: Option<AttributeSearch>,
+}
+
+impl FromMeta for AttributeIndex {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self::default())
+    }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        #[derive(FromMeta)]
+        struct FullIndex {
+            #[darling(default)]
+            name: Option<String>,
+            #[darling(default)]
+            fields: IndexFields,
+            #[darling(default)]
+            unique: bool,
+            #[darling(default)]
+            search: Option<AttributeSearch>,
+        }
+
+        let FullIndex {
+            name,
+            fields,
+            unique,
+            search,
+        } = FullIndex::from_list(items)?;
+
+        if unique && search.is_some() {
+            return Err(darling::Error::custom(
+                "An index cannot be both unique and a search index",
+            ));
+        }
+
+        Ok(Self {
+            name,
+            fields,
+            unique,
+            search,
+        })
+    }
+}
+
+/// The struct fields an index is defined on e.g `fields = [species, velocity]`
+#[derive(Debug, Clone, Default)]
+pub struct IndexFields(pub Vec<Ident>);
+
+impl FromMeta for IndexFields {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        let syn::Expr::Array(array) = expr else {
+            return Err(darling::Error::custom(
+                "Expected a list of fields e.g fields = [species, velocity]",
+            )
+            .with_span(&expr.span()));
+        };
+
+        array
+            .elems
+            .iter()
+            .map(|elem| match elem {
+                syn::Expr::Path(path) => path.path.get_ident().cloned().ok_or_else(|| {
+                    darling::Error::custom("Expected a field name").with_span(&elem.span())
+                }),
+                _ => Err(darling::Error::custom("Expected a field name").with_span(&elem.span())),
+            })
+            .collect::<darling::Result<Vec<_>>>()
+            .map(Self)
+    }
+}
+
+/// A full-text search index e.g `#[sorm(search(analyzer = "ascii", bm25(1.2, 0.75), highlights))]`
+#[derive(Debug, Clone, FromMeta)]
+pub struct AttributeSearch {
+    pub analyzer: String,
+    #[darling(default)]
+    pub bm25: Option<Bm25>,
+    #[darling(default)]
+    pub highlights: bool,
+}
+
+impl AttributeSearch {
+    /// The search clause of the index definition. The database expects the scoring before
+    /// HIGHLIGHTS and always scores, with BM25 and its own parameters by default.
+    pub fn search_clause(&self) -> String {
+        let AttributeSearch {
+            analyzer,
+            bm25,
+            highlights,
+        } = self;
+
+        let scoring = match bm25 {
+            Some(Bm25(Some((k1, b)))) => format!("BM25({k1:?},{b:?})"),
+            _ => "BM25".to_string(),
+        };
+        let highlights = if *highlights { " HIGHLIGHTS" } else { "" };
+
+        format!("SEARCH ANALYZER {analyzer} {scoring}{highlights}")
+    }
+}
+
+/// BM25 scoring of a search index, either `bm25` for the database defaults or `bm25(k1, b)`
+#[derive(Debug, Clone, Default)]
+pub struct Bm25(pub Option<(f32, f32)>);
+
+impl FromMeta for Bm25 {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self(None))
+    }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let params = items
+            .iter()
+            .map(|item| match item {
+                NestedMeta::Lit(syn::Lit::Float(float)) => float.base10_parse::<f32>(),
+                NestedMeta::Lit(syn::Lit::Int(int)) => int.base10_parse::<f32>(),
+                _ => Err(syn::Error::new(item.span(), "Expected a number")),
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        match params[..] {
+            [k1, b] => Ok(Self(Some((k1, b)))),
+            _ => Err(darling::Error::custom(
+                "Expected the k1 and b parameters e.g bm25(1.2, 0.75)",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_table_index() {
+        let meta: syn::Meta = parse_quote!(index(
+            name = "species_speed_idx",
+            fields = [species, velocity],
+            unique
+        ));
+        let index = AttributeIndex::from_meta(&meta).unwrap();
+
+        assert_eq!(index.name.as_deref(), Some("species_speed_idx"));
+        assert_eq!(
+            index
+                .fields
+                .0
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["species", "velocity"]
+        );
+        assert!(index.unique);
+        assert!(index.search.is_none());
+    }
+
+    #[test]
+    fn test_parse_field_index_word() {
+        let meta: syn::Meta = parse_quote!(index);
+        let index = AttributeIndex::from_meta(&meta).unwrap();
+
+        assert!(index.name.is_none());
+        assert!(index.fields.0.is_empty());
+        assert!(!index.unique);
+    }
+
+    #[test]
+    fn test_parse_search() {
+        let meta: syn::Meta = parse_quote!(search(analyzer = "ascii", bm25(1.2, 0.75), highlights));
+        let search = AttributeSearch::from_meta(&meta).unwrap();
+
+        assert_eq!(search.analyzer, "ascii");
+        assert_eq!(search.bm25.as_ref().unwrap().0, Some((1.2, 0.75)));
+        assert!(search.highlights);
+        assert_eq!(
+            search.search_clause(),
+            "SEARCH ANALYZER ascii BM25(1.2,0.75) HIGHLIGHTS"
+        );
+
+        let meta: syn::Meta = parse_quote!(search(analyzer = "ascii"));
+        let search = AttributeSearch::from_meta(&meta).unwrap();
+        assert_eq!(search.search_clause(), "SEARCH ANALYZER ascii BM25");
+    }
+
+    #[test]
+    fn test_parse_unique_search_index_fails() {
+        let meta: syn::Meta = parse_quote!(index(
+            name = "name_idx",
+            fields = [name],
+            unique,
+            search(analyzer = "ascii")
+        ));
+
+        assert!(AttributeIndex::from_meta(&meta).is_err());
+    }
+}
@@ -38,6 +38,16 @@ impl<'a> FieldTypeInference<'a> {
     }
 
     pub fn infer_type_by_priority(&self) -> ExtractorResult<Option<DbFieldTypeAstMeta>> {
+        let db_type = if self.infers_from_surreal_field_type() {
+            Some(self.based_on_surreal_field_type())
+        } else {
+            self.infer_type_syntactically()
+        };
+
+        Ok(db_type)
+    }
+
+    fn infer_type_syntactically(&self) -> Option<DbFieldTypeAstMeta> {
         let relation_type = self.relation_type;
         let model_attrs = self.model_attrs;
         let ty = self.field_ty;
@@ -48,7 +58,7 @@ impl<'a> FieldTypeInference<'a> {
         let priority2 = || self.based_on_db_field_name(&ty, &field_name, &model_attrs);
         let priority3 = || self.based_on_field_link_type(&ty, &relation_type);
 
-        let db_type = if let Ok(db_ty) = priority1() {
+        if let Ok(db_ty) = priority1() {
             Some(db_ty)
         } else if let Ok(db_ty) = priority2() {
             Some(db_ty)
@@ -56,9 +66,37 @@ impl<'a> FieldTypeInference<'a> {
             Some(db_ty)
         } else {
             None
-        };
+        }
+    }
 
-        Ok(db_type)
+    /// Fields that are neither links, relations, nested objects nor the reserved `id`, `in`
+    /// and `out` fields take the database type of their rust type's `SurrealFieldType`
+    /// implementation. Only the others are inferred from the shape of their rust type,
+    /// since their types cannot implement it.
+    pub fn infers_from_surreal_field_type(&self) -> bool {
+        let model_type = self.model_attrs.to_data_type();
+
+        matches!(
+            self.relation_type,
+            RelationType::None | RelationType::List(_)
+        ) && !self.db_field_name.is_id()
+            && !self.db_field_name.is_in_or_out_edge_node(&model_type)
+    }
+
+    fn based_on_surreal_field_type(&self) -> DbFieldTypeAstMeta {
+        let crate_name = get_crate_name(false);
+        let ty = self.field_ty;
+
+        DbFieldTypeAstMeta {
+            // The actual type is only known once the trait implementation is evaluated
+            field_type_db_original: FieldType::Any,
+            field_type_db_token: quote!(<#ty as #crate_name::SurrealFieldType>::field_type())
+                .into(),
+            static_assertion_token: quote!(
+                #crate_name::validators::assert_impl_one!(#ty: #crate_name::SurrealFieldType);
+            )
+            .into(),
+        }
     }
 
     /// This is done directly by morphologically analysing the field type path tokenstream
@@ -372,7 +410,7 @@ impl<'a> FieldTypeInference<'a> {
 
                 DbFieldTypeAstMeta {
                             field_type_db_original: FieldType::Record(vec![]),
-                            field_type_db_token: quote!(#crate_name::FieldType::Record(::std::vec![#ref_node::table()])).into(),
+                            field_type_db_token: quote!(#crate_name::FieldType::Record(<#ref_node as #crate_name::Linkable>::link_tables())).into(),
                             static_assertion_token: quote!(#crate_name::validators::assert_type_eq_all!(#field_ty, #crate_name::LinkOne<#ref_node>);).into()
                         }
             },
@@ -398,7 +436,7 @@ impl<'a> FieldTypeInference<'a> {
                                 ::std::option::Option::None
                             ),
                             field_type_db_token: quote!(#crate_name::FieldType::Array(
-                                ::std::boxed::Box::new(#crate_name::FieldType::Record(::std::vec![#ref_node::table()])),
+                                ::std::boxed::Box::new(#crate_name::FieldType::Record(<#ref_node as #crate_name::Linkable>::link_tables())),
                                 ::std::option::Option::None
                             )).into(),
                             static_assertion_token: quote!(
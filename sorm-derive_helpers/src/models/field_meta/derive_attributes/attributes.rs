@@ -7,6 +7,11 @@ impl MyFieldReceiver {
         &self.attrs
     }
 
+    /// Whether the field is a timestamp set by the database i.e `created_at` or `updated_at`
+    pub fn is_timestamp(&self) -> bool {
+        self.created_at || self.updated_at
+    }
+
     pub fn validate_attributes(&self) -> ExtractorResult<()> {
         if self.relate.is_some() {
             let has_required_attributes = self.skip_serializing && self.default;
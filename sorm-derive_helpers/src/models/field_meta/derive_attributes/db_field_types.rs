@@ -6,6 +6,7 @@
  */
 
 use crate::models::*;
+use proc_macro2::TokenStream;
 use sorm_query::FieldType;
 
 use super::MyFieldReceiver;
@@ -123,6 +124,36 @@ impl MyFieldReceiver {
         Ok(db_type)
     }
 
+    /// The assertion of the `SurrealFieldType` implementation of the field's type e.g
+    /// restricting an enum field to the enum's variants.
+    /// Not used when the field's database type is explicitly specified with `ty`
+    pub fn surreal_field_type_assertion(
+        &self,
+        model_attributes: &ModelAttributes,
+    ) -> ExtractorResult<Option<TokenStream>> {
+        if self.field_type_db.is_some() {
+            return Ok(None);
+        }
+
+        let crate_name = get_crate_name(false);
+        let casing = model_attributes.casing()?;
+        let field_ty = self
+            .ty()
+            .replace_self_with_current_struct_concrete_type(model_attributes)?
+            .into_inner();
+
+        let infers_from_surreal_field_type = FieldTypeInference {
+            db_field_name: &self.db_field_name(&casing)?,
+            relation_type: &self.to_relation_type(model_attributes),
+            field_ty: &field_ty,
+            model_attrs: model_attributes,
+        }
+        .infers_from_surreal_field_type();
+
+        Ok(infers_from_surreal_field_type
+            .then(|| quote!(<#field_ty as #crate_name::SurrealFieldType>::assertion())))
+    }
+
     pub fn is_in_or_out_edge_node_field(&self, model_attributes: &ModelAttributes) -> bool {
         let is_in_or_out_edge_node = self
             .db_field_name(
@@ -88,6 +88,27 @@ pub struct MyFieldReceiver {
     #[darling(default)]
     pub(crate) item_assert: Option<AttributeItemAssert>,
 
+    /// Version of the record used for optimistic concurrency control
+    #[darling(default)]
+    pub(crate) version: bool,
+
+    /// Set once to the time the record is created
+    #[darling(default)]
+    pub(crate) created_at: bool,
+
+    /// Set to the time the record is last written
+    #[darling(default)]
+    pub(crate) updated_at: bool,
+
+    #[darling(default)]
+    pub(crate) index: Option<AttributeIndex>,
+
+    #[darling(default)]
+    pub(crate) unique: bool,
+
+    #[darling(default)]
+    pub(crate) search: Option<AttributeSearch>,
+
     // Serde attributes
     #[darling(default)]
     pub(crate) skip_serializing: bool,
@@ -40,6 +40,10 @@ impl DbFieldName {
         self.0 == "id"
     }
 
+    pub fn is_soft_delete_field(&self) -> bool {
+        self.0 == "deleted_at"
+    }
+
     pub fn is_in_edge_node(&self, model_type: DataType) -> bool {
         model_type.is_edge() && self.0 == "in"
     }
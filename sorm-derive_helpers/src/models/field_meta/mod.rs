@@ -5,6 +5,8 @@
  * Licensed under the MIT license
  */
 
+mod attr_event;
+mod attr_index;
 mod attr_permissions;
 mod attr_relate;
 mod attr_type_db;
@@ -24,6 +26,8 @@ mod token_stream_hashable;
 mod token_wrappers;
 mod type_stripper;
 
+pub use attr_event::*;
+pub use attr_index::*;
 pub use attr_permissions::*;
 pub use attr_relate::*;
 pub use attr_type_db::*;
@@ -33,6 +33,9 @@ create_tokenstream_wrapper!(=> LinkOneField);
 create_tokenstream_wrapper!(=> LinkSelfField);
 create_tokenstream_wrapper!(=> LinkOneAndSelfField);
 create_tokenstream_wrapper!(=> LinkManyField);
+create_tokenstream_wrapper!(=> LinkedModelToken);
+create_tokenstream_wrapper!(=> VersionField);
+create_tokenstream_wrapper!(=> SoftDeleteField);
 create_tokenstream_wrapper!(=> SerializableField);
 create_tokenstream_wrapper!(=> SerializedIdentStructPartialInitFields);
 
@@ -66,3 +69,5 @@ create_tokenstream_wrapper!(=> DbFieldNamesToken);
 create_tokenstream_wrapper!(=> TableDefinitions);
 
 create_tokenstream_wrapper!(=> DefineFieldStatementToken);
+create_tokenstream_wrapper!(=> DefineIndexStatementToken);
+create_tokenstream_wrapper!(=> DefineEventStatementToken);
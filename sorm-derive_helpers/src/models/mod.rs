@@ -11,7 +11,10 @@ pub(crate) mod errors;
 pub(crate) mod field_meta;
 pub(crate) mod keywords;
 pub(crate) mod node;
+pub(crate) mod node_union;
 pub(crate) mod object;
+pub(crate) mod surreal_enum;
+pub(crate) mod surreal_field_type;
 pub(crate) mod table_meta;
 pub(crate) mod token_codegen;
 pub(crate) mod utils;
@@ -23,7 +26,10 @@ pub use errors::*;
 pub use field_meta::*;
 pub use keywords::*;
 pub use node::*;
+pub use node_union::*;
 pub use object::*;
+pub use surreal_enum::*;
+pub use surreal_field_type::*;
 pub use table_meta::*;
 pub use token_codegen::*;
 pub use utils::*;
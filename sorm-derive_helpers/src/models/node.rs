@@ -87,7 +87,12 @@ impl ToTokens for NodeToken {
             link_self_fields,
             link_one_and_self_fields,
             link_many_fields,
+            linked_models,
+            version_field,
+            soft_delete_field,
             field_definitions,
+            index_definitions,
+            event_definitions,
             fields_relations_aliased,
             struct_partial_fields,
             struct_partial_associated_functions,
@@ -97,6 +102,15 @@ impl ToTokens for NodeToken {
             ..
         } = &code_gen;
 
+        let version_field = match version_field {
+            Some(version_field) => quote!(::std::option::Option::Some(#version_field)),
+            None => quote!(::std::option::Option::None),
+        };
+        let soft_delete_field = match soft_delete_field {
+            Some(soft_delete_field) => quote!(::std::option::Option::Some(#soft_delete_field)),
+            None => quote!(::std::option::Option::None),
+        };
+
         let imports_referenced_node_schema =
             imports_referenced_node_schema.iter().collect::<Vec<_>>();
 
@@ -113,6 +127,8 @@ impl ToTokens for NodeToken {
         let struct_partial_builder_ident = struct_name_ident.partial_builder_ident();
 
         let serializable_fields_count = serializable_fields.len();
+        let allow_destructive = self.allow_destructive();
+        let old_table_name = self.old_table_name_token();
         let table_definitions = match self.get_table_definition_token() {
             Ok(table_definitions) => table_definitions,
             Err(err) => return tokens.extend(err.write_errors()),
@@ -279,10 +295,33 @@ impl ToTokens for NodeToken {
                     return ::std::vec![#( #link_many_fields), *]
                 }
 
+                fn get_linked_model(field: &str) -> ::std::option::Option<#crate_name::LinkedModel> {
+                    match field {
+                        #( #linked_models, )*
+                        _ => ::std::option::Option::None,
+                    }
+                }
+
+                fn version_field() -> ::std::option::Option<#crate_name::Field> {
+                    #version_field
+                }
+
+                fn soft_delete_field() -> ::std::option::Option<#crate_name::Field> {
+                    #soft_delete_field
+                }
+
                 fn get_field_meta() -> ::std::vec::Vec<#crate_name::FieldMetadata> {
                     return ::std::vec![#( #field_metadata), *]
                 }
 
+                fn allow_destructive() -> bool {
+                    #allow_destructive
+                }
+
+                fn old_table_name() -> ::std::option::Option<#crate_name::Table> {
+                    #old_table_name
+                }
+
                 fn define_table() -> #crate_name::Raw {
                     #table_definitions
                 }
@@ -292,6 +331,18 @@ impl ToTokens for NodeToken {
                        #( #field_definitions), *
                     ]
                 }
+
+                fn define_indexes() -> ::std::vec::Vec<#crate_name::Raw> {
+                    ::std::vec![
+                       #( #index_definitions), *
+                    ]
+                }
+
+                fn define_events() -> ::std::vec::Vec<#crate_name::Raw> {
+                    ::std::vec![
+                       #( #event_definitions), *
+                    ]
+                }
             }
 
             #[allow(non_snake_case)]
@@ -0,0 +1,346 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use convert_case::{Case, Casing};
+use darling::{ast, util, FromDeriveInput, FromVariant, ToTokens};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Ident;
+
+use crate::models::*;
+
+/// An enum of nodes that record links can point to e.g `enum Vehicle { Car(Car), Bike(Bike) }`
+#[derive(Debug, Clone, FromDeriveInput)]
+#[darling(supports(enum_newtype))]
+pub struct NodeUnionToken {
+    pub(crate) ident: Ident,
+    pub(crate) generics: syn::Generics,
+    pub data: ast::Data<NodeUnionVariant, util::Ignored>,
+}
+
+#[derive(Debug, Clone, FromVariant)]
+pub struct NodeUnionVariant {
+    pub(crate) ident: Ident,
+    pub(crate) fields: ast::Fields<NewtypeField>,
+}
+
+impl NodeUnionVariant {
+    fn node_ty(&self) -> &syn::Type {
+        &self.fields.fields[0].ty
+    }
+
+    /// The method of the schema traversing into the node of the variant e.g `car`
+    fn schema_method(&self) -> Ident {
+        format_ident!("{}", self.ident.to_string().to_case(Case::Snake))
+    }
+}
+
+impl NodeUnionToken {
+    pub(crate) fn variants(&self) -> ExtractorResult<Vec<&NodeUnionVariant>> {
+        if !self.generics.params.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &self.generics,
+                "Generic node unions are not supported",
+            )
+            .into());
+        }
+
+        let variants = self.data.as_ref().take_enum().ok_or_else(|| {
+            darling::Error::custom("Only enums are supported").with_span(&self.ident)
+        })?;
+
+        if variants.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &self.ident,
+                "A node union must have at least one variant",
+            )
+            .into());
+        }
+
+        Ok(variants)
+    }
+}
+
+impl ToTokens for NodeUnionToken {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let crate_name = get_crate_name(false);
+        let union_ident = &self.ident;
+        let union_name = union_ident.to_string();
+        let module_name = format_ident!("{}", union_name.to_case(Case::Snake));
+        let VariablesModelMacro {
+            __________connect_node_to_graph_traversal_string,
+            ___________graph_traversal_string,
+            ___________bindings,
+            ___________errors,
+            ..
+        } = VariablesModelMacro::new();
+
+        let variants = match self.variants() {
+            Ok(variants) => variants,
+            Err(err) => return tokens.extend(err.write_errors()),
+        };
+        let variant_idents = variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+        let node_tys = variants.iter().map(|v| v.node_ty()).collect::<Vec<_>>();
+        let schema_methods = variants
+            .iter()
+            .map(|v| v.schema_method())
+            .collect::<Vec<_>>();
+        let schema_methods_docs = variant_idents
+            .iter()
+            .map(|ident| format!("Traverses into the fields of the `{ident}` variant"));
+
+        tokens.extend(quote!(
+            #(
+                impl ::std::convert::From<#node_tys> for #union_ident {
+                    fn from(node: #node_tys) -> Self {
+                        Self::#variant_idents(node)
+                    }
+                }
+            )*
+
+            impl #crate_name::serde::Serialize for #union_ident {
+                fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+                where
+                    S: #crate_name::serde::Serializer,
+                {
+                    match self {
+                        #( Self::#variant_idents(node) => #crate_name::serde::Serialize::serialize(node, serializer), )*
+                    }
+                }
+            }
+
+            impl<'de> #crate_name::serde::Deserialize<'de> for #union_ident {
+                fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+                where
+                    D: #crate_name::serde::Deserializer<'de>,
+                {
+                    use #crate_name::serde::de::Error as _;
+
+                    let record = <#crate_name::internal_tools::serde_json::Value as #crate_name::serde::Deserialize>::deserialize(deserializer)?;
+                    let table = <Self as #crate_name::NodeUnion>::record_table(&record).ok_or_else(|| {
+                        D::Error::custom(concat!("Unable to get the table of the record from its id to deserialize it into ", #union_name))
+                    })?;
+
+                    #(
+                        if table == <#node_tys as #crate_name::Model>::table().to_string() {
+                            return #crate_name::internal_tools::serde_json::from_value::<#node_tys>(record)
+                                .map(Self::#variant_idents)
+                                .map_err(D::Error::custom);
+                        }
+                    )*
+
+                    ::std::result::Result::Err(D::Error::custom(format!(
+                        "Table {} is not the table of any of the nodes of {}", table, #union_name
+                    )))
+                }
+            }
+
+            impl #crate_name::NodeUnion for #union_ident {
+                fn variant_tables() -> ::std::vec::Vec<#crate_name::Table> {
+                    ::std::vec![#( <#node_tys as #crate_name::Model>::table() ),*]
+                }
+
+                fn variant_table(&self) -> #crate_name::Table {
+                    match self {
+                        #( Self::#variant_idents(_) => <#node_tys as #crate_name::Model>::table(), )*
+                    }
+                }
+            }
+
+            impl #crate_name::Linkable for #union_ident {
+                type LinkId = #crate_name::sql::Thing;
+
+                fn link_tables() -> ::std::vec::Vec<#crate_name::Table> {
+                    <Self as #crate_name::NodeUnion>::variant_tables()
+                }
+
+                fn link_id(&self) -> #crate_name::sql::Thing {
+                    match self {
+                        #( Self::#variant_idents(node) => <#node_tys as #crate_name::Model>::get_id_as_thing(node), )*
+                    }
+                }
+
+                fn linked_model() -> #crate_name::LinkedModel {
+                    fn linked_fields() -> ::std::vec::Vec<#crate_name::Field> {
+                        [#( <#node_tys as #crate_name::Model>::get_linked_fields() ),*].concat()
+                    }
+
+                    fn linked_model(field: &str) -> ::std::option::Option<#crate_name::LinkedModel> {
+                        ::std::option::Option::None
+                            #( .or_else(|| <#node_tys as #crate_name::Model>::get_linked_model(field)) )*
+                    }
+
+                    #crate_name::LinkedModel::new(linked_fields, linked_model)
+                }
+            }
+
+            impl #crate_name::SchemaGetter for #union_ident {
+                type Schema = #module_name::Schema;
+
+                fn schema() -> Self::Schema {
+                    #module_name::Schema::new()
+                }
+
+                fn schema_prefixed(prefix: impl ::std::convert::Into<#crate_name::ValueLike>) -> Self::Schema {
+                    #module_name::Schema::new_prefixed(prefix)
+                }
+            }
+
+            #[allow(non_snake_case)]
+            pub mod #module_name {
+                use #crate_name::Parametric as _;
+                use #crate_name::Buildable as _;
+                use #crate_name::Erroneous as _;
+                use super::*;
+
+                /// The schema of a record link to any of the nodes of the union
+                #[derive(Debug, Clone)]
+                pub struct Schema {
+                    #___________graph_traversal_string: ::std::string::String,
+                    #___________bindings: #crate_name::BindingsList,
+                    #___________errors: ::std::vec::Vec<::std::string::String>,
+                }
+
+                impl Schema {
+                    pub fn new() -> Self {
+                        Self {
+                            #___________graph_traversal_string: "".into(),
+                            #___________bindings: ::std::vec![],
+                            #___________errors: ::std::vec![],
+                        }
+                    }
+
+                    pub fn new_prefixed(prefix: impl ::std::convert::Into<#crate_name::ValueLike>) -> Self {
+                        let prefix: #crate_name::ValueLike = prefix.into();
+
+                        Self {
+                            #___________graph_traversal_string: prefix.build(),
+                            #___________bindings: prefix.get_bindings(),
+                            #___________errors: ::std::vec![],
+                        }
+                    }
+
+                    pub fn #__________connect_node_to_graph_traversal_string(
+                        connection: impl #crate_name::Buildable + #crate_name::Parametric + #crate_name::Erroneous,
+                        clause: impl ::std::convert::Into<#crate_name::NodeClause>,
+                    ) -> Self {
+                        let clause: #crate_name::NodeClause = clause.into();
+
+                        Self {
+                            #___________graph_traversal_string: format!("{}{}", connection.build(), clause.build()),
+                            #___________bindings: [connection.get_bindings(), clause.get_bindings()].concat(),
+                            #___________errors: [connection.get_errors(), clause.get_errors()].concat(),
+                        }
+                    }
+
+                    #(
+                        #[doc = #schema_methods_docs]
+                        pub fn #schema_methods(&self) -> <#node_tys as #crate_name::SchemaGetter>::Schema {
+                            <<#node_tys as #crate_name::SchemaGetter>::Schema>::#__________connect_node_to_graph_traversal_string(
+                                self,
+                                #crate_name::Clause::from(#crate_name::Empty),
+                            )
+                        }
+                    )*
+                }
+
+                impl #crate_name::Parametric for Schema {
+                    fn get_bindings(&self) -> #crate_name::BindingsList {
+                        self.#___________bindings.to_vec()
+                    }
+                }
+
+                impl #crate_name::Buildable for Schema {
+                    fn build(&self) -> ::std::string::String {
+                        self.#___________graph_traversal_string.to_string()
+                    }
+                }
+
+                impl #crate_name::Erroneous for Schema {
+                    fn get_errors(&self) -> ::std::vec::Vec<::std::string::String> {
+                        self.#___________errors.to_vec()
+                    }
+                }
+
+                impl #crate_name::Parametric for &Schema {
+                    fn get_bindings(&self) -> #crate_name::BindingsList {
+                        self.#___________bindings.to_vec()
+                    }
+                }
+
+                impl #crate_name::Buildable for &Schema {
+                    fn build(&self) -> ::std::string::String {
+                        self.#___________graph_traversal_string.to_string()
+                    }
+                }
+
+                impl #crate_name::Erroneous for &Schema {
+                    fn get_errors(&self) -> ::std::vec::Vec<::std::string::String> {
+                        self.#___________errors.to_vec()
+                    }
+                }
+
+                impl ::std::fmt::Display for Schema {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        f.write_str(&self.#___________graph_traversal_string)
+                    }
+                }
+            }
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_variant_schema_methods() {
+        let input: syn::DeriveInput = parse_quote!(
+            enum Vehicle {
+                Car(Car),
+                CargoBike(CargoBike),
+            }
+        );
+        let token = NodeUnionToken::from_derive_input(&input).unwrap();
+        let methods = token
+            .variants()
+            .unwrap()
+            .iter()
+            .map(|v| v.schema_method().to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(methods, vec!["car", "cargo_bike"]);
+    }
+
+    #[test]
+    fn test_variants_must_wrap_a_node() {
+        let input: syn::DeriveInput = parse_quote!(
+            enum Vehicle {
+                Car(Car),
+                Unknown,
+            }
+        );
+
+        assert!(NodeUnionToken::from_derive_input(&input).is_err());
+    }
+
+    #[test]
+    fn test_generic_union_fails() {
+        let input: syn::DeriveInput = parse_quote!(
+            enum Vehicle<T> {
+                Car(Car<T>),
+            }
+        );
+
+        assert!(NodeUnionToken::from_derive_input(&input)
+            .unwrap()
+            .variants()
+            .is_err());
+    }
+}
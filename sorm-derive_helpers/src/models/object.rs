@@ -125,6 +125,12 @@ impl ToTokens for ObjectToken {
                 // }
             }
 
+            impl #impl_generics #crate_name::SurrealFieldType for #struct_name_ident #ty_generics #where_clause {
+                fn field_type() -> #crate_name::FieldType {
+                    #crate_name::FieldType::Object
+                }
+            }
+
             #[allow(non_snake_case)]
             #[derive(#crate_name::serde::Serialize, Debug, Clone, Default)]
             pub struct  #struct_partial_ident #impl_generics #where_clause {
@@ -0,0 +1,239 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::str::FromStr;
+
+use crate::models::*;
+use darling::{ast, util, FromDeriveInput, FromVariant, ToTokens};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+/// Reads the serde attributes of an enum to know how its variants are serialized.
+#[derive(Debug, Clone, FromDeriveInput)]
+#[darling(attributes(serde), supports(enum_any), allow_unknown_fields)]
+pub struct SurrealEnumToken {
+    pub(crate) ident: Ident,
+    pub(crate) generics: syn::Generics,
+    pub data: ast::Data<SurrealEnumVariant, util::Ignored>,
+
+    #[darling(default)]
+    pub(crate) rename_all: Option<Rename>,
+
+    #[darling(default)]
+    pub(crate) tag: Option<String>,
+
+    #[darling(default)]
+    pub(crate) untagged: bool,
+}
+
+#[derive(Debug, Clone, FromVariant)]
+#[darling(attributes(serde), allow_unknown_fields)]
+pub struct SurrealEnumVariant {
+    pub(crate) ident: Ident,
+    pub(crate) fields: ast::Fields<util::Ignored>,
+
+    #[darling(default)]
+    pub(crate) rename: Option<Rename>,
+
+    #[darling(default)]
+    pub(crate) skip: bool,
+
+    #[darling(default)]
+    pub(crate) skip_serializing: bool,
+}
+
+/// The serialized names of the unit variants and the variants carrying data
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SurrealEnumVariants {
+    pub(crate) unit: Vec<String>,
+    pub(crate) data: Vec<String>,
+}
+
+impl SurrealEnumToken {
+    fn casing(&self) -> ExtractorResult<CaseString> {
+        match self.rename_all.as_ref().and_then(|r| r.serialize.as_ref()) {
+            Some(case) => {
+                CaseString::from_str(case).map_err(|e| darling::Error::custom(e.to_string()).into())
+            }
+            None => Ok(CaseString::None),
+        }
+    }
+
+    pub(crate) fn variants(&self) -> ExtractorResult<SurrealEnumVariants> {
+        if self.untagged {
+            return Err(syn::Error::new_spanned(
+                &self.ident,
+                "Untagged enums are not supported since their variants cannot be told apart in the database",
+            )
+            .into());
+        }
+
+        let casing = self.casing()?;
+        let variants = self.data.as_ref().take_enum().ok_or_else(|| {
+            darling::Error::custom("Only enums are supported").with_span(&self.ident)
+        })?;
+
+        let mut surreal_enum_variants = SurrealEnumVariants::default();
+        for variant in variants
+            .into_iter()
+            .filter(|v| !v.skip && !v.skip_serializing)
+        {
+            let name = variant
+                .rename
+                .as_ref()
+                .and_then(|r| r.serialize.clone())
+                .unwrap_or_else(|| casing.apply_to_variant(&variant.ident.to_string()));
+
+            if variant.fields.is_unit() {
+                surreal_enum_variants.unit.push(name);
+            } else {
+                surreal_enum_variants.data.push(name);
+            }
+        }
+
+        Ok(surreal_enum_variants)
+    }
+}
+
+impl ToTokens for SurrealEnumToken {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let crate_name = get_crate_name(false);
+        let enum_ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let SurrealEnumVariants { unit, data } = match self.variants() {
+            Ok(variants) => variants,
+            Err(err) => return tokens.extend(err.write_errors()),
+        };
+        let tag = match &self.tag {
+            Some(tag) => quote!(::std::option::Option::Some(#tag)),
+            None => quote!(::std::option::Option::None),
+        };
+
+        tokens.extend(quote!(
+            impl #impl_generics #crate_name::SurrealEnum for #enum_ident #ty_generics #where_clause {
+                fn unit_variants() -> ::std::vec::Vec<&'static str> {
+                    ::std::vec![#( #unit ),*]
+                }
+
+                fn data_variants() -> ::std::vec::Vec<&'static str> {
+                    ::std::vec![#( #data ),*]
+                }
+
+                fn tag() -> ::std::option::Option<&'static str> {
+                    #tag
+                }
+            }
+
+            impl #impl_generics #crate_name::SurrealFieldType for #enum_ident #ty_generics #where_clause {
+                fn field_type() -> #crate_name::FieldType {
+                    <Self as #crate_name::SurrealEnum>::variants_field_type()
+                }
+
+                fn assertion() -> ::std::option::Option<#crate_name::Filter> {
+                    ::std::option::Option::Some(<Self as #crate_name::SurrealEnum>::variants_assertion())
+                }
+            }
+
+            impl #impl_generics ::std::convert::From<#enum_ident #ty_generics> for #crate_name::sql::Value #where_clause {
+                fn from(value: #enum_ident #ty_generics) -> Self {
+                    #crate_name::sql::to_value(&value)
+                        .expect(concat!("Unable to serialize ", stringify!(#enum_ident), " into a database value"))
+                }
+            }
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn variants(input: syn::DeriveInput) -> SurrealEnumVariants {
+        SurrealEnumToken::from_derive_input(&input)
+            .unwrap()
+            .variants()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_unit_and_data_variants_with_rename_all() {
+        let SurrealEnumVariants { unit, data } = variants(parse_quote!(
+            #[derive(SurrealEnum, Serialize, Deserialize)]
+            #[serde(rename_all = "snake_case")]
+            enum Status {
+                Active,
+                OnLeave,
+                Retired {
+                    year: u16,
+                },
+                #[serde(rename = "transferred")]
+                MovedTo(String),
+                #[serde(skip)]
+                Unknown,
+            }
+        ));
+
+        assert_eq!(unit, vec!["active", "on_leave"]);
+        assert_eq!(data, vec!["retired", "transferred"]);
+    }
+
+    #[test]
+    fn test_variant_casings() {
+        let SurrealEnumVariants { unit, .. } = variants(parse_quote!(
+            #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+            enum Rank {
+                FleetAdmiral,
+            }
+        ));
+        assert_eq!(unit, vec!["FLEET-ADMIRAL"]);
+
+        let SurrealEnumVariants { unit, .. } = variants(parse_quote!(
+            #[serde(rename_all = "camelCase")]
+            enum Rank {
+                FleetAdmiral,
+            }
+        ));
+        assert_eq!(unit, vec!["fleetAdmiral"]);
+
+        let SurrealEnumVariants { unit, .. } = variants(parse_quote!(
+            #[serde(rename_all = "camelCase")]
+            enum Rank {
+                Ωmega,
+            }
+        ));
+        assert_eq!(unit, vec!["Ωmega"]);
+    }
+
+    #[test]
+    fn test_untagged_enum_fails() {
+        let input: syn::DeriveInput = parse_quote!(
+            #[serde(untagged)]
+            enum Status {
+                Active,
+            }
+        );
+
+        assert!(SurrealEnumToken::from_derive_input(&input)
+            .unwrap()
+            .variants()
+            .is_err());
+    }
+
+    #[test]
+    fn test_struct_fails() {
+        let input: syn::DeriveInput = parse_quote!(
+            struct Status {
+                active: bool,
+            }
+        );
+
+        assert!(SurrealEnumToken::from_derive_input(&input).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use crate::models::*;
+use darling::{ast, FromDeriveInput, FromField, ToTokens};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+/// A newtype taking the database type of the type it wraps e.g `struct Email(String)`
+#[derive(Debug, Clone, FromDeriveInput)]
+#[darling(supports(struct_newtype))]
+pub struct SurrealFieldTypeToken {
+    pub(crate) ident: Ident,
+    pub(crate) generics: syn::Generics,
+    pub data: ast::Data<(), NewtypeField>,
+}
+
+#[derive(Debug, Clone, FromField)]
+pub struct NewtypeField {
+    pub(crate) ty: syn::Type,
+}
+
+impl ToTokens for SurrealFieldTypeToken {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let crate_name = get_crate_name(false);
+        let newtype_ident = &self.ident;
+        let (impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let inner_ty = match self.data.as_ref().take_struct() {
+            Some(fields) => &fields.fields[0].ty,
+            None => {
+                return tokens.extend(
+                    darling::Error::custom("Only newtype structs are supported")
+                        .with_span(&self.ident)
+                        .write_errors(),
+                )
+            }
+        };
+
+        tokens.extend(quote!(
+            impl #impl_generics #crate_name::SurrealFieldType for #newtype_ident #ty_generics #where_clause {
+                fn field_type() -> #crate_name::FieldType {
+                    <#inner_ty as #crate_name::SurrealFieldType>::field_type()
+                }
+
+                fn assertion() -> ::std::option::Option<#crate_name::Filter> {
+                    <#inner_ty as #crate_name::SurrealFieldType>::assertion()
+                }
+            }
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_newtype() {
+        let input: syn::DeriveInput = parse_quote!(
+            struct Email(String);
+        );
+        let token = SurrealFieldTypeToken::from_derive_input(&input).unwrap();
+
+        assert!(token
+            .to_token_stream()
+            .to_string()
+            .contains("< String as sorm :: SurrealFieldType > :: field_type ()"));
+    }
+
+    #[test]
+    fn test_struct_with_named_fields_fails() {
+        let input: syn::DeriveInput = parse_quote!(
+            struct Email {
+                address: String,
+            }
+        );
+
+        assert!(SurrealFieldTypeToken::from_derive_input(&input).is_err());
+    }
+}
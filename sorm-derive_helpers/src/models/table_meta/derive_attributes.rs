@@ -17,6 +17,7 @@ use crate::models::*;
 create_ident_wrapper!(StructIdent);
 create_ident_wrapper!(StructPartialIdent);
 create_ident_wrapper!(StructPartialBuilderIdent);
+create_ident_wrapper!(OldTableName);
 
 impl StructIdent {
     pub fn partial_ident(&self) -> StructPartialIdent {
@@ -71,6 +72,21 @@ pub struct TableDeriveAttributes {
 
     #[darling(default)]
     pub(crate) define: Option<AttributeDefine>,
+
+    #[darling(default)]
+    pub(crate) allow_destructive: Option<bool>,
+
+    #[darling(default)]
+    pub(crate) old_name: Option<OldTableName>,
+
+    #[darling(default)]
+    pub(crate) soft_delete: Option<bool>,
+
+    #[darling(multiple)]
+    pub(crate) index: Vec<AttributeIndex>,
+
+    #[darling(multiple)]
+    pub(crate) event: Vec<AttributeEvent>,
 }
 
 impl TableDeriveAttributes {
@@ -91,6 +107,32 @@ impl TableDeriveAttributes {
             .validate_and_return(&self.ident(), &self.relax_table)
     }
 
+    pub fn allow_destructive(&self) -> bool {
+        self.allow_destructive.unwrap_or_default()
+    }
+
+    pub fn soft_delete(&self) -> bool {
+        self.soft_delete.unwrap_or_default()
+    }
+
+    pub fn indexes(&self) -> &[AttributeIndex] {
+        &self.index
+    }
+
+    pub fn events(&self) -> &[AttributeEvent] {
+        &self.event
+    }
+
+    pub fn old_table_name_token(&self) -> TokenStream {
+        match self.old_name.as_ref() {
+            Some(old_name) if !old_name.to_string().is_empty() => {
+                let old_name = old_name.to_string();
+                quote!(::std::option::Option::Some(#old_name.into()))
+            }
+            _ => quote!(::std::option::Option::None),
+        }
+    }
+
     pub fn get_table_definition_token(&self) -> ExtractorResult<TableDefinitions> {
         let TableDeriveAttributes {
             ref drop,
@@ -164,6 +164,30 @@ impl<'a> ModelAttributes<'a> {
         explicit_generics.into()
     }
 
+    pub fn soft_delete(&self) -> bool {
+        match self {
+            ModelAttributes::Node(node) => node.soft_delete(),
+            ModelAttributes::Edge(edge) => edge.soft_delete(),
+            ModelAttributes::Object(_object) => false,
+        }
+    }
+
+    pub fn indexes(&self) -> &[AttributeIndex] {
+        match self {
+            ModelAttributes::Node(node) => node.indexes(),
+            ModelAttributes::Edge(edge) => edge.indexes(),
+            ModelAttributes::Object(_object) => &[],
+        }
+    }
+
+    pub fn events(&self) -> &[AttributeEvent] {
+        match self {
+            ModelAttributes::Node(node) => node.events(),
+            ModelAttributes::Edge(edge) => edge.events(),
+            ModelAttributes::Object(_object) => &[],
+        }
+    }
+
     pub fn to_data_type(&self) -> DataType {
         match self {
             ModelAttributes::Node(_) => DataType::Node,
@@ -0,0 +1,60 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::collections::HashSet;
+
+use quote::quote;
+use sorm_macros_helpers::get_crate_name;
+
+use super::Codegen;
+use crate::models::*;
+
+impl<'a> Codegen<'a> {
+    /// Defines the events declared on the struct with `event(name = "...", when = ..., then = ...)`.
+    /// The `when` and `then` expressions can use `before` and `after`, the model's schema
+    /// prefixed with `$before` and `$after`, and `event`, the `$event` param.
+    pub fn create_table_event_definitions(&mut self) -> ExtractorResult<()> {
+        let crate_name = get_crate_name(false);
+        let table_derive_attrs = self.table_derive_attributes();
+        let struct_ident = table_derive_attrs.ident().into_inner();
+
+        let mut names = HashSet::new();
+        for event in table_derive_attrs.events() {
+            let AttributeEvent { name, when, then } = event;
+
+            if !names.insert(name) {
+                return Err(syn::Error::new_spanned(
+                    &struct_ident,
+                    format!("The event {name} is declared more than once"),
+                )
+                .into());
+            }
+
+            // Each event gets its own bindings so that the schema fields
+            // moved into one event's expressions are available to the next.
+            self.event_definitions.push(
+                quote!({
+                    #[allow(unused_variables)]
+                    let before = #crate_name::before().with_path::<Self>(#crate_name::Empty);
+                    #[allow(unused_variables)]
+                    let after = #crate_name::after().with_path::<Self>(#crate_name::Empty);
+                    #[allow(unused_variables)]
+                    let event = #crate_name::Param::new("event");
+
+                    #crate_name::statements::define_event(#name)
+                        .on_table(#crate_name::Table::from(Self::table()))
+                        .when(#when)
+                        .then(#then)
+                        .to_raw()
+                })
+                .into(),
+            );
+        }
+
+        Ok(())
+    }
+}
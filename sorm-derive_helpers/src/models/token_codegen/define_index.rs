@@ -0,0 +1,199 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use quote::quote;
+use sorm_macros_helpers::get_crate_name;
+
+use super::Codegen;
+use crate::models::*;
+
+impl<'a> Codegen<'a> {
+    /// Defines the indexes declared on a field with `index`, `unique` or `search`
+    pub fn create_field_index_definitions(&mut self) -> ExtractorResult<()> {
+        let field_receiver = self.field_receiver();
+        let index = match (&field_receiver.index, field_receiver.unique) {
+            (Some(index), unique) => Some(AttributeIndex {
+                unique: index.unique || unique,
+                ..index.clone()
+            }),
+            (None, true) => Some(AttributeIndex {
+                unique: true,
+                ..Default::default()
+            }),
+            (None, false) => None,
+        };
+        let search = field_receiver.search.as_ref();
+
+        if index.is_none() && search.is_none() {
+            return Ok(());
+        }
+
+        let ident = field_receiver.ident()?;
+        if self.skip_field()? {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "Only fields stored in the database can be indexed",
+            )
+            .into());
+        }
+
+        let db_field_name =
+            field_receiver.db_field_name(&self.table_derive_attributes().casing()?)?;
+
+        if let Some(index) = index {
+            if !index.fields.0.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "The fields of an index can only be listed on the struct. A field index is always on the field itself",
+                )
+                .into());
+            }
+            if index.unique && index.search.is_some() {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    "An index cannot be both unique and a search index",
+                )
+                .into());
+            }
+
+            let name = index
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("{db_field_name}_idx"));
+            self.index_definitions.push(Self::index_definition(
+                &name,
+                std::slice::from_ref(&db_field_name),
+                &index,
+            ));
+        }
+
+        if let Some(search) = search {
+            let index = AttributeIndex {
+                search: Some(search.clone()),
+                ..Default::default()
+            };
+            self.index_definitions.push(Self::index_definition(
+                &format!("{db_field_name}_search"),
+                std::slice::from_ref(&db_field_name),
+                &index,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Defines the indexes declared on the struct with `index(name = "...", fields = [...])`
+    pub fn create_table_index_definitions(&mut self) -> ExtractorResult<()> {
+        let table_derive_attrs = self.table_derive_attributes();
+        let struct_ident = table_derive_attrs.ident().into_inner();
+        let casing = table_derive_attrs.casing()?;
+        let fields = table_derive_attrs.fields()?;
+
+        for index in table_derive_attrs.indexes() {
+            let Some(name) = index.name.as_ref() else {
+                return Err(syn::Error::new_spanned(
+                    &struct_ident,
+                    "An index on the struct must be named e.g index(name = \"species_idx\", fields = [species])",
+                )
+                .into());
+            };
+
+            if index.fields.0.is_empty() {
+                return Err(syn::Error::new_spanned(
+                    &struct_ident,
+                    format!(
+                        "The index {name} must list the fields it is on e.g fields = [species]"
+                    ),
+                )
+                .into());
+            }
+
+            if index.search.is_some() && index.fields.0.len() > 1 {
+                return Err(syn::Error::new_spanned(
+                    &struct_ident,
+                    format!("The search index {name} can only be on a single field"),
+                )
+                .into());
+            }
+
+            let mut db_field_names = vec![];
+            for field_ident in &index.fields.0 {
+                let field_receiver = fields
+                    .iter()
+                    .find(|field_receiver| {
+                        field_receiver
+                            .ident()
+                            .is_ok_and(|ident| ident.to_string() == field_ident.to_string())
+                    })
+                    .ok_or_else(|| {
+                        syn::Error::new_spanned(
+                            field_ident,
+                            format!("No field named {field_ident} on {struct_ident}"),
+                        )
+                    })?;
+                if field_receiver.skip
+                    || field_receiver.skip_serializing
+                    || field_receiver
+                        .to_relation_type(table_derive_attrs)
+                        .is_relate_graph()
+                {
+                    return Err(syn::Error::new_spanned(
+                        field_ident,
+                        "Only fields stored in the database can be indexed",
+                    )
+                    .into());
+                }
+                db_field_names.push(field_receiver.db_field_name(&casing)?);
+            }
+
+            self.index_definitions
+                .push(Self::index_definition(name, &db_field_names, index));
+        }
+
+        Ok(())
+    }
+
+    fn index_definition(
+        name: &str,
+        db_field_names: &[DbFieldName],
+        index: &AttributeIndex,
+    ) -> DefineIndexStatementToken {
+        let crate_name = get_crate_name(false);
+
+        // Written out since the search analyzer builder does not order the clauses of a search
+        // index the way the database expects
+        if let Some(search) = index.search.as_ref() {
+            let fields = db_field_names
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            let definition = format!(
+                "DEFINE INDEX {name} ON TABLE {{}} FIELDS {fields} {};",
+                search.search_clause()
+            );
+            return quote!(
+                #crate_name::Raw::new(::std::format!(#definition, Self::table()))
+            )
+            .into();
+        }
+
+        let mut define_index_methods = vec![];
+        if index.unique {
+            define_index_methods.push(quote!(.unique()));
+        }
+
+        quote!(
+            #crate_name::statements::define_index(#name)
+                .on_table(#crate_name::Table::from(Self::table()))
+                .fields(::std::vec![#( #crate_name::Field::new(#db_field_names) ),*])
+                #( #define_index_methods )*
+                .to_raw()
+        )
+        .into()
+    }
+}
@@ -46,9 +46,13 @@ impl<'a> Codegen<'a> {
         let mut define_array_field_item_methods = vec![];
         let mut all_field_defintions: Vec<DefineFieldStatementToken> = vec![];
 
-        if let Some(assert) = field_receiver.assert.as_ref() {
-            define_field_methods.push(quote!(.assert(#assert)));
-        }
+        let field_type_assertion = match field_receiver.assert.as_ref() {
+            Some(assert) => {
+                define_field_methods.push(quote!(.assert(#assert)));
+                None
+            }
+            None => field_receiver.surreal_field_type_assertion(model_attributes)?,
+        };
 
         if let Some(item_assert) = field_receiver.item_assert.as_ref() {
             define_array_field_item_methods.push(quote!(.assert(#item_assert)));
@@ -58,6 +62,18 @@ impl<'a> Codegen<'a> {
             define_field_methods.push(quote!(.value(#value)));
         }
 
+        if field_receiver.created_at {
+            define_field_methods.push(quote!(.computed_value(#crate_name::ValueLike::new(
+                #crate_name::Raw::new("$before OR time::now()")
+            ))));
+        }
+
+        if field_receiver.updated_at {
+            define_field_methods.push(quote!(.computed_value(#crate_name::ValueLike::new(
+                #crate_name::Raw::new("time::now()")
+            ))));
+        }
+
         if let Some(permissions) = field_receiver.permissions.as_ref() {
             define_field_methods.push(permissions.into_token_stream());
         }
@@ -67,9 +83,21 @@ impl<'a> Codegen<'a> {
             .on_table(#crate_name::Table::from(Self::table()))
             .type_(#field_type_in_db_token)
             #( # define_field_methods) *
-            .to_raw()
         );
 
+        // Only known once the field type's `SurrealFieldType` implementation is evaluated
+        let main_field_def = match field_type_assertion {
+            Some(field_type_assertion) => quote!({
+                let definition = #main_field_def;
+                match #field_type_assertion {
+                    ::std::option::Option::Some(assertion) => definition.assert(assertion),
+                    ::std::option::Option::None => definition,
+                }
+                .to_raw()
+            }),
+            None => quote!(#main_field_def.to_raw()),
+        };
+
         all_field_defintions.push(main_field_def.into());
 
         if !define_array_field_item_methods.is_empty() {
@@ -121,6 +149,8 @@ impl<'a> Codegen<'a> {
             permissions,
             item_assert,
             relate,
+            created_at,
+            updated_at,
             ..
         } = field_receiver;
         let ident = field_receiver.ident()?;
@@ -162,6 +192,33 @@ impl<'a> Codegen<'a> {
             .into());
         }
 
+        if *created_at && *updated_at {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "A field cannot be both `created_at` and `updated_at`",
+            )
+            .into());
+        }
+
+        if field_receiver.is_timestamp() {
+            let relation_type = field_receiver.to_relation_type(self.table_derive_attributes());
+            if define.is_some()
+                || value.is_some()
+                || db_field_name.is_id()
+                || field_receiver.skip
+                || field_receiver.skip_serializing
+                || !matches!(relation_type, RelationType::None)
+            {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    r#"A `created_at` or `updated_at` field is set by the database. It must be a serialized datetime field that is neither the id nor a link or relation and does not allow the following attributes:
+    define,
+    value"#,
+                )
+                .into());
+            }
+        }
+
         Ok(())
     }
 }
@@ -187,7 +187,7 @@ impl<'a> Codegen<'a> {
                     foreign_node.get_generics_from_current_struct(model_attributes)?;
                 (
                     Some(generics_meta),
-                    Some(quote!(<#foreign_node as #crate_name::Model>::Id)),
+                    Some(quote!(<#foreign_node as #crate_name::Linkable>::LinkId)),
                 )
             }
             RelationType::LinkManyInAndOutEdgeNodesInert(_foreign_node_generics) => {
@@ -175,7 +175,7 @@ impl<'a> Codegen<'a> {
         Ok(LinkMethodMeta {
             foreign_node_schema_import: foreign_node_schema_import.into(),
             foreign_node_type_validator: quote!(
-                #crate_name::validators::assert_impl_one!(#link_one: #crate_name::Node);
+                #crate_name::validators::assert_impl_one!(#link_one: #crate_name::Linkable);
             )
             .into(),
             link_field_method: record_link_default_alias_as_method.into(),
@@ -228,7 +228,7 @@ impl<'a> Codegen<'a> {
             foreign_node_schema_import: foreign_node_schema_import.into(),
             link_field_method: link_field_method.into(),
             foreign_node_type_validator: quote!(
-                #crate_name::validators::assert_impl_one!(#link_many_node_type: #crate_name::Node);
+                #crate_name::validators::assert_impl_one!(#link_many_node_type: #crate_name::Linkable);
             )
             .into(),
         })
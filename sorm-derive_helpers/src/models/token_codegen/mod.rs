@@ -6,6 +6,8 @@
  */
 
 mod aliases;
+mod define_event;
+mod define_index;
 mod define_statement;
 mod field_connection_build;
 mod field_metadata;
@@ -79,6 +81,13 @@ pub struct Codegen<'a> {
     pub link_one_and_self_fields: Vec<LinkOneAndSelfField>,
     /// The names of link_many fields
     pub link_many_fields: Vec<LinkManyField>,
+    /// Match arms from the link field names to the models they point to
+    /// e.g "spaceShips" => Some(<SpaceShip as Linkable>::linked_model()),
+    pub linked_models: Vec<LinkedModelToken>,
+    /// The field marked as the version of the record e.g Field::new("version")
+    pub version_field: Option<VersionField>,
+    /// The field holding when a record of a soft deleted table was deleted i.e Field::new("deleted_at")
+    pub soft_delete_field: Option<SoftDeleteField>,
     /// Generated example: pub timeWritten: Field,
     /// key(normalized_field_name)-value(Field) e.g pub out: Field, of field name and Field type
     /// to build up struct for generating fields of a Schema of the Edge
@@ -213,6 +222,10 @@ pub struct Codegen<'a> {
     /// ```
     pub record_link_fields_methods: Vec<LinkFieldTraversalMethodToken>,
     pub field_definitions: Vec<DefineFieldStatementToken>,
+    /// Definitions of the indexes declared on the fields and the struct
+    pub index_definitions: Vec<DefineIndexStatementToken>,
+    /// Definitions of the events declared on the struct
+    pub event_definitions: Vec<DefineEventStatementToken>,
     pub field_metadata: Vec<FieldMetadataToken>,
     pub node_edge_metadata: NodeEdgeMetadataLookupTable<'a>,
     pub fields_relations_aliased: Vec<FieldsRelationsAliased>,
@@ -298,7 +311,9 @@ impl<'a> Codegen<'a> {
 
             tokens_generator.create_table_id_type_token()?;
             tokens_generator.create_field_definitions()?;
+            tokens_generator.create_field_index_definitions()?;
             tokens_generator.create_db_field_names_token()?;
+            tokens_generator.create_version_field_token()?;
             tokens_generator.create_field_type_static_assertion_token()?;
             tokens_generator.create_field_setter_impl()?;
             tokens_generator.create_field_metadata_token()?;
@@ -309,6 +324,9 @@ impl<'a> Codegen<'a> {
             tokens_generator.create_relation_aliases_struct_fields_types_kv()?;
             tokens_generator.create_struct_partial_metadata()?;
         }
+        tokens_generator.create_soft_delete_field_token()?;
+        tokens_generator.create_table_index_definitions()?;
+        tokens_generator.create_table_event_definitions()?;
         Ok(tokens_generator)
     }
 }
@@ -5,7 +5,7 @@
  * Licensed under the MIT license
  */
 
-use quote::quote;
+use quote::{quote, ToTokens};
 use sorm_macros_helpers::get_crate_name;
 
 use crate::models::*;
@@ -26,24 +26,29 @@ impl<'a> Codegen<'a> {
         }
 
         let serialized_field_fmt = || quote!(#crate_name::Field::new(#db_field_name));
+        let linked_model_fmt = |model: &dyn ToTokens| quote!(#db_field_name => ::std::option::Option::Some(<#model as #crate_name::Linkable>::linked_model()));
 
         if !field_receiver.skip_serializing && !field_receiver.skip {
             match relation_type {
-                RelationType::LinkOne(_) => {
+                RelationType::LinkOne(link_one) => {
                     self.link_one_fields.push(serialized_field_fmt().into());
                     self.link_one_and_self_fields
                         .push(serialized_field_fmt().into());
                     self.linked_fields.push(serialized_field_fmt().into());
+                    self.linked_models.push(linked_model_fmt(&link_one).into());
                 }
-                RelationType::LinkSelf(_) => {
+                RelationType::LinkSelf(link_self) => {
                     self.link_self_fields.push(serialized_field_fmt().into());
                     self.link_one_and_self_fields
                         .push(serialized_field_fmt().into());
                     self.linked_fields.push(serialized_field_fmt().into());
+                    let link_self = link_self.to_linkone_attr_type(table_derive_attrs)?;
+                    self.linked_models.push(linked_model_fmt(&link_self).into());
                 }
-                RelationType::LinkMany(_) => {
+                RelationType::LinkMany(link_many) => {
                     self.link_many_fields.push(serialized_field_fmt().into());
                     self.linked_fields.push(serialized_field_fmt().into());
+                    self.linked_models.push(linked_model_fmt(&link_many).into());
                 }
                 _ => {}
             }
@@ -6,6 +6,7 @@
  */
 
 use quote::quote;
+use sorm_macros_helpers::get_crate_name;
 
 use crate::models::*;
 
@@ -37,4 +38,81 @@ impl<'a> Codegen<'a> {
 
         Ok(())
     }
+
+    pub fn create_version_field_token(&mut self) -> ExtractorResult<()> {
+        let crate_name = get_crate_name(false);
+        let field_receiver = self.field_receiver();
+        if !field_receiver.version {
+            return Ok(());
+        }
+
+        let table_derive_attrs = self.table_derive_attributes();
+        let db_field_name = field_receiver.db_field_name(&table_derive_attrs.casing()?)?;
+
+        if self.version_field.is_some() {
+            return Err(syn::Error::new_spanned(
+                field_receiver.ident()?,
+                "Only one field of a model can be the version field",
+            )
+            .into());
+        }
+
+        if db_field_name.is_id()
+            || field_receiver.skip
+            || field_receiver.skip_serializing
+            || !matches!(
+                field_receiver.to_relation_type(table_derive_attrs),
+                RelationType::None
+            )
+        {
+            return Err(syn::Error::new_spanned(
+                field_receiver.ident()?,
+                "The version field must be a serialized integer field that is neither the id nor a link or relation",
+            )
+            .into());
+        }
+
+        self.version_field = Some(quote!(#crate_name::Field::new(#db_field_name)).into());
+        Ok(())
+    }
+
+    /// Defines the `deleted_at` field of a soft deleted table unless the struct has it already.
+    pub fn create_soft_delete_field_token(&mut self) -> ExtractorResult<()> {
+        let crate_name = get_crate_name(false);
+        let table_derive_attrs = self.table_derive_attributes();
+        if !table_derive_attrs.soft_delete() {
+            return Ok(());
+        }
+
+        let casing = table_derive_attrs.casing()?;
+        let mut has_soft_delete_field = false;
+        for field_receiver in table_derive_attrs.fields()? {
+            has_soft_delete_field |= field_receiver
+                .db_field_name(&casing)?
+                .is_soft_delete_field();
+        }
+
+        if !has_soft_delete_field {
+            let definition = quote!(
+                #crate_name::statements::define_field(#crate_name::Field::new("deleted_at"))
+                    .on_table(#crate_name::Table::from(Self::table()))
+                    .type_(#crate_name::FieldType::Option(::std::boxed::Box::new(
+                        #crate_name::FieldType::Datetime
+                    )))
+                    .to_raw()
+            );
+            self.field_definitions.push(definition.clone().into());
+            self.field_metadata.push(
+                quote!(#crate_name::FieldMetadata {
+                    name: "deleted_at".into(),
+                    old_name: ::std::option::Option::None,
+                    definition: ::std::vec![#definition]
+                })
+                .into(),
+            );
+        }
+
+        self.soft_delete_field = Some(quote!(#crate_name::Field::new("deleted_at")).into());
+        Ok(())
+    }
 }
@@ -23,6 +23,12 @@ impl<'a> Codegen<'a> {
         let field_ident_original = field_receiver.ident()?;
         let db_field_name = field_receiver.db_field_name(&model_attributes.casing()?)?;
 
+        // Timestamps are set by the database and cannot be updated
+        if field_receiver.is_timestamp() {
+            self.insert_renamed_serialized_fields_kv()?;
+            return Ok(());
+        }
+
         let maybe_fn_path = format!("{crate_name}::Maybe::is_none");
         match field_receiver.to_relation_type(model_attributes) {
             RelationType::None
@@ -10,11 +10,13 @@ use migration::generate_embedded_migrations;
 use proc_macro::TokenStream;
 
 /// embed_migrations!() is a macro that embeds migrations in the binary at compile time.
-/// It takes 2 arguments:
+/// It takes 3 arguments:
 /// 1. The path to the migrations directory
-/// 3. The migration mode
+/// 2. The migration mode
+/// 3. The Rust data migrations registry
 /// The path to the migrations directory is optional. If not provided, it defaults to 'migrations'.
 /// The migration mode is optional. If not provided, it defaults to Mode::Strict.
+/// The data migrations registry is optional. It must be a `DataMigrations` constant.
 ///
 /// # Example
 /// ```rust, ignore
@@ -29,6 +31,10 @@ use proc_macro::TokenStream;
 /// const MIGRATIONS_TWO_WAY: sorm-migrator::EmbeddedMigrationsTwoWay = embed_migrations!();
 /// const MIGRATIONS_TWO_WAY: sorm-migrator::EmbeddedMigrationsTwoWay = embed_migrations!("migrations");
 /// const MIGRATIONS_TWO_WAY: sorm-migrator::EmbeddedMigrationsTwoWay = embed_migrations!("migrations", strict);
+///
+/// // Embed migrations with Rust data migrations run in order with them
+/// const DATA_MIGRATIONS: sorm-migrator::DataMigrations = sorm-migrator::DataMigrations::new(&[]);
+/// const MIGRATIONS_WITH_DATA: sorm-migrator::EmbeddedMigrationsTwoWay = embed_migrations!("migrations", strict, DATA_MIGRATIONS);
 /// ```
 #[proc_macro]
 pub fn embed_migrations(input: TokenStream) -> TokenStream {
@@ -15,13 +15,21 @@ use syn::{
     Token,
 };
 
-fn generate_migration_code(custom_path: Option<String>, mode: Mode) -> proc_macro2::TokenStream {
+fn generate_migration_code(
+    custom_path: Option<String>,
+    mode: Mode,
+    data_migrations: Option<Expr>,
+) -> proc_macro2::TokenStream {
     let mut files_config = MigrationConfig::new().set_mode(mode);
     if let Some(custom_path) = custom_path {
         files_config = files_config.set_custom_path(custom_path);
     }
 
     let crate_name = get_crate_name(false);
+    let data_migrations = data_migrations.map_or_else(
+        || quote!(#crate_name::migrator::DataMigrations::new(&[])),
+        |expr| quote!(#expr),
+    );
     let flag = files_config
         .detect_migration_type()
         .expect("Failed to detect migration type");
@@ -44,7 +52,10 @@ fn generate_migration_code(custom_path: Option<String>, mode: Mode) -> proc_macr
                     ))
                 })
                 .collect::<Vec<_>>();
-            quote!(#crate_name::migrator::EmbeddedMigrationsOneWay::new(&[#(#config),*]))
+            quote!(#crate_name::migrator::EmbeddedMigrationsOneWay::with_data_migrations(
+                &[#(#config),*],
+                #data_migrations
+            ))
         }
         MigrationFlag::TwoWay => {
             let config = files_config
@@ -71,7 +82,10 @@ fn generate_migration_code(custom_path: Option<String>, mode: Mode) -> proc_macr
                 })
                 .collect::<Vec<_>>();
             quote!(
-                    #crate_name::migrator::EmbeddedMigrationsTwoWay::new(&[#(#config),*])
+                    #crate_name::migrator::EmbeddedMigrationsTwoWay::with_data_migrations(
+                        &[#(#config),*],
+                        #data_migrations
+                    )
             )
         }
     };
@@ -134,9 +148,11 @@ pub fn generate_embedded_migrations(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as Args);
 
     if input.args.len() > 3 {
-        panic!("Too many arguments. Expected 2 or less");
+        panic!("Too many arguments. Expected 3 or less");
     }
 
+    // The data migrations registry is passed through as is
+    let data_migrations = input.args.iter().nth(2).cloned();
     let mut args = parse_args(input).into_iter();
 
     let custom_path = args.next().flatten().clone();
@@ -147,7 +163,7 @@ pub fn generate_embedded_migrations(input: TokenStream) -> TokenStream {
             .expect("Invalid mode")
     });
 
-    generate_migration_code(custom_path, mode).into()
+    generate_migration_code(custom_path, mode, data_migrations).into()
 }
 
 #[cfg(test)]
@@ -0,0 +1,180 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use sorm::migrator::{
+    DataMigration, DataMigrations, DriftReport, FastForwardDelta, Migration, Mode, UpdateStrategy,
+};
+use sorm_migrator_tests::{current_function, TestConfig};
+use sorm_models::migrations::ResourcesV10;
+use tempfile::tempdir;
+use test_case::test_case;
+
+fn backfill_after(conf: &TestConfig, position: u8, reversible: bool) -> DataMigrations {
+    let after = conf.get_either_filename_type_at_position(position, reversible);
+    let name = format!("{}_backfill.rs", after.timestamp().into_inner() + 1);
+
+    vec![DataMigration::new(
+        Box::leak(name.into_boxed_str()),
+        "1",
+        |db| {
+            Box::pin(async move {
+                let mut response = db.query("SELECT count() FROM migration GROUP ALL").await?;
+                let applied: Option<i64> = response.take((0, "count"))?;
+                db.query("CREATE backfill:run SET applied_before = $applied")
+                    .bind(("applied", applied.unwrap_or_default()))
+                    .await?
+                    .check()?;
+                Ok(())
+            })
+        },
+    )]
+    .into()
+}
+
+async fn applied_before(conf: &TestConfig) -> Option<i64> {
+    conf.migrator
+        .db()
+        .query("SELECT VALUE applied_before FROM backfill:run")
+        .await
+        .expect("Failed to query backfill")
+        .take(0)
+        .expect("Failed to take backfill")
+}
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, true; "Reversible Lax")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_data_migration_runs_in_order_with_file_migrations(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    conf.generate_12_test_migrations_reversible(reversible)
+        .await;
+    let data_migrations = backfill_after(&conf, 6, reversible);
+    let backfill_name = data_migrations.get_migrations()[0]
+        .name()
+        .expect("Invalid data migration name");
+    conf.migrator.set_data_migrations(data_migrations);
+
+    conf.run_up(&FastForwardDelta::builder().number(6).build())
+        .await;
+    assert_eq!(applied_before(&conf).await, None);
+
+    conf.run_up(&FastForwardDelta::builder().number(1).build())
+        .await;
+    assert_eq!(applied_before(&conf).await, Some(6));
+    let latest = Migration::get_latest(conf.migrator.db())
+        .await
+        .expect("No migration applied");
+    assert_eq!(latest.name, backfill_name.to_string());
+
+    conf.run_up(&FastForwardDelta::default()).await;
+    let migrations = Migration::get_all_desc(conf.migrator.db()).await;
+    assert_eq!(migrations.len(), 13);
+
+    let report = DriftReport::detect(
+        conf.migrator.db(),
+        &conf.migrator.file_manager(),
+        &ResourcesV10,
+    )
+    .await
+    .expect("Failed to detect drift");
+    assert!(report.migration_files.is_empty(), "{report}");
+}
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[tokio::test]
+async fn test_plan_marks_data_migrations_as_run_separately(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    conf.generate_12_test_migrations_reversible(reversible)
+        .await;
+    let data_migrations = backfill_after(&conf, 6, reversible);
+    let backfill_name = data_migrations.get_migrations()[0]
+        .name()
+        .expect("Invalid data migration name");
+    conf.migrator.set_data_migrations(data_migrations);
+
+    let db = conf.migrator.db();
+    let file_manager = conf.migrator.file_manager();
+    let plan = if reversible {
+        file_manager
+            .two_way()
+            .plan_up_pending_migrations(db.clone(), UpdateStrategy::Number(7))
+            .await
+    } else {
+        file_manager
+            .one_way()
+            .plan_pending_migrations(db.clone(), UpdateStrategy::Number(7))
+            .await
+    }
+    .expect("Failed to plan migrations");
+
+    assert_eq!(plan.files.len(), 7);
+    assert_eq!(plan.data_migrations, vec![backfill_name.clone()]);
+    assert!(plan.to_string().contains(&format!(
+        "-- {backfill_name} (data migration, run separately)\n"
+    )));
+    let transaction = plan.transaction().build();
+    assert!(!transaction.contains(&backfill_name.to_string()));
+    assert_eq!(transaction.matches("CREATE migration:").count(), 6);
+    assert!(Migration::get_all_desc(db.clone()).await.is_empty());
+}
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[tokio::test]
+async fn test_failed_data_migration_is_not_registered(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    conf.generate_12_test_migrations_reversible(reversible)
+        .await;
+    let after = conf.get_either_filename_type_at_position(12, reversible);
+    let name = format!("{}_backfill.rs", after.timestamp().into_inner() + 1);
+    conf.migrator.set_data_migrations(
+        vec![DataMigration::new(
+            Box::leak(name.into_boxed_str()),
+            "1",
+            |db| {
+                Box::pin(async move {
+                    db.query("CREATE backfill:run SET applied_before = 12")
+                        .await?
+                        .check()?;
+                    // Fails after the first write, which is kept
+                    db.query("CREATE backfill:run SET applied_before = 13")
+                        .await?
+                        .check()?;
+                    Ok(())
+                })
+            },
+        )]
+        .into(),
+    );
+
+    let db = conf.migrator.db();
+    let file_manager = conf.migrator.file_manager();
+    let run = if reversible {
+        file_manager
+            .two_way()
+            .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+            .await
+    } else {
+        file_manager
+            .one_way()
+            .run_pending_migrations(db.clone(), UpdateStrategy::Latest)
+            .await
+    };
+    assert!(run.is_err());
+
+    assert_eq!(applied_before(&conf).await, Some(12));
+    assert_eq!(Migration::get_all_desc(db.clone()).await.len(), 12);
+}
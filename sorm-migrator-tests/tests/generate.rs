@@ -5,10 +5,15 @@
  * Licensed under the MIT license
  */
 
+use std::collections::HashMap;
+
 use sorm::{
     create_table_resources,
-    migrator::{Generate, Init, MockPrompter, Mode, RenameOrDelete},
-    DbResources,
+    migrator::{
+        DestructiveChangePolicy, Generate, Init, MigrationError, MockPrompter, Mode,
+        RenameOrDelete, RollbackStrategyStruct,
+    },
+    DbResources, FieldMetadata, Model, Raw, Table,
 };
 use sorm_migrator_tests::{current_function, AssertionArg, TestConfig};
 use sorm_models::migrations::{
@@ -923,3 +928,530 @@ async fn test_should_panic_if_renaming_from_non_existing_field_in_migration_dire
     .await;
     assert!(migration_dir.exists());
 }
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, true; "Reversible Lax")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_destructive_change_policy(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    #[derive(Debug, Clone)]
+    pub struct ResourcesV1;
+    impl DbResources for ResourcesV1 {
+        create_table_resources!(Animal, Planet);
+    }
+    // Same as V2 but acknowledging the destructive changes on animal only
+    #[derive(Debug, Clone)]
+    pub struct ResourcesV2AnimalAcknowledged;
+    impl DbResources for ResourcesV2AnimalAcknowledged {
+        fn tables(&self) -> Vec<Raw> {
+            ResourcesV2.tables()
+        }
+
+        fn tables_fields_meta(&self) -> HashMap<Table, Vec<FieldMetadata>> {
+            ResourcesV2.tables_fields_meta()
+        }
+
+        fn tables_allowing_destructive_changes(&self) -> Vec<Table> {
+            vec![AnimalV2::table()]
+        }
+    }
+
+    // Deleting planet's firstName rather than renaming it loses its data
+    let mock_prompter = MockPrompter::builder()
+        .allow_empty_migrations_gen(true)
+        .rename_or_delete_single_field_change(RenameOrDelete::Delete)
+        .build();
+
+    conf.run_init(
+        Init::builder()
+            .reversible(reversible)
+            .name("migration init".into())
+            .run(false)
+            .build(),
+        ResourcesV1,
+        mock_prompter,
+    )
+    .await;
+    let files_count = conf.read_migrations_from_dir_sorted_asc().len();
+    let generate = |policy: DestructiveChangePolicy| {
+        let file_manager = conf
+            .migrator
+            .file_manager()
+            .set_destructive_change_policy(policy);
+        async move {
+            if reversible {
+                file_manager
+                    .two_way()
+                    .generate_migrations(
+                        &"remove fields".into(),
+                        ResourcesV2AnimalAcknowledged,
+                        mock_prompter,
+                    )
+                    .await
+            } else {
+                file_manager
+                    .one_way()
+                    .generate_migrations(
+                        &"remove fields".into(),
+                        ResourcesV2AnimalAcknowledged,
+                        mock_prompter,
+                    )
+                    .await
+            }
+        }
+    };
+
+    let rejected = generate(DestructiveChangePolicy::Fail).await;
+    match rejected {
+        Err(MigrationError::DestructiveChangesRejected { changes, .. }) => {
+            assert!(changes.contains("[destructive] REMOVE FIELD firstName ON TABLE planet;"));
+            assert!(changes.contains("[destructive] REMOVE FIELD createdAt ON TABLE animal;"));
+        }
+        other => panic!("Expected destructive changes to be rejected. Got: {other:?}"),
+    }
+
+    let rejected = generate(DestructiveChangePolicy::RequireAck).await;
+    match rejected {
+        Err(MigrationError::DestructiveChangesRejected { changes, .. }) => {
+            assert!(changes.contains("ON TABLE planet"));
+            assert!(!changes.contains("ON TABLE animal"));
+        }
+        other => panic!("Expected unacknowledged changes to be rejected. Got: {other:?}"),
+    }
+    assert_eq!(
+        conf.read_migrations_from_dir_sorted_asc().len(),
+        files_count
+    );
+
+    generate(DestructiveChangePolicy::Warn)
+        .await
+        .expect("Warn policy should generate the migration");
+    assert!(conf.read_migrations_from_dir_sorted_asc().len() > files_count);
+}
+
+// Animal table defined with raw field types so the types can change between versions
+#[derive(Debug, Clone)]
+struct AnimalFieldTypes {
+    age: &'static str,
+    score: &'static str,
+}
+
+impl DbResources for AnimalFieldTypes {
+    fn tables(&self) -> Vec<Raw> {
+        vec![
+            Raw::new("DEFINE TABLE animal SCHEMAFULL"),
+            Raw::new(format!(
+                "DEFINE FIELD age ON TABLE animal TYPE {}",
+                self.age
+            )),
+            Raw::new(format!(
+                "DEFINE FIELD score ON TABLE animal TYPE {}",
+                self.score
+            )),
+        ]
+    }
+}
+
+async fn animals(conf: &TestConfig) -> String {
+    let animals: sorm::sql::Value = conf
+        .migrator
+        .db()
+        .query("SELECT id, age, score FROM animal ORDER BY id")
+        .await
+        .expect("Failed to query animals")
+        .take(0)
+        .expect("Failed to take animals");
+    animals.to_string()
+}
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, true; "Reversible Lax")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_field_type_change_converts_existing_data(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    let mock_prompter = MockPrompter::builder()
+        .allow_empty_migrations_gen(true)
+        .rename_or_delete_single_field_change(RenameOrDelete::Rename)
+        .required_field_default("0")
+        .build();
+
+    conf.run_init(
+        Init::builder()
+            .reversible(reversible)
+            .name("migration init".into())
+            .run(true)
+            .build(),
+        AnimalFieldTypes {
+            age: "int",
+            score: "option<int>",
+        },
+        mock_prompter,
+    )
+    .await;
+    conf.migrator
+        .db()
+        .query("CREATE animal:1 SET age = 3; CREATE animal:2 SET age = 5, score = 8")
+        .await
+        .expect("Failed to create animals")
+        .check()
+        .expect("Failed to create animals");
+
+    conf.run_gen(
+        Generate::builder()
+            .name("change types".into())
+            .run(true)
+            .build(),
+        AnimalFieldTypes {
+            age: "string",
+            score: "int",
+        },
+        mock_prompter,
+    )
+    .await;
+    assert_eq!(
+        animals(&conf).await,
+        "[{ age: '3', id: animal:1, score: 0 }, { age: '5', id: animal:2, score: 8 }]"
+    );
+
+    if reversible {
+        conf.run_down(
+            &RollbackStrategyStruct::builder().previous(true).build(),
+            false,
+        )
+        .await;
+        assert_eq!(
+            animals(&conf).await,
+            "[{ age: 3, id: animal:1, score: 0 }, { age: 5, id: animal:2, score: 8 }]"
+        );
+    }
+}
+
+// Animal table linked from crop whose name can change between versions
+#[derive(Debug, Clone)]
+struct AnimalRenamed {
+    table: &'static str,
+    old_name: Option<&'static str>,
+}
+
+impl DbResources for AnimalRenamed {
+    fn tables(&self) -> Vec<Raw> {
+        let table = self.table;
+        vec![
+            Raw::new(format!("DEFINE TABLE {table} SCHEMAFULL")),
+            Raw::new(format!("DEFINE FIELD name ON TABLE {table} TYPE string")),
+            Raw::new(format!(
+                "DEFINE FIELD friend ON TABLE {table} TYPE option<record<{table}>>"
+            )),
+            Raw::new("DEFINE TABLE crop SCHEMAFULL"),
+            Raw::new(format!(
+                "DEFINE FIELD eater ON TABLE crop TYPE option<record<{table}>>"
+            )),
+            Raw::new(format!(
+                "DEFINE FIELD eaters ON TABLE crop TYPE array<record<{table}>>"
+            )),
+        ]
+    }
+
+    fn tables_old_names(&self) -> HashMap<Table, Table> {
+        self.old_name
+            .map(|old_name| HashMap::from([(self.table.into(), old_name.into())]))
+            .unwrap_or_default()
+    }
+}
+
+async fn animals_and_crops(conf: &TestConfig, table: &str) -> (String, String) {
+    let mut response = conf
+        .migrator
+        .db()
+        .query(format!(
+            "SELECT * FROM {table} ORDER BY id; SELECT * FROM crop ORDER BY id"
+        ))
+        .await
+        .expect("Failed to query animals and crops");
+    let animals: sorm::sql::Value = response.take(0).expect("Failed to take animals");
+    let crops: sorm::sql::Value = response.take(1).expect("Failed to take crops");
+    (animals.to_string(), crops.to_string())
+}
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, true; "Reversible Lax")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_table_rename_keeps_data_and_links(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    let mock_prompter = MockPrompter::default();
+
+    conf.run_init(
+        Init::builder()
+            .reversible(reversible)
+            .name("migration init".into())
+            .run(true)
+            .build(),
+        AnimalRenamed {
+            table: "animal",
+            old_name: None,
+        },
+        mock_prompter,
+    )
+    .await;
+    conf.migrator
+        .db()
+        .query(
+            "CREATE animal:a SET name = 'a'; \
+            CREATE animal:b SET name = 'b', friend = animal:a; \
+            CREATE crop:1 SET eater = animal:b, eaters = [animal:a, animal:b]",
+        )
+        .await
+        .expect("Failed to create animals")
+        .check()
+        .expect("Failed to create animals");
+
+    let file_manager = conf
+        .migrator
+        .file_manager()
+        .set_destructive_change_policy(DestructiveChangePolicy::Fail);
+    let renamed = AnimalRenamed {
+        table: "beast",
+        old_name: Some("animal"),
+    };
+    let rejected = if reversible {
+        file_manager
+            .two_way()
+            .generate_migrations(&"rename animal".into(), renamed, mock_prompter)
+            .await
+    } else {
+        file_manager
+            .one_way()
+            .generate_migrations(&"rename animal".into(), renamed, mock_prompter)
+            .await
+    };
+    match rejected {
+        Err(MigrationError::DestructiveChangesRejected { changes, .. }) => {
+            assert!(
+                changes.contains("[destructive] REMOVE TABLE animal;"),
+                "{changes}"
+            );
+            assert!(!changes.contains("INSERT INTO beast"), "{changes}");
+            assert!(!changes.contains("UPDATE crop"), "{changes}");
+        }
+        other => panic!("Expected the old table removal to be rejected. Got: {other:?}"),
+    }
+
+    conf.run_gen(
+        Generate::builder()
+            .name("rename animal".into())
+            .run(true)
+            .build(),
+        AnimalRenamed {
+            table: "beast",
+            old_name: Some("animal"),
+        },
+        mock_prompter,
+    )
+    .await;
+    assert_eq!(
+        animals_and_crops(&conf, "beast").await,
+        (
+            "[{ id: beast:a, name: 'a' }, { friend: beast:a, id: beast:b, name: 'b' }]".into(),
+            "[{ eater: beast:b, eaters: [beast:a, beast:b], id: crop:1 }]".into()
+        )
+    );
+    assert_eq!(animals_and_crops(&conf, "animal").await.0, "[]");
+
+    if reversible {
+        conf.run_down(
+            &RollbackStrategyStruct::builder().previous(true).build(),
+            false,
+        )
+        .await;
+        assert_eq!(
+            animals_and_crops(&conf, "animal").await,
+            (
+                "[{ id: animal:a, name: 'a' }, { friend: animal:a, id: animal:b, name: 'b' }]"
+                    .into(),
+                "[{ eater: animal:b, eaters: [animal:a, animal:b], id: crop:1 }]".into()
+            )
+        );
+    }
+}
+
+#[test_case(Mode::Strict; "Strict")]
+#[test_case(Mode::Lax; "Lax")]
+#[tokio::test]
+async fn test_table_rename_from_table_in_use_disallowed(mode: Mode) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    conf.run_init(
+        Init::builder()
+            .reversible(true)
+            .name("migration init".into())
+            .run(false)
+            .build(),
+        AnimalRenamed {
+            table: "animal",
+            old_name: None,
+        },
+        MockPrompter::default(),
+    )
+    .await;
+
+    let result = conf
+        .migrator
+        .file_manager()
+        .two_way()
+        .generate_migrations(
+            &"rename crop".into(),
+            AnimalRenamed {
+                table: "animal",
+                old_name: Some("crop"),
+            },
+            MockPrompter::default(),
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(MigrationError::CannotRenameFromOldTableInUse { .. })
+    ));
+}
+
+// Gadget table whose schema is tightened between versions, with a legacy table that is dropped
+#[derive(Debug, Clone)]
+struct GadgetResources {
+    v2: bool,
+}
+
+impl DbResources for GadgetResources {
+    fn tables(&self) -> Vec<Raw> {
+        if self.v2 {
+            return vec![
+                Raw::new("DEFINE TABLE gadget SCHEMAFULL"),
+                Raw::new("DEFINE FIELD title ON TABLE gadget TYPE string"),
+            ];
+        }
+
+        vec![
+            Raw::new("DEFINE TABLE gadget SCHEMALESS"),
+            Raw::new("DEFINE FIELD name ON TABLE gadget TYPE string"),
+            Raw::new("DEFINE INDEX name_idx ON TABLE gadget COLUMNS name"),
+            Raw::new("DEFINE EVENT name_changed ON TABLE gadget WHEN $before.name != $after.name THEN (CREATE log SET name = $after.name)"),
+            Raw::new("DEFINE TABLE legacy SCHEMALESS"),
+        ]
+    }
+
+    fn tables_fields_meta(&self) -> HashMap<Table, Vec<FieldMetadata>> {
+        if !self.v2 {
+            return HashMap::default();
+        }
+
+        HashMap::from([(
+            Table::from("gadget"),
+            vec![FieldMetadata {
+                name: "title".into(),
+                old_name: Some("name".into()),
+                definition: vec![Raw::new("DEFINE FIELD title ON TABLE gadget TYPE string")],
+            }],
+        )])
+    }
+}
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, true; "Reversible Lax")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_destructive_changes_of_renames_removals_and_schemafull_tables(
+    mode: Mode,
+    reversible: bool,
+) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    let mock_prompter = MockPrompter::builder()
+        .allow_empty_migrations_gen(true)
+        .rename_or_delete_single_field_change(RenameOrDelete::Rename)
+        .build();
+
+    conf.run_init(
+        Init::builder()
+            .reversible(reversible)
+            .name("migration init".into())
+            .run(false)
+            .build(),
+        GadgetResources { v2: false },
+        mock_prompter,
+    )
+    .await;
+    let files_count = conf.read_migrations_from_dir_sorted_asc().len();
+    let generate = |policy: DestructiveChangePolicy, acknowledged: Vec<&'static str>| {
+        let file_manager = conf
+            .migrator
+            .file_manager()
+            .set_destructive_change_policy(policy)
+            .set_acknowledged_destructive_tables(acknowledged);
+        async move {
+            let resources = GadgetResources { v2: true };
+            if reversible {
+                file_manager
+                    .two_way()
+                    .generate_migrations(&"tighten gadget".into(), resources, mock_prompter)
+                    .await
+            } else {
+                file_manager
+                    .one_way()
+                    .generate_migrations(&"tighten gadget".into(), resources, mock_prompter)
+                    .await
+            }
+        }
+    };
+
+    match generate(DestructiveChangePolicy::Fail, vec![]).await {
+        Err(MigrationError::DestructiveChangesRejected { changes, .. }) => {
+            for change in [
+                "[destructive] DEFINE TABLE gadget",
+                "[destructive] UPDATE gadget SET title = name",
+                "[destructive] REMOVE FIELD name ON TABLE gadget;",
+                "[destructive] REMOVE TABLE legacy;",
+            ] {
+                assert!(changes.contains(change), "Missing {change} in:\n{changes}");
+            }
+            assert!(!changes.contains("DEFINE FIELD title"), "{changes}");
+            assert!(!changes.contains("REMOVE INDEX name_idx"), "{changes}");
+            assert!(!changes.contains("REMOVE EVENT name_changed"), "{changes}");
+        }
+        other => panic!("Expected destructive changes to be rejected. Got: {other:?}"),
+    }
+
+    // The dropped legacy table has no model to opt in with
+    match generate(DestructiveChangePolicy::RequireAck, vec!["gadget"]).await {
+        Err(MigrationError::DestructiveChangesRejected { changes, .. }) => {
+            assert!(changes.contains("REMOVE TABLE legacy;"));
+            assert!(!changes.contains("gadget"));
+        }
+        other => panic!("Expected the legacy table drop to be rejected. Got: {other:?}"),
+    }
+    assert_eq!(
+        conf.read_migrations_from_dir_sorted_asc().len(),
+        files_count
+    );
+
+    generate(
+        DestructiveChangePolicy::RequireAck,
+        vec!["gadget", "legacy"],
+    )
+    .await
+    .expect("Acknowledged changes should generate the migration");
+    assert!(conf.read_migrations_from_dir_sorted_asc().len() > files_count);
+}
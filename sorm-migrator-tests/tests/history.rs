@@ -0,0 +1,76 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use sorm::migrator::{
+    Direction, Migration, MigrationHistory, MigrationOutcome, Mode, RollbackOptions, UpdateStrategy,
+};
+use sorm_migrator_tests::{current_function, TestConfig};
+use tempfile::tempdir;
+use test_case::test_case;
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, true; "Reversible Lax")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_history_records_applied_and_rolled_back_migrations(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    conf.generate_12_test_migrations_reversible(reversible)
+        .await;
+    let db = conf.migrator.db();
+    let file_manager = conf.migrator.file_manager().set_applied_by("ci-job-42");
+
+    if reversible {
+        let two_way = file_manager.two_way();
+        two_way
+            .run_up_pending_migrations(db.clone(), UpdateStrategy::Number(3))
+            .await
+            .expect("Failed to apply migrations");
+        two_way
+            .run_down_migrations(db.clone(), RollbackOptions::new().mode(mode))
+            .await
+            .expect("Failed to rollback migrations");
+    } else {
+        file_manager
+            .one_way()
+            .run_pending_migrations(db.clone(), UpdateStrategy::Number(3))
+            .await
+            .expect("Failed to apply migrations");
+    }
+
+    let history = MigrationHistory::get_all_asc(db.clone())
+        .await
+        .expect("Failed to get migration history");
+    let applied = Migration::get_all_desc(db.clone()).await;
+
+    // Rolled back migrations are unregistered but stay in the history
+    assert_eq!(applied.len(), if reversible { 2 } else { 3 });
+    assert_eq!(history.len(), if reversible { 4 } else { 3 });
+
+    for entry in &history {
+        assert_eq!(entry.applied_by, "ci-job-42");
+        assert_eq!(entry.outcome, MigrationOutcome::Succeeded);
+        assert_eq!(entry.sorm_version, MigrationHistory::SORM_VERSION);
+        assert!(entry.finished_at >= entry.started_at);
+        assert!(entry.duration_ms >= 0);
+        assert!(entry.error.is_none());
+    }
+
+    let directions = history.iter().map(|h| h.direction).collect::<Vec<_>>();
+    if reversible {
+        assert_eq!(
+            directions,
+            vec![Direction::Up, Direction::Up, Direction::Up, Direction::Down]
+        );
+        let rolled_back = conf.get_down_filename_at_position(3);
+        assert_eq!(history[3].name, rolled_back.to_string());
+    } else {
+        assert_eq!(directions, vec![Direction::Up; 3]);
+    }
+}
@@ -0,0 +1,74 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use sorm::migrator::{Introspection, Mode};
+use sorm_migrator_tests::{current_function, TestConfig};
+use tempfile::tempdir;
+
+#[tokio::test]
+async fn test_introspect_generates_models_from_database_schema() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+    let db = conf.migrator.db();
+
+    db.query(
+        "DEFINE TABLE animal SCHEMAFULL;
+        DEFINE FIELD species ON animal TYPE string;
+        DEFINE FIELD firstName ON animal TYPE option<string>;
+        DEFINE FIELD velocity ON animal TYPE int ASSERT $value > 0;
+        DEFINE FIELD address ON animal TYPE object;
+        DEFINE FIELD address.city ON animal TYPE string;
+        DEFINE FIELD crops ON animal TYPE array<record<crop>>;
+        DEFINE INDEX species_idx ON animal FIELDS species UNIQUE;
+        DEFINE TABLE crop SCHEMAFULL;
+        DEFINE FIELD color ON crop TYPE string;
+        DEFINE TABLE eats SCHEMAFULL;
+        DEFINE FIELD in ON eats TYPE record<animal>;
+        DEFINE FIELD out ON eats TYPE record<crop>;
+        DEFINE FIELD place ON eats TYPE string;",
+    )
+    .await
+    .expect("Failed to define schema")
+    .check()
+    .expect("Failed to define schema");
+
+    let code = Introspection::from_db(db.clone())
+        .await
+        .expect("Failed to introspect database")
+        .generate()
+        .expect("Failed to generate code");
+
+    let expected = [
+        "#[derive(Node, Serialize, Deserialize, Debug, Clone)]\n#[sorm(table = animal, schemafull)]\npub struct Animal {",
+        "    #[sorm(ty = \"string\")]\n    pub species: String,",
+        "    #[serde(rename = \"firstName\")]\n    #[sorm(ty = \"option<string>\")]\n    pub first_name: Option<String>,",
+        "    /// DEFINE FIELD velocity ON animal TYPE int ASSERT $value > 0",
+        "    #[sorm(nest_object = AnimalAddress, ty = \"object\")]\n    pub address: AnimalAddress,",
+        "#[derive(Object, Serialize, Deserialize, Debug, Clone)]\npub struct AnimalAddress {",
+        "    #[sorm(link_many = Crop, ty = \"array<record<crop>>\")]\n    pub crops: LinkMany<Crop>,",
+        "impl TableResources for Animal {",
+        "#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone)]\n#[sorm(table = crop, schemafull)]\npub struct Crop {",
+        "#[derive(Edge, TableResources, Serialize, Deserialize, Debug, Clone)]\n#[sorm(table = eats, schemafull)]\npub struct Eats<In: Node, Out: Node> {",
+        "    #[sorm(link_one = In, ty = \"record<animal>\")]\n    pub r#in: LinkOne<In>,",
+        "pub type AnimalEatsCrop = Eats<Animal, Crop>;",
+        "    create_table_resources!(Animal, Crop, AnimalEatsCrop);",
+    ];
+    for snippet in expected {
+        assert!(code.contains(snippet), "Missing:\n{snippet}\n\nIn:\n{code}");
+    }
+    assert!(!code.contains("pub struct Migration"));
+
+    // The generated models must compile as they are
+    let generated_file = migration_dir
+        .parent()
+        .expect("Migration directory has a parent")
+        .join("generated_models.rs");
+    std::fs::write(&generated_file, format!("{code}\nfn main() {{}}\n"))
+        .expect("Failed to write generated models");
+    trybuild::TestCases::new().pass(&generated_file);
+}
@@ -0,0 +1,204 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::time::Duration;
+
+use sorm::migrator::{
+    FastForwardDelta, LockOptions, Migration, MigrationError, MigrationLock, Mode, UpdateStrategy,
+};
+use sorm_migrator_tests::{current_function, TestConfig};
+use tempfile::tempdir;
+use test_case::test_case;
+
+#[tokio::test]
+async fn test_lock_cannot_be_acquired_twice() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+    let db = conf.migrator.db();
+
+    let deploy_1 = LockOptions::new().owner("deploy-1");
+    let deploy_2 = LockOptions::new().owner("deploy-2");
+
+    let lock = MigrationLock::acquire(db.clone(), &deploy_1)
+        .await
+        .expect("Failed to acquire lock");
+    assert_eq!(lock.owner, "deploy-1");
+
+    let second = MigrationLock::acquire(db.clone(), &deploy_2).await;
+    assert!(matches!(
+        second,
+        Err(MigrationError::MigrationLocked { ref owner, .. }) if owner == "deploy-1"
+    ));
+
+    // Only the holder can release the lock
+    MigrationLock::release(db.clone(), &deploy_2)
+        .await
+        .expect("Failed to release lock");
+    assert!(MigrationLock::get_current(db.clone())
+        .await
+        .unwrap()
+        .is_some());
+
+    MigrationLock::release(db.clone(), &deploy_1)
+        .await
+        .expect("Failed to release lock");
+    assert!(MigrationLock::get_current(db.clone())
+        .await
+        .unwrap()
+        .is_none());
+
+    MigrationLock::acquire(db.clone(), &deploy_2)
+        .await
+        .expect("Failed to acquire lock after release");
+}
+
+#[tokio::test]
+async fn test_expired_lock_can_be_taken_over_and_force_unlocked() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+    let db = conf.migrator.db();
+
+    let crashed = LockOptions::new()
+        .owner("crashed")
+        .ttl(Duration::from_secs(0));
+    MigrationLock::acquire(db.clone(), &crashed)
+        .await
+        .expect("Failed to acquire lock");
+
+    let next = LockOptions::new().owner("next");
+    let lock = MigrationLock::acquire(db.clone(), &next)
+        .await
+        .expect("Expired lock should be taken over");
+    assert_eq!(lock.owner, "next");
+
+    let removed = MigrationLock::force_unlock(db.clone())
+        .await
+        .expect("Failed to force unlock");
+    assert_eq!(removed.map(|l| l.owner), Some("next".to_string()));
+    assert!(MigrationLock::get_current(db.clone())
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_lock_is_extended_while_held() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+    let db = conf.migrator.db();
+
+    let long_deploy = LockOptions::new()
+        .owner("long-deploy")
+        .ttl(Duration::from_secs(2));
+    let other_deploy = LockOptions::new().owner("other-deploy");
+
+    let attempt = MigrationLock::run_locked(db.clone(), &long_deploy, async {
+        // Outlive the ttl the lock was taken with
+        tokio::time::sleep(Duration::from_secs(4)).await;
+        MigrationLock::acquire(db.clone(), &other_deploy).await
+    })
+    .await;
+    assert!(matches!(
+        attempt,
+        Err(MigrationError::MigrationLocked { ref owner, .. }) if owner == "long-deploy"
+    ));
+    assert!(MigrationLock::get_current(db.clone())
+        .await
+        .unwrap()
+        .is_none());
+
+    // A crashed holder does not extend the lock, which then expires
+    MigrationLock::acquire(db.clone(), &long_deploy)
+        .await
+        .expect("Failed to acquire lock");
+    assert!(matches!(
+        MigrationLock::acquire(db.clone(), &other_deploy).await,
+        Err(MigrationError::MigrationLocked { .. })
+    ));
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    let lock = MigrationLock::acquire(db.clone(), &other_deploy)
+        .await
+        .expect("Expired lock should be taken over");
+    assert_eq!(lock.owner, "other-deploy");
+}
+
+#[tokio::test]
+async fn test_lock_is_released_when_the_locked_future_panics() {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let conf = TestConfig::new(Mode::Strict, migration_dir, current_function!()).await;
+    let db = conf.migrator.db();
+
+    let panicking = tokio::spawn({
+        let db = db.clone();
+        async move {
+            let deploy = LockOptions::new().owner("panicking-deploy");
+            MigrationLock::run_locked(db, &deploy, async {
+                panic!("Migration panicked");
+                #[allow(unreachable_code)]
+                Ok(())
+            })
+            .await
+        }
+    })
+    .await;
+    assert!(panicking.is_err_and(|e| e.is_panic()));
+
+    // The release is spawned when the lock guard is dropped
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(MigrationLock::get_current(db.clone())
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, true; "Reversible Lax")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_up_fails_while_another_deploy_holds_the_lock(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    conf.generate_12_test_migrations_reversible(reversible)
+        .await;
+    let db = conf.migrator.db();
+
+    let other_deploy = LockOptions::new().owner("other-deploy");
+    MigrationLock::acquire(db.clone(), &other_deploy)
+        .await
+        .expect("Failed to acquire lock");
+
+    let file_manager = conf.migrator.file_manager();
+    let run = if reversible {
+        file_manager
+            .two_way()
+            .run_up_pending_migrations(db.clone(), UpdateStrategy::Latest)
+            .await
+    } else {
+        file_manager
+            .one_way()
+            .run_pending_migrations(db.clone(), UpdateStrategy::Latest)
+            .await
+    };
+    assert!(matches!(run, Err(MigrationError::MigrationLocked { .. })));
+
+    MigrationLock::release(db.clone(), &other_deploy)
+        .await
+        .expect("Failed to release lock");
+
+    conf.run_up(&FastForwardDelta::default()).await;
+    assert!(MigrationLock::get_current(db.clone())
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(Migration::get_all_desc(db.clone()).await.len(), 12);
+}
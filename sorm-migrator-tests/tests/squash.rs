@@ -0,0 +1,136 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use sorm::migrator::{
+    DriftReport, FastForwardDelta, Migration, MigrationError, MockPrompter, Mode, SquashRange,
+};
+use sorm_migrator_tests::{current_function, TestConfig};
+use sorm_models::migrations::ResourcesV10;
+use tempfile::tempdir;
+use test_case::test_case;
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, true; "Reversible Lax")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_squash_applied_migrations_into_baseline(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    conf.generate_12_test_migrations_reversible(reversible)
+        .await;
+    conf.run_up(&FastForwardDelta::default()).await;
+    let db = conf.migrator.db();
+    let file_manager = conf.migrator.file_manager();
+    let files_per_migration = if reversible { 2 } else { 1 };
+    let latest = conf
+        .read_migrations_from_dir_sorted_asc()
+        .last()
+        .cloned()
+        .expect("No migration files");
+
+    let squashed = file_manager
+        .squash_migrations(
+            db.clone(),
+            &"baseline".into(),
+            &SquashRange::new(),
+            MockPrompter::default(),
+        )
+        .await
+        .expect("Failed to squash migrations");
+    assert_eq!(squashed.replaced.len(), 12 * files_per_migration);
+    assert_eq!(squashed.till, latest.timestamp());
+
+    let files = conf.read_migrations_from_dir_sorted_asc();
+    assert_eq!(files.len(), files_per_migration);
+    assert!(files.iter().all(|f| f.basename().to_string() == "baseline"));
+
+    let migrations = Migration::get_all_desc(db.clone()).await;
+    assert_eq!(migrations.len(), 1);
+    assert_eq!(
+        migrations[0].name,
+        squashed.migration.name_forward().to_string()
+    );
+
+    let report = DriftReport::detect(db.clone(), &file_manager, &ResourcesV10)
+        .await
+        .expect("Failed to detect drift");
+    assert!(!report.has_drift(), "{report}");
+
+    // Squashing again is a no-op, so the same command can be run against every environment
+    let squashed_again = file_manager
+        .squash_migrations(
+            db.clone(),
+            &"baseline".into(),
+            &SquashRange::new(),
+            MockPrompter::default(),
+        )
+        .await
+        .expect("Failed to squash migrations");
+    assert!(squashed_again.replaced.is_empty());
+    assert_eq!(conf.read_migrations_from_dir_sorted_asc(), files);
+    assert_eq!(Migration::get_all_desc(db.clone()).await.len(), 1);
+}
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, true; "Reversible Lax")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_squash_range_keeps_later_migrations(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    conf.generate_12_test_migrations_reversible(reversible)
+        .await;
+    let db = conf.migrator.db();
+    let file_manager = conf.migrator.file_manager();
+    let files_per_migration = if reversible { 2 } else { 1 };
+    let till = conf.get_either_filename_type_at_position(6, reversible);
+
+    // Apply only part of the range
+    conf.run_up(&FastForwardDelta::builder().number(3).build())
+        .await;
+    let range = SquashRange::new().till(till.clone());
+    let partially_applied = file_manager
+        .squash_migrations(
+            db.clone(),
+            &"baseline".into(),
+            &range,
+            MockPrompter::default(),
+        )
+        .await;
+    assert!(matches!(
+        partially_applied,
+        Err(MigrationError::SquashRangePartiallyApplied { .. })
+    ));
+
+    conf.run_up(&FastForwardDelta::builder().number(3).build())
+        .await;
+    file_manager
+        .squash_migrations(
+            db.clone(),
+            &"baseline".into(),
+            &range,
+            MockPrompter::default(),
+        )
+        .await
+        .expect("Failed to squash migrations");
+
+    let files = conf.read_migrations_from_dir_sorted_asc();
+    assert_eq!(files.len(), 7 * files_per_migration);
+    assert_eq!(Migration::get_all_desc(db.clone()).await.len(), 1);
+
+    conf.run_up(&FastForwardDelta::default()).await;
+    assert_eq!(Migration::get_all_desc(db.clone()).await.len(), 7);
+
+    let report = DriftReport::detect(db.clone(), &file_manager, &ResourcesV10)
+        .await
+        .expect("Failed to detect drift");
+    assert!(!report.has_drift(), "{report}");
+}
@@ -5,8 +5,12 @@
  * Licensed under the MIT license
  */
 use pretty_assertions::assert_eq;
-use sorm::migrator::{
-    FastForwardDelta, Informational, Init, MigrationFilename, MockPrompter, Mode,
+use sorm::{
+    migrator::{
+        FastForwardDelta, Informational, Init, MigrationFilename, MockPrompter, Mode,
+        RollbackOptions, RollbackStrategy, UpdateStrategy,
+    },
+    Buildable,
 };
 use sorm_migrator_tests::{current_function, AssertionArg, TestConfig};
 use sorm_models::migrations::Resources;
@@ -776,3 +780,107 @@ async fn test_mixture_of_update_strategies(mode: Mode, reversible: bool) {
     })
     .await;
 }
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, true; "Reversible Lax")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_plan_up_does_not_apply_migrations(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+
+    conf.generate_12_test_migrations_reversible(reversible)
+        .await;
+
+    let file_manager = conf.migrator.file_manager();
+    let db = conf.migrator.db();
+    let plan = if reversible {
+        file_manager
+            .two_way()
+            .plan_up_pending_migrations(db.clone(), UpdateStrategy::Number(3))
+            .await
+    } else {
+        file_manager
+            .one_way()
+            .plan_pending_migrations(db.clone(), UpdateStrategy::Number(3))
+            .await
+    }
+    .expect("Failed to plan migrations");
+
+    assert_eq!(plan.files.len(), 3);
+    assert_eq!(
+        plan.files.first().map(|f| f.basename()),
+        Some("migration_1_init".into())
+    );
+    let transaction = plan.transaction().build();
+    assert!(transaction.starts_with("BEGIN TRANSACTION;"));
+    assert!(transaction.contains("CREATE migration:"));
+    assert!(transaction.trim_end().ends_with("COMMIT TRANSACTION;"));
+
+    conf.assert_with_db_instance(AssertionArg {
+        expected_mig_files_count: 12,
+        expected_db_mig_meta_count: 0,
+        expected_latest_migration_file_basename_normalized: Some(
+            "migration_12_gen_after_init".into(),
+        ),
+        expected_latest_db_migration_meta_basename_normalized: None,
+        code_origin_line: std::line!(),
+    })
+    .await;
+}
+
+#[test_case(Mode::Strict; "Reversible Strict")]
+#[test_case(Mode::Lax; "Reversible Lax")]
+#[tokio::test]
+async fn test_plan_down_does_not_rollback_migrations(mode: Mode) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+
+    conf.generate_12_test_migrations_reversible(true).await;
+    conf.run_up(&FastForwardDelta::default()).await;
+
+    let plan = conf
+        .migrator
+        .file_manager()
+        .two_way()
+        .plan_down_migrations(
+            conf.migrator.db(),
+            RollbackOptions::new()
+                .strategy(RollbackStrategy::Number(2))
+                .mode(mode),
+        )
+        .await
+        .expect("Failed to plan rollback");
+
+    assert_eq!(plan.files.len(), 2);
+    assert_eq!(
+        plan.files.first().map(|f| f.basename()),
+        Some("migration_12_gen_after_init".into())
+    );
+    let printed = plan.to_string();
+    assert!(printed.starts_with("-- 2 migration(s) would be rolled back:\n"));
+    for file in &plan.files {
+        assert!(printed.contains(&format!("-- {file}\n")));
+    }
+    let transaction = plan.transaction().build();
+    assert!(printed.ends_with(&transaction));
+    assert!(transaction.starts_with("BEGIN TRANSACTION;"));
+    assert_eq!(transaction.matches("DELETE migration:").count(), 2);
+    assert!(transaction.trim_end().ends_with("COMMIT TRANSACTION;"));
+
+    conf.assert_with_db_instance(AssertionArg {
+        expected_mig_files_count: 12,
+        expected_db_mig_meta_count: 12,
+        expected_latest_migration_file_basename_normalized: Some(
+            "migration_12_gen_after_init".into(),
+        ),
+        expected_latest_db_migration_meta_basename_normalized: Some(
+            "migration_12_gen_after_init".into(),
+        ),
+        code_origin_line: std::line!(),
+    })
+    .await;
+}
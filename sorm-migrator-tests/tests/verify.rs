@@ -0,0 +1,103 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use sorm::migrator::{DriftReport, FastForwardDelta, MigrationFileDrift, Mode};
+use sorm_migrator_tests::{current_function, TestConfig};
+use sorm_models::migrations::{Resources, ResourcesV10};
+use tempfile::tempdir;
+use test_case::test_case;
+
+#[test_case(Mode::Strict, true; "Reversible Strict")]
+#[test_case(Mode::Lax, true; "Reversible Lax")]
+#[test_case(Mode::Strict, false; "Non-Reversible Strict")]
+#[test_case(Mode::Lax, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_verify_reports_pending_migrations_and_codebase_drift(mode: Mode, reversible: bool) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    conf.generate_12_test_migrations_reversible(reversible)
+        .await;
+    let db = conf.migrator.db();
+    let file_manager = conf.migrator.file_manager();
+
+    let report = DriftReport::detect(db.clone(), &file_manager, &ResourcesV10)
+        .await
+        .expect("Failed to detect drift");
+    assert!(report.has_drift());
+    assert!(report.codebase_vs_migrations.is_empty());
+    assert!(!report.migrations_vs_live.is_empty());
+    assert_eq!(report.migration_files.len(), 12);
+    assert!(report
+        .migration_files
+        .iter()
+        .all(|d| matches!(d, MigrationFileDrift::Pending { .. })));
+
+    conf.run_up(&FastForwardDelta::default()).await;
+
+    let report = DriftReport::detect(db.clone(), &file_manager, &ResourcesV10)
+        .await
+        .expect("Failed to detect drift");
+    assert!(!report.has_drift(), "{report}");
+
+    // The migrations were generated up to ResourcesV10
+    let report = DriftReport::detect(db.clone(), &file_manager, &Resources)
+        .await
+        .expect("Failed to detect drift");
+    assert!(!report.codebase_vs_migrations.is_empty());
+    assert!(report.migrations_vs_live.is_empty());
+    assert!(report.migration_files.is_empty());
+}
+
+#[test_case(Mode::Strict, true, true; "Reversible Strict")]
+#[test_case(Mode::Lax, true, false; "Reversible Lax")]
+#[test_case(Mode::Strict, false, true; "Non-Reversible Strict")]
+#[test_case(Mode::Lax, false, false; "Non-Reversible Lax")]
+#[tokio::test]
+async fn test_verify_reports_edited_applied_migration_in_strict_mode(
+    mode: Mode,
+    reversible: bool,
+    expect_checksum_drift: bool,
+) {
+    let migration_dir = tempdir().expect("Failed to create temp directory");
+    let migration_dir = &migration_dir.path().join("migrations-tests");
+    let mut conf = TestConfig::new(mode, migration_dir, current_function!()).await;
+    conf.generate_12_test_migrations_reversible(reversible)
+        .await;
+    conf.run_up(&FastForwardDelta::default()).await;
+    let db = conf.migrator.db();
+    let file_manager = conf.migrator.file_manager();
+
+    let first_migration = conf
+        .read_migrations_from_dir_sorted_asc()
+        .first()
+        .cloned()
+        .expect("No migration files");
+    let first_migration_path = file_manager
+        .get_migration_dir()
+        .expect("Failed to get migration dir")
+        .join(first_migration.to_string());
+    let content =
+        std::fs::read_to_string(&first_migration_path).expect("Failed to read migration file");
+    std::fs::write(&first_migration_path, format!("{content}\n-- edited"))
+        .expect("Failed to edit migration file");
+
+    let report = DriftReport::detect(db.clone(), &file_manager, &ResourcesV10)
+        .await
+        .expect("Failed to detect drift");
+    assert!(report.codebase_vs_migrations.is_empty());
+    assert!(report.migrations_vs_live.is_empty());
+    assert_eq!(report.has_drift(), expect_checksum_drift);
+    assert_eq!(
+        report
+            .migration_files
+            .iter()
+            .filter(|d| matches!(d, MigrationFileDrift::ChecksumMismatch { .. }))
+            .count(),
+        usize::from(expect_checksum_drift)
+    );
+}
@@ -23,6 +23,15 @@ pub struct Down {
         default_value_t = false
     )]
     pub(crate) prune: bool,
+
+    /// Print the migrations and SurrealQL that would be rolled back without running them
+    #[arg(
+        long,
+        help = "Print the migrations and SurrealQL that would be rolled back without running them",
+        default_value_t = false
+    )]
+    #[builder(default)]
+    pub(crate) dry_run: bool,
 }
 
 impl Down {
@@ -108,16 +117,33 @@ impl Down {
         }
 
         let rollback_strategy = self.rollback_strategy();
+        let rollback_options = RollbackOptions {
+            rollback_strategy,
+            mode: cli.mode,
+        };
+
+        if self.dry_run {
+            let plan = file_manager
+                .two_way()
+                .plan_down_migrations(db.clone(), rollback_options)
+                .await;
+
+            match plan {
+                Ok(plan) => {
+                    log::info!("Dry run. No migrations were rolled back.");
+                    println!("{plan}");
+                }
+                Err(e) => {
+                    log::error!("Failed to plan rollback: {e}");
+                    panic!("Failed to plan rollback: {e}");
+                }
+            }
+            return;
+        }
 
         let rollback = file_manager
             .two_way()
-            .run_down_migrations(
-                db.clone(),
-                RollbackOptions {
-                    rollback_strategy,
-                    mode: cli.mode,
-                },
-            )
+            .run_down_migrations(db.clone(), rollback_options)
             .await;
 
         if let Err(ref e) = rollback {
@@ -0,0 +1,38 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+use crate::*;
+use clap::Args;
+
+/// Remove the migration lock regardless of who holds it.
+/// Use this only when the deploy holding the lock has crashed.
+/// cargo run -- force-unlock
+#[derive(Args, Debug, Clone)]
+pub struct ForceUnlock;
+
+impl ForceUnlock {
+    pub async fn run(&self, cli: &mut Migrator) {
+        cli.setup_db().await;
+        let db = cli.db().clone();
+
+        match MigrationLock::force_unlock(db.clone()).await {
+            Ok(Some(lock)) => {
+                log::info!(
+                    "Removed migration lock held by {} since {}",
+                    lock.owner,
+                    lock.acquired_at
+                );
+            }
+            Ok(None) => {
+                log::info!("No migration lock is currently held");
+            }
+            Err(e) => {
+                log::error!("Failed to remove migration lock: {e}");
+                panic!("Failed to remove migration lock: {e}");
+            }
+        }
+    }
+}
@@ -23,6 +23,27 @@ pub struct Generate {
     #[arg(long, help = "Whether to run the migrations after generation")]
     #[builder(default)]
     pub(crate) run: bool,
+
+    /// What to do when the migration removes or narrows existing data
+    #[arg(
+        value_enum,
+        long,
+        default_value_t = DestructiveChangePolicy::Warn,
+        help = "What to do when the migration removes or narrows existing data. \
+        Can be 'warn', 'fail' or 'require-ack'"
+    )]
+    #[builder(default)]
+    pub(crate) policy: DestructiveChangePolicy,
+
+    /// Tables whose data-losing changes are acknowledged under the require-ack policy
+    #[arg(
+        long = "allow-destructive",
+        value_name = "TABLE",
+        help = "Acknowledge the data-losing changes on a table under the require-ack policy \
+        e.g a table whose model was deleted. Can be repeated"
+    )]
+    #[builder(default)]
+    pub(crate) allow_destructive: Vec<String>,
 }
 
 impl Generate {
@@ -32,7 +53,10 @@ impl Generate {
         codebase_resources: impl DbResources,
         prompter: impl Prompter,
     ) {
-        let file_manager = cli.file_manager();
+        let file_manager = cli
+            .file_manager()
+            .set_destructive_change_policy(self.policy)
+            .set_acknowledged_destructive_tables(self.allow_destructive.to_vec());
         let migration_basename = &self.name;
         let mig_type = file_manager.detect_migration_type();
 
@@ -84,6 +108,7 @@ impl Generate {
                 number: None,
                 till: None,
             },
+            dry_run: false,
         }
     }
 }
@@ -0,0 +1,40 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+use crate::*;
+use clap::Args;
+
+/// Print the audit trail of every migration applied or rolled back,
+/// oldest first, with who ran it, when, how long it took and its outcome.
+/// cargo run -- history
+#[derive(Args, Debug, Clone)]
+pub struct History;
+
+impl History {
+    pub async fn run(&self, cli: &mut Migrator) {
+        cli.setup_db().await;
+        let db = cli.db().clone();
+
+        match MigrationHistory::get_all_asc(db.clone()).await {
+            Ok(history) if history.is_empty() => {
+                log::info!("No migrations have been applied or rolled back yet");
+            }
+            Ok(history) => {
+                log::info!("Listing {} migration history entries.", history.len());
+                log::info!("=================================================");
+                for entry in history {
+                    log::info!("{entry}");
+                }
+                log::info!("=================================================");
+                log::info!("Listing end.");
+            }
+            Err(e) => {
+                log::error!("Failed to get migration history: {e}");
+                panic!("Failed to get migration history: {e}");
+            }
+        }
+    }
+}
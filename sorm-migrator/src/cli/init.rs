@@ -102,6 +102,7 @@ impl Init {
                 number: None,
                 till: None,
             },
+            dry_run: false,
         }
     }
 }
@@ -0,0 +1,51 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+use std::path::PathBuf;
+
+use crate::*;
+use clap::Args;
+use typed_builder::TypedBuilder;
+
+/// Generate Rust structs from the schema of an existing database.
+/// cargo run -- introspect --output src/models.rs
+#[derive(Args, Debug, Clone, TypedBuilder)]
+pub struct Introspect {
+    /// File to write the generated code to
+    #[arg(
+        short,
+        long,
+        help = "File to write the generated code to. Prints to stdout if not provided"
+    )]
+    #[builder(default, setter(strip_option))]
+    pub(crate) output: Option<PathBuf>,
+}
+
+impl Introspect {
+    pub async fn run(&self, cli: &mut Migrator) {
+        cli.setup_db().await;
+        let db = cli.db().clone();
+
+        let code = match Introspection::from_db(db).await.and_then(|i| i.generate()) {
+            Ok(code) => code,
+            Err(e) => {
+                log::error!("Failed to introspect database: {e}");
+                panic!("Failed to introspect database: {e}");
+            }
+        };
+
+        match &self.output {
+            Some(output) => {
+                if let Err(e) = std::fs::write(output, code) {
+                    log::error!("Failed to write generated code: {e}");
+                    panic!("Failed to write generated code: {e}");
+                }
+                log::info!("Generated code written to {}", output.display());
+            }
+            None => println!("{code}"),
+        }
+    }
+}
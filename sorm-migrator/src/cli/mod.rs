@@ -7,22 +7,32 @@
 mod arg_parser;
 pub mod config;
 mod down;
+mod force_unlock;
 mod generate;
+mod history;
 mod init;
+mod introspect;
 mod list;
 mod prune;
 mod reset;
+mod squash;
 mod up;
+mod verify;
 
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 pub use arg_parser::*;
 pub use down::{Down, RollbackStrategy, RollbackStrategyStruct};
+pub use force_unlock::ForceUnlock;
 pub use generate::Generate;
+pub use history::History;
 pub use init::Init;
+pub use introspect::Introspect;
 pub use list::{List, Status};
 pub use prune::Prune;
 pub use reset::Reset;
+pub use squash::Squash;
+pub use verify::Verify;
 
 use surrealdb::{engine::any::Any, Surreal};
 use typed_builder::TypedBuilder;
@@ -32,7 +42,10 @@ use clap::{ArgAction, Parser};
 use sorm_query::DbResources;
 
 pub use self::config::DatabaseConnection;
-use crate::{MigrationConfig, MockPrompter, Mode, Prompter, RealPrompter, RenameOrDelete};
+use crate::{
+    DataMigrations, LockOptions, MigrationConfig, MockPrompter, Mode, Prompter, RealPrompter,
+    RenameOrDelete,
+};
 
 /// Surreal ORM CLI
 #[derive(Parser, Debug, Clone, TypedBuilder)]
@@ -68,8 +81,41 @@ pub struct Migrator {
     )]
     pub(crate) mode: Mode,
 
+    /// Owner recorded on the migration lock
+    #[arg(
+        global = true,
+        long,
+        help = "Owner recorded on the migration lock taken while running migrations. Defaults to <hostname>:<pid>"
+    )]
+    #[builder(default, setter(strip_option))]
+    pub(crate) lock_owner: Option<String>,
+
+    /// Seconds after which a migration lock is considered abandoned
+    #[arg(
+        global = true,
+        long,
+        help = "Seconds after which a migration lock held by a crashed deploy is considered abandoned",
+        default_value_t = LockOptions::DEFAULT_TTL.as_secs()
+    )]
+    #[builder(default = LockOptions::DEFAULT_TTL.as_secs())]
+    pub(crate) lock_ttl: u64,
+
+    /// Label recorded in the migration history
+    #[arg(
+        global = true,
+        long,
+        help = "Label recorded in the migration history as who applied or rolled back migrations e.g a CI job id. Defaults to the lock owner"
+    )]
+    #[builder(default, setter(strip_option))]
+    pub(crate) applied_by: Option<String>,
+
     #[command(flatten)]
     pub(crate) db_connection: DatabaseConnection,
+
+    /// Rust data migrations run in order with the migration files
+    #[arg(skip)]
+    #[builder(default)]
+    pub(crate) data_migrations: DataMigrations,
 }
 
 impl Migrator {
@@ -86,14 +132,30 @@ impl Migrator {
         self
     }
 
+    pub fn set_data_migrations(&mut self, data_migrations: DataMigrations) -> &mut Self {
+        self.data_migrations = data_migrations;
+        self
+    }
+
     pub fn file_manager(&self) -> MigrationConfig {
         let fm_init = MigrationConfig::builder()
             .custom_path(self.dir.clone())
-            .mode(self.mode);
+            .mode(self.mode)
+            .lock(self.lock_options())
+            .applied_by(self.applied_by.clone())
+            .data_migrations(self.data_migrations.clone());
 
         fm_init.build()
     }
 
+    pub fn lock_options(&self) -> LockOptions {
+        let lock = LockOptions::new().ttl(Duration::from_secs(self.lock_ttl));
+        match &self.lock_owner {
+            Some(owner) => lock.owner(owner),
+            None => lock,
+        }
+    }
+
     /// Run migration cli
     /// # Example
     /// ```rust, ignore
@@ -111,6 +173,29 @@ impl Migrator {
         cli.run_fn(codebase_resources, RealPrompter).await;
     }
 
+    /// Run migration cli with Rust data migrations applied in order with the migration files
+    /// # Example
+    /// ```rust, ignore
+    /// use surreal_models::migrations::Resources;
+    /// use surreal_orm::sorm-migrator::{DataMigrations, Migrator};
+    ///
+    /// const DATA_MIGRATIONS: DataMigrations = DataMigrations::new(&[]);
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     Migrator::run_with_data_migrations(Resources, DATA_MIGRATIONS).await;
+    /// }
+    /// ```
+    pub async fn run_with_data_migrations(
+        codebase_resources: impl DbResources,
+        data_migrations: DataMigrations,
+    ) {
+        let mut cli = Self::parse();
+        cli.set_data_migrations(data_migrations);
+        cli.setup_logging();
+        cli.run_fn(codebase_resources, RealPrompter).await;
+    }
+
     pub async fn run_test_main(codebase_resources: impl DbResources) {
         let mut cli = Self::parse();
         cli.setup_logging();
@@ -156,6 +241,9 @@ impl Migrator {
                 SubCommand::Down(down) => down.run(self).await,
                 SubCommand::Prune(prune) => prune.run(self).await,
                 SubCommand::List(prune) => prune.run(self).await,
+                SubCommand::ForceUnlock(force_unlock) => force_unlock.run(self).await,
+                SubCommand::History(history) => history.run(self).await,
+                SubCommand::Introspect(introspect) => introspect.run(self).await,
                 SubCommand::Reset(reset) => {
                     reset
                         .run(
@@ -166,6 +254,16 @@ impl Migrator {
                         )
                         .await
                 }
+                SubCommand::Verify(verify) => {
+                    verify
+                        .run(
+                            self,
+                            codebase_resources
+                                .expect("resources must be provided for verify command"),
+                        )
+                        .await
+                }
+                SubCommand::Squash(squash) => squash.run(self, prompter).await,
             },
         };
     }
@@ -183,7 +281,12 @@ impl Migrator {
                 SubCommand::Down(down) => down.run(self).await,
                 SubCommand::Prune(prune) => prune.run(self).await,
                 SubCommand::List(prune) => prune.run(self).await,
+                SubCommand::ForceUnlock(force_unlock) => force_unlock.run(self).await,
+                SubCommand::History(history) => history.run(self).await,
+                SubCommand::Introspect(introspect) => introspect.run(self).await,
                 SubCommand::Reset(reset) => reset.run(self, codebase_resources, prompter).await,
+                SubCommand::Verify(verify) => verify.run(self, codebase_resources).await,
+                SubCommand::Squash(squash) => squash.run(self, prompter).await,
             },
         };
     }
@@ -231,6 +334,17 @@ pub enum SubCommand {
     List(List),
     /// Delete Unapplied local migration files that have not been applied to the current database instance
     Prune(Prune),
+    /// Remove the migration lock left behind by a crashed deploy
+    ForceUnlock(ForceUnlock),
+    /// Show who applied or rolled back each migration, when, how long it took and the outcome
+    History(History),
+    /// Report drift between the codebase, migration files and the live database
+    #[clap(alias = "status")]
+    Verify(Verify),
+    /// Generate Rust structs from the schema of an existing database
+    Introspect(Introspect),
+    /// Squash a range of migrations into a single baseline migration
+    Squash(Squash),
 }
 
 macro_rules! impl_from {
@@ -249,3 +363,8 @@ impl_from!(Down);
 impl_from!(Reset);
 impl_from!(List);
 impl_from!(Prune);
+impl_from!(ForceUnlock);
+impl_from!(History);
+impl_from!(Verify);
+impl_from!(Introspect);
+impl_from!(Squash);
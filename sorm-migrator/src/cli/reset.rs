@@ -46,7 +46,34 @@ impl Reset {
         codebase_resources: impl DbResources,
         prompter: impl Prompter,
     ) {
+        cli.setup_db().await;
         let file_manager = cli.file_manager();
+        let db = cli.db().clone();
+
+        // Migrations are run after the lock is released because `up` takes the lock itself
+        let reset = MigrationLock::run_locked(db, &file_manager.lock, async {
+            Self::recreate_migration_dir(&file_manager);
+            self.init_command()
+                .run(cli, codebase_resources, prompter)
+                .await;
+            Ok(())
+        })
+        .await;
+
+        if let Err(e) = reset {
+            log::error!("Failed to reset migrations: {e}");
+            panic!("Failed to reset migrations: {e}");
+        }
+
+        if self.run {
+            log::info!("Running reinitialized migrations");
+            Up::default().run(cli).await;
+        }
+
+        log::info!("Reset successful");
+    }
+
+    fn recreate_migration_dir(file_manager: &MigrationConfig) {
         let dir = file_manager.get_migration_dir_create_if_none();
 
         match dir {
@@ -70,18 +97,12 @@ impl Reset {
                 panic!();
             }
         };
-
-        self.init_command()
-            .run(cli, codebase_resources, prompter)
-            .await;
-
-        log::info!("Reset successful");
     }
 
     fn init_command(&self) -> Init {
         Init {
             name: self.name.clone(),
-            run: self.run,
+            run: false,
             reversible: self.reversible,
         }
     }
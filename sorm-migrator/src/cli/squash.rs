@@ -0,0 +1,76 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use crate::*;
+use clap::Args;
+use typed_builder::TypedBuilder;
+
+/// Squash a range of migrations into a single baseline migration.
+/// Databases that have applied the whole range get their migration records rewritten.
+/// Run it again against each environment after the files have been squashed.
+/// cargo run -- squash --from 20231022090000000_create_users --till 20231115090000000_add_index
+#[derive(Args, Debug, TypedBuilder, Clone)]
+pub struct Squash {
+    /// Squash from a specific migration ID. Defaults to the first migration
+    #[arg(
+        short,
+        long,
+        value_parser = mig_name_parser,
+        help = "Squash from a specific migration ID. Defaults to the first migration"
+    )]
+    #[builder(default, setter(strip_option))]
+    pub(crate) from: Option<MigrationFilename>,
+
+    /// Squash till a specific migration ID. Defaults to the latest migration
+    #[arg(
+        short,
+        long,
+        value_parser = mig_name_parser,
+        help = "Squash till a specific migration ID. Defaults to the latest migration"
+    )]
+    #[builder(default, setter(strip_option))]
+    pub(crate) till: Option<MigrationFilename>,
+
+    /// Name of the squashed migration
+    #[arg(
+        long,
+        default_value = "squashed",
+        help = "Name of the squashed migration"
+    )]
+    #[builder(default = "squashed".into())]
+    pub(crate) name: Basename,
+}
+
+impl Squash {
+    pub async fn run(&self, cli: &mut Migrator, prompter: impl Prompter) {
+        cli.setup_db().await;
+        let db = cli.db().clone();
+        let file_manager = cli.file_manager();
+        let range = SquashRange {
+            from: self.from.clone(),
+            till: self.till.clone(),
+        };
+
+        let squashed = file_manager
+            .squash_migrations(db, &self.name, &range, prompter)
+            .await;
+
+        match squashed {
+            Ok(squashed) => {
+                log::info!(
+                    "Squashed {} migration file(s) into {}",
+                    squashed.replaced.len(),
+                    squashed.migration.name_forward()
+                );
+            }
+            Err(e) => {
+                log::error!("Failed to squash migrations: {e}");
+                panic!("Failed to squash migrations: {e}");
+            }
+        }
+    }
+}
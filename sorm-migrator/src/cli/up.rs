@@ -9,6 +9,7 @@ use crate::*;
 use clap::Args;
 use sorm_query::statements::info_for;
 use sorm_query::Runnable;
+use surrealdb::{engine::any::Any, Surreal};
 use typed_builder::TypedBuilder;
 
 /// Run migrations
@@ -16,10 +17,20 @@ use typed_builder::TypedBuilder;
 /// cargo run -- up -l
 /// cargo run -- up -n 2
 /// cargo run -- up -t 2021-09-09-xxxxx
+/// cargo run -- up --dry-run
 #[derive(Args, Debug, TypedBuilder, Clone, Default)]
 pub struct Up {
     #[command(flatten)]
     pub(crate) fast_forward: FastForwardDelta,
+
+    /// Print the migrations and SurrealQL that would be run without applying them
+    #[arg(
+        long,
+        help = "Print the migrations and SurrealQL that would be run without applying them",
+        default_value_t = false
+    )]
+    #[builder(default)]
+    pub(crate) dry_run: bool,
 }
 
 impl Up {
@@ -97,6 +108,11 @@ impl Up {
         let update_strategy = self.update_strategy();
         let db = cli.db().clone();
 
+        if self.dry_run {
+            self.plan(&file_manager, db, update_strategy).await;
+            return;
+        }
+
         match file_manager.detect_migration_type() {
             Ok(MigrationFlag::TwoWay) => {
                 log::info!("Running two way migrations");
@@ -135,4 +151,42 @@ impl Up {
         log::info!("Successfully ran migrations");
         log::info!("Database: {:?}", info);
     }
+
+    async fn plan(
+        &self,
+        file_manager: &MigrationConfig,
+        db: Surreal<Any>,
+        update_strategy: UpdateStrategy,
+    ) {
+        let plan = match file_manager.detect_migration_type() {
+            Ok(MigrationFlag::TwoWay) => {
+                file_manager
+                    .two_way()
+                    .plan_up_pending_migrations(db, update_strategy)
+                    .await
+            }
+            Ok(MigrationFlag::OneWay) => {
+                file_manager
+                    .one_way()
+                    .plan_pending_migrations(db, update_strategy)
+                    .await
+            }
+            Err(e) => {
+                log::error!("Failed to detect migration type. Make sure the migration  \
+                is first initialized or reset by running cargo run -- init -n '<migration name>'. Error: {e}");
+                panic!("Failed to detect migration type.");
+            }
+        };
+
+        match plan {
+            Ok(plan) => {
+                log::info!("Dry run. No migrations were applied.");
+                println!("{plan}");
+            }
+            Err(e) => {
+                log::error!("Failed to plan migrations: {e}");
+                panic!("Failed to plan migrations. Migration already run or not found");
+            }
+        }
+    }
 }
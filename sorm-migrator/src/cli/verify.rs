@@ -0,0 +1,40 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+use crate::*;
+use clap::Args;
+use sorm_query::DbResources;
+
+/// Report drift between the codebase, the migration directory and the live database.
+/// Fails if there is any drift, so it can be used to gate CI.
+/// cargo run -- verify
+#[derive(Args, Debug, Clone)]
+pub struct Verify;
+
+impl Verify {
+    pub async fn run(&self, cli: &mut Migrator, codebase_resources: impl DbResources) {
+        cli.setup_db().await;
+        let db = cli.db().clone();
+        let file_manager = cli.file_manager();
+
+        let report = DriftReport::detect(db, &file_manager, &codebase_resources).await;
+
+        match report {
+            Ok(report) if report.has_drift() => {
+                println!("{report}");
+                log::error!("Drift detected");
+                panic!("Drift detected");
+            }
+            Ok(report) => {
+                println!("{report}");
+            }
+            Err(e) => {
+                log::error!("Failed to verify migrations: {e}");
+                panic!("Failed to verify migrations: {e}");
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::{borrow::Cow, future::Future, pin::Pin};
+
+use sorm_query::{Raw, Runnable};
+use surrealdb::{engine::any::Any, Surreal};
+
+use crate::*;
+
+pub type DataMigrationFuture = Pin<Box<dyn Future<Output = MigrationResult<()>> + Send>>;
+
+/// Function run by a data migration with the database it migrates. Non-capturing
+/// async closures coerce to it when they box their future e.g `|db| Box::pin(async move { ... })`.
+pub type DataMigrationFn = fn(Surreal<Any>) -> DataMigrationFuture;
+
+/// A migration written in Rust, for backfills that need more than SurrealQL.
+/// It is keyed by a timestamped name with the `.rs` extension
+/// e.g `20240110093000000_backfill_password_hashes.rs`, so it runs in order
+/// with the file migrations around it. It is recorded in the migration table
+/// with the checksum of its version. Bump the version when the migration changes
+/// so that strict verification flags databases that ran an older version.
+///
+/// A data migration is not run in a transaction. Its queries are committed as it
+/// runs and it is only registered once it succeeds, so after a failure it runs
+/// again in full on the next run and should be safe to rerun
+/// e.g by only updating the records it has not migrated yet.
+#[derive(Debug, Clone, Copy)]
+pub struct DataMigration {
+    name: &'static str,
+    version: &'static str,
+    run: DataMigrationFn,
+}
+
+impl DataMigration {
+    pub const fn new(name: &'static str, version: &'static str, run: DataMigrationFn) -> Self {
+        Self { name, version, run }
+    }
+
+    pub fn name(&self) -> MigrationResult<MigrationFilename> {
+        let name: MigrationFilename = self.name.to_string().try_into()?;
+        if !name.is_data() {
+            return Err(MigrationError::InvalidDataMigrationName(
+                self.name.to_string(),
+            ));
+        }
+        Ok(name)
+    }
+
+    pub fn version(&self) -> &'static str {
+        self.version
+    }
+
+    /// The version as it is checksummed in the migration table
+    pub fn content(&self) -> FileContent {
+        self.version.to_string().into()
+    }
+
+    pub fn checksum(&self) -> MigrationResult<Checksum> {
+        self.content().as_checksum()
+    }
+
+    /// Query registering the data migration as applied
+    pub fn create_raw(&self) -> MigrationResult<Raw> {
+        Ok(Migration::create_raw(
+            &self.name()?,
+            &self.checksum()?,
+            None,
+        ))
+    }
+
+    /// Runs the migration, then registers it as applied. It is left unregistered if it fails.
+    pub async fn apply(&self, db: Surreal<Any>) -> MigrationResult<()> {
+        let name = self.name()?;
+        log::info!("Running data migration: {name}");
+
+        (self.run)(db.clone()).await?;
+
+        let response = self.create_raw()?.run(db.clone()).await?;
+        MigrationRunner::check_transaction(response)?;
+
+        log::info!("Applied data migration: {name}");
+        Ok(())
+    }
+}
+
+/// Registry of data migrations. It can be built as a constant so it can
+/// be embedded alongside file migrations with `embed_migrations!`.
+///
+/// # Example
+/// ```rust, ignore
+/// const DATA_MIGRATIONS: DataMigrations = DataMigrations::new(&[DataMigration::new(
+///     "20240110093000000_backfill_password_hashes.rs",
+///     "1",
+///     |db| Box::pin(async move {
+///         db.query("UPDATE user SET password = crypto::argon2::generate(password) WHERE !string::starts_with(password, '$argon2')")
+///             .await?
+///             .check()?;
+///         Ok(())
+///     }),
+/// )]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DataMigrations {
+    migrations: Cow<'static, [DataMigration]>,
+}
+
+impl DataMigrations {
+    pub const fn new(migrations: &'static [DataMigration]) -> Self {
+        Self {
+            migrations: Cow::Borrowed(migrations),
+        }
+    }
+
+    pub fn get_migrations(&self) -> &[DataMigration] {
+        &self.migrations
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.migrations.is_empty()
+    }
+
+    /// Data migrations sorted by timestamp. Fails on an invalid or duplicate name.
+    pub fn sorted_asc(&self) -> MigrationResult<Vec<(MigrationFilename, DataMigration)>> {
+        let mut migrations = self
+            .migrations
+            .iter()
+            .map(|m| Ok((m.name()?, *m)))
+            .collect::<MigrationResult<Vec<_>>>()?;
+        migrations.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        if let Some(pair) = migrations.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(MigrationError::InvalidDataMigrationName(format!(
+                "{} is registered more than once",
+                pair[0].0
+            )));
+        }
+
+        Ok(migrations)
+    }
+}
+
+impl From<Vec<DataMigration>> for DataMigrations {
+    fn from(migrations: Vec<DataMigration>) -> Self {
+        Self {
+            migrations: Cow::Owned(migrations),
+        }
+    }
+}
@@ -4,8 +4,9 @@
  * Copyright (c) 2023 Oyelowo Oyedayo
  * Licensed under the MIT license
  */
-use std::{collections::BTreeSet, path::PathBuf};
+use std::collections::BTreeSet;
 
+use chrono::Utc;
 use sorm_query::{statements::*, *};
 use surrealdb::{engine::any::Any, Connection, Surreal};
 
@@ -17,6 +18,21 @@ pub struct MigrationRunner {
     // file_manager: FileManager,
 }
 
+#[derive(Debug, Clone)]
+enum PendingMigration {
+    File(PendingMigrationFile),
+    Data(MigrationFilename, DataMigration),
+}
+
+impl PendingMigration {
+    fn name_forward(&self) -> MigrationFilename {
+        match self {
+            Self::File(m) => m.name_forward().clone(),
+            Self::Data(name, _) => name.clone(),
+        }
+    }
+}
+
 pub struct RollbackOptions {
     pub rollback_strategy: RollbackStrategy,
     pub mode: Mode,
@@ -58,20 +74,49 @@ impl MigrationRunner {
         fm: &MigrationConfig,
         rollback_options: RollbackOptions,
     ) -> MigrationResult<()> {
+        let plan = Self::plan_rollback_migrations(db.clone(), fm, rollback_options).await?;
+
+        if plan.is_empty() {
+            log::info!("No migrations to rollback");
+        } else {
+            Self::execute_plan_recorded(db.clone(), &plan, fm.applied_by()).await?;
+
+            log::info!("Rolled back {} migrations", plan.files.len());
+        }
+
+        Ok(())
+    }
+
+    /// Resolves the rollback strategy into the down migrations and the migration
+    /// metadata deletions that would be run, without running them.
+    pub async fn plan_rollback_migrations(
+        db: Surreal<Any>,
+        fm: &MigrationConfig,
+        rollback_options: RollbackOptions,
+    ) -> MigrationResult<MigrationPlan> {
         let RollbackOptions {
             ref rollback_strategy,
             mode: ref strictness,
         } = rollback_options;
 
         let all_migrations_from_dir = fm.get_two_way_migrations_sorted_desc(false)?;
+        let applied_by = fm.applied_by();
 
-        let (queries_to_run, _rolledback_file_paths) = match rollback_strategy {
+        let plan = match rollback_strategy {
             RollbackStrategy::Previous => {
                 let latest_migration = Self::get_latest_migration(db.clone()).await?;
                 match latest_migration {
                     Some(latest_migration) => {
                         let latest_migration_name: MigrationFilename =
                             latest_migration.name.clone().try_into()?;
+                        if latest_migration_name.is_data() {
+                            return Self::generate_rollback_plan(
+                                vec![],
+                                vec![latest_migration],
+                                strictness,
+                                applied_by,
+                            );
+                        }
                         let migrations_from_dir = all_migrations_from_dir
                             .iter()
                             .find(|m| m.up.name == latest_migration_name.to_up())
@@ -79,19 +124,14 @@ impl MigrationRunner {
                 "The latest migration - {} - does not have a corresponding down migration file",
                 latest_migration_name
             )))?;
-                        Self::generate_rollback_queries_and_filepaths(
-                            fm,
+                        Self::generate_rollback_plan(
                             vec![migrations_from_dir.clone()],
                             vec![latest_migration],
                             strictness,
+                            applied_by,
                         )?
                     }
-                    None => Self::generate_rollback_queries_and_filepaths(
-                        fm,
-                        vec![],
-                        vec![],
-                        strictness,
-                    )?,
+                    None => Self::generate_rollback_plan(vec![], vec![], strictness, applied_by)?,
                 }
             }
             RollbackStrategy::Number(count) => {
@@ -106,17 +146,23 @@ impl MigrationRunner {
                     .first()
                     .ok_or(MigrationError::NoMigrationsRegisteredYetInDb)?;
 
+                // Data migrations are counted, but have no down file to run
+                let file_migration_count = migrations_from_db
+                    .iter()
+                    .filter(|m| !Self::is_data_migration(m))
+                    .count();
+
                 let migrations_to_rollback = all_migrations_from_dir
                     .into_iter()
                     .filter(|m| m.up.name.timestamp() <= latest_migration.timestamp)
-                    .take(*count as usize)
+                    .take(file_migration_count)
                     .collect::<Vec<_>>();
 
-                Self::generate_rollback_queries_and_filepaths(
-                    fm,
+                Self::generate_rollback_plan(
                     migrations_to_rollback,
                     migrations_from_db,
                     strictness,
+                    applied_by,
                 )?
             }
             RollbackStrategy::Till(file_cursor) => {
@@ -127,7 +173,11 @@ impl MigrationRunner {
                 // By using to_up(), we also allow using the down migration file name
                 // counterpart to the up migration filename as the cursor, giving user
                 // more flexibility to use either.
-                let file_cursor = &file_cursor.to_up();
+                let file_cursor = &if file_cursor.is_data() {
+                    file_cursor.clone()
+                } else {
+                    file_cursor.to_up()
+                };
                 let timestamp_value = file_cursor.timestamp().into_inner();
                 let migration_meta = Migration::get_by_filename(db.clone(), file_cursor).await;
                 if migration_meta.is_none() {
@@ -173,25 +223,60 @@ impl MigrationRunner {
                     })
                     .collect::<Vec<_>>();
 
-                Self::generate_rollback_queries_and_filepaths(
-                    fm,
+                Self::generate_rollback_plan(
                     migrations_files_to_rollback,
                     migrations_from_db,
                     strictness,
+                    applied_by,
                 )?
             }
         };
 
-        if queries_to_run.build().trim().is_empty() {
-            log::info!("No migrations to rollback");
-        } else {
-            begin_transaction()
-                .query(queries_to_run)
-                .commit_transaction()
-                .run(db.clone())
-                .await?;
+        Ok(plan)
+    }
+
+    /// Runs a resolved plan against the database as a single transaction
+    pub async fn execute_plan(
+        db: Surreal<impl Connection>,
+        plan: &MigrationPlan,
+    ) -> MigrationResult<()> {
+        let response = begin_transaction()
+            .query(plan.queries())
+            .commit_transaction()
+            .run(db.clone())
+            .await?;
+
+        Self::check_transaction(response)
+    }
 
-            log::info!("Rolled back {} migrations", 1);
+    /// Surfaces the statement that made a transaction fail
+    pub(crate) fn check_transaction(mut response: surrealdb::Response) -> MigrationResult<()> {
+        match take_first_failure(&mut response) {
+            Some((_, e)) => Err(e.into()),
+            None => Ok(()),
+        }
+    }
+
+    /// Runs a resolved plan and, if its transaction fails, records the failure
+    /// of its migrations in the migration history
+    async fn execute_plan_recorded(
+        db: Surreal<impl Connection>,
+        plan: &MigrationPlan,
+        applied_by: &str,
+    ) -> MigrationResult<()> {
+        let started_at = Utc::now();
+
+        if let Err(e) = Self::execute_plan(db.clone(), plan).await {
+            MigrationHistory::record(
+                db.clone(),
+                &plan.files,
+                plan.direction,
+                applied_by,
+                started_at,
+                Some(&e),
+            )
+            .await?;
+            return Err(e);
         }
 
         Ok(())
@@ -287,15 +372,35 @@ impl MigrationRunner {
         Ok(())
     }
 
-    fn generate_rollback_queries_and_filepaths(
-        fm: &MigrationConfig,
+    fn is_data_migration(migration: &Migration) -> bool {
+        MigrationFilename::try_from(migration.name.clone()).is_ok_and(|name| name.is_data())
+    }
+
+    fn generate_rollback_plan(
         migrations_to_rollback: Vec<MigrationFileTwoWayPair>,
         migrations_from_db: Vec<Migration>,
         mode: &Mode,
-    ) -> MigrationResult<(Raw, Vec<PathBuf>)> {
+        applied_by: &str,
+    ) -> MigrationResult<MigrationPlan> {
+        // Data migrations have no down migration. Rolling them back only
+        // unregisters them, the data they changed is left as is.
+        let (data_migrations_from_db, file_migrations_from_db): (Vec<_>, Vec<_>) =
+            migrations_from_db
+                .iter()
+                .cloned()
+                .partition(Self::is_data_migration);
+
+        for m in &data_migrations_from_db {
+            log::warn!(
+                "Unregistering data migration {} without reverting the data it changed",
+                m.name
+            );
+        }
+
         if mode.is_strict() {
-            for (m_from_file, m_from_db) in
-                migrations_to_rollback.iter().zip(migrations_from_db.iter())
+            for (m_from_file, m_from_db) in migrations_to_rollback
+                .iter()
+                .zip(file_migrations_from_db.iter())
             {
                 let db_mig_name: MigrationFilename = m_from_db.name.clone().try_into()?;
 
@@ -323,9 +428,28 @@ impl MigrationRunner {
         log::info!("Rolling back {} migration(s)", migrations_to_rollback.len());
 
         let rollback_queries = migrations_to_rollback
-            .clone()
-            .into_iter()
-            .map(|m| m.down.content.to_string())
+            .iter()
+            .map(|m| {
+                MigrationHistory::wrap_raw(
+                    &m.down.name,
+                    &m.down.content,
+                    Direction::Down,
+                    applied_by,
+                )
+                .build()
+            })
+            .chain(
+                data_migrations_from_db
+                    .iter()
+                    .map(|m| -> MigrationResult<String> {
+                        let name = m.name.clone().try_into()?;
+                        Ok(
+                            MigrationHistory::wrap_raw(&name, "", Direction::Down, applied_by)
+                                .build(),
+                        )
+                    })
+                    .collect::<MigrationResult<Vec<_>>>()?,
+            )
             .collect::<Vec<_>>()
             .join("\n");
 
@@ -336,43 +460,63 @@ impl MigrationRunner {
             .collect::<Vec<_>>()
             .join("\n");
 
-        let all = format!(
-            "{}\n{}",
-            rollback_queries, rollbacked_migration_deletion_queries
-        );
-
-        let file_paths = migrations_to_rollback
+        let mut files = migrations_to_rollback
             .iter()
-            .map(|m| {
-                fm.get_migration_dir().map(|d| {
-                    vec![
-                        d.join(m.up.name.to_string()),
-                        d.join(m.down.name.to_string()),
-                    ]
-                })
-            })
-            .collect::<MigrationResult<Vec<_>>>();
-
-        Ok((
-            Raw::new(all),
-            file_paths?.iter().flatten().cloned().collect::<Vec<_>>(),
-        ))
+            .map(|m| m.down.name.clone())
+            .chain(
+                data_migrations_from_db
+                    .iter()
+                    .map(|m| m.name.clone().try_into())
+                    .collect::<MigrationResult<Vec<_>>>()?,
+            )
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| b.cmp(a));
+
+        Ok(MigrationPlan {
+            direction: Direction::Down,
+            files,
+            data_migrations: vec![],
+            migration_queries: rollback_queries,
+            bookkeeping_queries: rollbacked_migration_deletion_queries,
+        })
     }
 
-    async fn run_up_pending_migrations(
-        db: Surreal<impl Connection>,
-        filtered_pending_migrations: Vec<PendingMigrationFile>,
-    ) -> MigrationResult<()> {
-        let mut migration_queries: Vec<FileContent> = vec![];
+    fn generate_up_plan(
+        filtered_pending_migrations: Vec<PendingMigration>,
+        applied_by: &str,
+    ) -> MigrationResult<MigrationPlan> {
+        let mut files: Vec<MigrationFilename> = vec![];
+        let mut data_migrations: Vec<MigrationFilename> = vec![];
+        let mut migration_queries: Vec<String> = vec![];
         let mut mark_queries_registered_queries: Vec<Raw> = vec![];
 
-        for mf in filtered_pending_migrations.into_iter() {
+        for pending in filtered_pending_migrations.into_iter() {
+            let mf = match pending {
+                PendingMigration::File(mf) => mf,
+                PendingMigration::Data(name, _) => {
+                    // Data migrations are run and registered on their own,
+                    // outside the transaction of the file migrations
+                    files.push(name.clone());
+                    data_migrations.push(name);
+                    continue;
+                }
+            };
+
             match mf.into() {
                 MigrationFile::OneWay(m) => {
                     let created_registered_mig =
                         Migration::create_raw(m.name(), &m.content().as_checksum()?, None);
 
-                    migration_queries.push(m.content().to_owned());
+                    files.push(m.name().to_owned());
+                    migration_queries.push(
+                        MigrationHistory::wrap_raw(
+                            m.name(),
+                            m.content(),
+                            Direction::Up,
+                            applied_by,
+                        )
+                        .build(),
+                    );
                     mark_queries_registered_queries.push(created_registered_mig);
                 }
                 MigrationFile::TwoWay(m) => {
@@ -382,17 +526,22 @@ impl MigrationRunner {
                         Some(&m.down.content.as_checksum()?),
                     );
 
-                    migration_queries.push(m.up.content);
+                    migration_queries.push(
+                        MigrationHistory::wrap_raw(
+                            &m.up.name,
+                            &m.up.content,
+                            Direction::Up,
+                            applied_by,
+                        )
+                        .build(),
+                    );
+                    files.push(m.up.name);
                     mark_queries_registered_queries.push(created_registered_mig);
                 }
             }
         }
 
-        let migration_queries_str = migration_queries
-            .iter()
-            .map(ToString::to_string)
-            .collect::<Vec<_>>()
-            .join("\n");
+        let migration_queries_str = migration_queries.join("\n");
 
         // Create queries to mark migrations as applied
         let mark_queries_registered_queries_str = mark_queries_registered_queries
@@ -401,62 +550,57 @@ impl MigrationRunner {
             .collect::<Vec<_>>()
             .join("\n");
 
-        log::info!("Running {} migrations", migration_queries.len());
-        log::info!(
-            "Marking {} query(ies) as registered",
-            mark_queries_registered_queries.len()
-        );
-
-        // Join migrations with mark queries
-        let all = format!(
-            "{}\n{}",
-            migration_queries_str, mark_queries_registered_queries_str
-        );
-
-        if all.trim().is_empty() {
-            log::info!("No new migrations to apply");
-        } else {
-            println!("{}", &all);
-            begin_transaction()
-                .query(Raw::new(all))
-                .commit_transaction()
-                .run(db.clone())
-                .await?;
-
-            log::info!("Applied {} migrations", migration_queries.len());
-        }
-
-        Ok(())
+        Ok(MigrationPlan {
+            direction: Direction::Up,
+            files,
+            data_migrations,
+            migration_queries: migration_queries_str,
+            bookkeeping_queries: mark_queries_registered_queries_str,
+        })
     }
 
-    pub async fn apply_pending_migrations(
+    /// Pending file and data migrations in the order they are applied,
+    /// filtered by the update strategy
+    async fn get_pending_steps(
         db: Surreal<impl Connection>,
         all_migrations: Vec<impl Into<MigrationFile> + ::std::fmt::Debug>,
+        data_migrations: &DataMigrations,
         update_strategy: UpdateStrategy,
-    ) -> MigrationResult<()> {
-        log::info!("Running pending migrations");
+    ) -> MigrationResult<Vec<PendingMigration>> {
+        let latest_migration = Self::get_latest_migration(db.clone()).await?;
+        let pending_data_migrations = data_migrations
+            .sorted_asc()?
+            .into_iter()
+            .filter(|(name, _)| {
+                latest_migration.as_ref().map_or(true, |latest_migration| {
+                    name.timestamp() > latest_migration.timestamp
+                })
+            })
+            .map(|(name, m)| PendingMigration::Data(name, m));
+
+        let mut pending_migs = Self::get_pending_migrations(all_migrations, db.clone())
+            .await?
+            .into_iter()
+            .map(PendingMigration::File)
+            .chain(pending_data_migrations)
+            .collect::<Vec<_>>();
+        pending_migs.sort_by_key(PendingMigration::name_forward);
 
         let filtered_pending_migrations = match update_strategy {
-            UpdateStrategy::Latest => {
-                Self::get_pending_migrations(all_migrations, db.clone()).await?
-            }
-            UpdateStrategy::Number(count) => {
-                Self::get_pending_migrations(all_migrations, db.clone())
-                    .await?
-                    .into_iter()
-                    .take(count as usize)
-                    .collect::<Vec<_>>()
-            }
+            UpdateStrategy::Latest => pending_migs,
+            UpdateStrategy::Number(count) => pending_migs
+                .into_iter()
+                .take(count as usize)
+                .collect::<Vec<_>>(),
             UpdateStrategy::Till(filecursor) => {
-                let pending_migs = Self::get_pending_migrations(all_migrations, db.clone()).await?;
-
                 let mut migration_found = false;
-                let mut filtered_migs: Vec<PendingMigrationFile> = vec![];
+                let mut filtered_migs: Vec<PendingMigration> = vec![];
 
                 for mig in pending_migs {
-                    filtered_migs.push(mig.clone());
+                    let name = mig.name_forward();
+                    filtered_migs.push(mig);
                     // if *mig.name_forward() == filecursor {
-                    if mig.name_forward().to_up() == filecursor.to_up() {
+                    if name.to_up() == filecursor.to_up() {
                         migration_found = true;
                         break;
                     }
@@ -472,15 +616,108 @@ impl MigrationRunner {
             }
         };
 
-        Self::run_up_pending_migrations(db.clone(), filtered_pending_migrations).await
+        Ok(filtered_pending_migrations)
+    }
+
+    /// Resolves the update strategy into the pending migrations and the migration
+    /// metadata registrations that would be run, without running them.
+    pub async fn plan_pending_migrations(
+        db: Surreal<impl Connection>,
+        all_migrations: Vec<impl Into<MigrationFile> + ::std::fmt::Debug>,
+        data_migrations: &DataMigrations,
+        update_strategy: UpdateStrategy,
+        applied_by: &str,
+    ) -> MigrationResult<MigrationPlan> {
+        let filtered_pending_migrations =
+            Self::get_pending_steps(db, all_migrations, data_migrations, update_strategy).await?;
+
+        Self::generate_up_plan(filtered_pending_migrations, applied_by)
+    }
+
+    /// Applies pending file and data migrations in timestamp order. Consecutive
+    /// file migrations are applied together in a transaction and each data
+    /// migration is run and registered on its own in between.
+    pub async fn apply_pending_migrations(
+        db: Surreal<Any>,
+        all_migrations: Vec<impl Into<MigrationFile> + ::std::fmt::Debug>,
+        data_migrations: &DataMigrations,
+        update_strategy: UpdateStrategy,
+        applied_by: &str,
+    ) -> MigrationResult<()> {
+        log::info!("Running pending migrations");
+
+        let pending_migs =
+            Self::get_pending_steps(db.clone(), all_migrations, data_migrations, update_strategy)
+                .await?;
+
+        log::info!("Running {} migrations", pending_migs.len());
+
+        if pending_migs.is_empty() {
+            log::info!("No new migrations to apply");
+            return Ok(());
+        }
+
+        let migration_count = pending_migs.len();
+        let mut files = vec![];
+        for pending in pending_migs {
+            match pending {
+                PendingMigration::File(_) => files.push(pending),
+                PendingMigration::Data(name, m) => {
+                    Self::apply_files(db.clone(), std::mem::take(&mut files), applied_by).await?;
+
+                    let started_at = Utc::now();
+                    let applied = m.apply(db.clone()).await;
+                    MigrationHistory::record(
+                        db.clone(),
+                        &[name],
+                        Direction::Up,
+                        applied_by,
+                        started_at,
+                        applied.as_ref().err(),
+                    )
+                    .await?;
+                    applied?;
+                }
+            }
+        }
+        Self::apply_files(db.clone(), files, applied_by).await?;
+
+        log::info!("Applied {} migrations", migration_count);
+
+        Ok(())
+    }
+
+    async fn apply_files(
+        db: Surreal<impl Connection>,
+        files: Vec<PendingMigration>,
+        applied_by: &str,
+    ) -> MigrationResult<()> {
+        if files.is_empty() {
+            return Ok(());
+        }
+
+        let plan = Self::generate_up_plan(files, applied_by)?;
+        log::info!("Marking {} query(ies) as registered", plan.files.len());
+        println!("{}", plan.queries().build());
+        Self::execute_plan_recorded(db.clone(), &plan, applied_by).await?;
+
+        Ok(())
     }
 
     pub(crate) async fn list_migrations(
         db: Surreal<impl Connection>,
-        migrations_local_dir: Vec<MigrationFilename>,
+        mut migrations_local_dir: Vec<MigrationFilename>,
+        data_migrations: &DataMigrations,
         status: Status,
         mode: Mode,
     ) -> MigrationResult<Vec<MigrationFilename>> {
+        migrations_local_dir.extend(
+            data_migrations
+                .sorted_asc()?
+                .into_iter()
+                .map(|(name, _)| name),
+        );
+
         let migrations = match status {
             Status::All => {
                 let mut migrations = migrations_local_dir;
@@ -514,7 +751,13 @@ impl MigrationRunner {
                     .order_by(Migration::schema().timestamp.asc())
                     .return_many::<Migration>(db.clone())
                     .await?;
-                Self::_get_db_migrations_meta_from_mig_files(migrations_local_dir, db_migs, mode)?
+                let mut migrations = Self::_get_db_migrations_meta_from_mig_files(
+                    migrations_local_dir,
+                    db_migs,
+                    mode,
+                )?;
+                migrations.sort_by_key(|name| name.timestamp());
+                migrations
             }
         };
         Ok(migrations)
@@ -0,0 +1,484 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use sorm_query::{DbResources, Table};
+use surrealdb::{engine::any::Any, Surreal};
+
+use crate::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ResourceKind {
+    Analyzer,
+    Function,
+    Param,
+    Scope,
+    Token,
+    User,
+    Table,
+    Field,
+    Index,
+    Event,
+}
+
+impl Display for ResourceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            Self::Analyzer => "analyzer",
+            Self::Function => "function",
+            Self::Param => "param",
+            Self::Scope => "scope",
+            Self::Token => "token",
+            Self::User => "user",
+            Self::Table => "table",
+            Self::Field => "field",
+            Self::Index => "index",
+            Self::Event => "event",
+        };
+        write!(f, "{kind}")
+    }
+}
+
+/// A resource that differs between an expected and an actual database state
+#[derive(Debug, Clone)]
+pub enum ResourceDrift {
+    /// Defined in the expected state but not in the actual state
+    Missing {
+        kind: ResourceKind,
+        table: Option<Table>,
+        name: String,
+        expected: DefineStatementRaw,
+    },
+    /// Defined in the actual state but not in the expected state
+    Extra {
+        kind: ResourceKind,
+        table: Option<Table>,
+        name: String,
+        actual: DefineStatementRaw,
+    },
+    /// Defined in both states but with different definitions
+    Changed {
+        kind: ResourceKind,
+        table: Option<Table>,
+        name: String,
+        expected: DefineStatementRaw,
+        actual: DefineStatementRaw,
+    },
+}
+
+impl ResourceDrift {
+    pub fn kind(&self) -> ResourceKind {
+        match self {
+            Self::Missing { kind, .. } | Self::Extra { kind, .. } | Self::Changed { kind, .. } => {
+                *kind
+            }
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Missing { name, .. } | Self::Extra { name, .. } | Self::Changed { name, .. } => {
+                name
+            }
+        }
+    }
+}
+
+impl Display for ResourceDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let on_table = |table: &Option<Table>| {
+            table
+                .as_ref()
+                .map(|t| format!(" on table `{t}`"))
+                .unwrap_or_default()
+        };
+
+        match self {
+            Self::Missing {
+                kind,
+                table,
+                name,
+                expected,
+            } => write!(
+                f,
+                "missing {kind} `{name}`{}\n      expected: {expected}",
+                on_table(table)
+            ),
+            Self::Extra {
+                kind,
+                table,
+                name,
+                actual,
+            } => write!(
+                f,
+                "extra {kind} `{name}`{}\n      actual: {actual}",
+                on_table(table)
+            ),
+            Self::Changed {
+                kind,
+                table,
+                name,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "changed {kind} `{name}`{}\n      expected: {expected}\n      actual: {actual}",
+                on_table(table)
+            ),
+        }
+    }
+}
+
+/// A migration file that does not line up with the migrations applied to the live database
+#[derive(Debug, Clone)]
+pub enum MigrationFileDrift {
+    /// The applied migration file was edited after it was applied
+    ChecksumMismatch {
+        migration_name: String,
+        expected_checksum: Checksum,
+        actual_checksum: Checksum,
+    },
+    /// The migration was applied but its file is no longer in the migration directory
+    FileMissing { migration_name: String },
+    /// The migration file has not been applied
+    Pending { migration_name: String },
+}
+
+impl Display for MigrationFileDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ChecksumMismatch {
+                migration_name,
+                expected_checksum,
+                actual_checksum,
+            } => write!(
+                f,
+                "checksum mismatch in {migration_name}. Applied checksum: {expected_checksum}. File checksum: {actual_checksum}"
+            ),
+            Self::FileMissing { migration_name } => {
+                write!(f, "applied migration {migration_name} is missing from the migration directory")
+            }
+            Self::Pending { migration_name } => {
+                write!(f, "migration {migration_name} has not been applied")
+            }
+        }
+    }
+}
+
+/// Drift between the codebase, the migration directory replayed into an in-memory
+/// database and the live database.
+#[derive(Debug, Clone, Default)]
+pub struct DriftReport {
+    /// Expected: codebase. Actual: migration directory.
+    /// Non empty when migrations have not been generated for the latest codebase changes.
+    pub codebase_vs_migrations: Vec<ResourceDrift>,
+    /// Expected: migration directory. Actual: live database.
+    /// Non empty when migrations are pending or the live database was changed by hand.
+    pub migrations_vs_live: Vec<ResourceDrift>,
+    /// Migration files against the migration records in the live database
+    pub migration_files: Vec<MigrationFileDrift>,
+}
+
+impl DriftReport {
+    pub fn has_drift(&self) -> bool {
+        !self.codebase_vs_migrations.is_empty()
+            || !self.migrations_vs_live.is_empty()
+            || !self.migration_files.is_empty()
+    }
+
+    pub async fn detect(
+        db: Surreal<Any>,
+        file_manager: &MigrationConfig,
+        codebase_resources: &impl DbResources,
+    ) -> MigrationResult<Self> {
+        let migration_flag = file_manager.detect_migration_type()?;
+        let ComparisonDatabase { left, right } = ComparisonDatabase::init().await;
+
+        match migration_flag {
+            MigrationFlag::TwoWay => {
+                left.run_twoway_up_migrations(file_manager, false).await?;
+            }
+            MigrationFlag::OneWay => {
+                left.run_oneway_migrations(file_manager, false).await?;
+            }
+        };
+        right
+            .run_codebase_schema_queries(codebase_resources, migration_flag)
+            .await?;
+
+        let left = left.get_all_resources().await?;
+        let right = right.get_all_resources().await?;
+        let live = FullDbInfo::from_db(db.clone()).await?;
+
+        Ok(Self {
+            codebase_vs_migrations: Self::diff(&right, &left),
+            migrations_vs_live: Self::diff(&left, &live),
+            migration_files: Self::diff_migration_files(db, file_manager, migration_flag).await?,
+        })
+    }
+
+    /// Compares all top level resources and the fields, indexes and events of tables
+    /// defined in both states. The migration lock table only exists while
+    /// migrations are running and the migration history table is created on the
+    /// first run rather than by a migration, so both are ignored.
+    pub fn diff(expected: &FullDbInfo, actual: &FullDbInfo) -> Vec<ResourceDrift> {
+        let mut drifts = vec![];
+        let ignored_tables = [
+            MigrationLock::table().to_string(),
+            MigrationHistory::table().to_string(),
+        ];
+
+        Self::diff_resources(
+            ResourceKind::Analyzer,
+            None,
+            &expected.analyzers(),
+            &actual.analyzers(),
+            &mut drifts,
+        );
+        Self::diff_resources(
+            ResourceKind::Function,
+            None,
+            &expected.functions(),
+            &actual.functions(),
+            &mut drifts,
+        );
+        Self::diff_resources(
+            ResourceKind::Param,
+            None,
+            &expected.params(),
+            &actual.params(),
+            &mut drifts,
+        );
+        Self::diff_resources(
+            ResourceKind::Scope,
+            None,
+            &expected.scopes(),
+            &actual.scopes(),
+            &mut drifts,
+        );
+        Self::diff_resources(
+            ResourceKind::Token,
+            None,
+            &expected.tokens(),
+            &actual.tokens(),
+            &mut drifts,
+        );
+        Self::diff_resources(
+            ResourceKind::User,
+            None,
+            &expected.users(),
+            &actual.users(),
+            &mut drifts,
+        );
+        Self::diff_resources(
+            ResourceKind::Table,
+            None,
+            &expected.tables(),
+            &actual.tables(),
+            &mut drifts,
+        );
+        drifts.retain(|d| {
+            !(d.kind() == ResourceKind::Table && ignored_tables.iter().any(|t| t == d.name()))
+        });
+
+        // Tables missing on either side are already reported as a whole
+        for table in expected.get_tables() {
+            let (Some(expected_table), Some(actual_table)) = (
+                expected.get_table_info(table.clone()),
+                actual.get_table_info(table.clone()),
+            ) else {
+                continue;
+            };
+
+            Self::diff_resources(
+                ResourceKind::Field,
+                Some(&table),
+                &expected_table.fields(),
+                &actual_table.fields(),
+                &mut drifts,
+            );
+            Self::diff_resources(
+                ResourceKind::Index,
+                Some(&table),
+                &expected_table.indexes(),
+                &actual_table.indexes(),
+                &mut drifts,
+            );
+            Self::diff_resources(
+                ResourceKind::Event,
+                Some(&table),
+                &expected_table.events(),
+                &actual_table.events(),
+                &mut drifts,
+            );
+        }
+
+        drifts
+    }
+
+    fn diff_resources(
+        kind: ResourceKind,
+        table: Option<&Table>,
+        expected: &impl Informational,
+        actual: &impl Informational,
+        drifts: &mut Vec<ResourceDrift>,
+    ) {
+        let expected_names = expected.get_names_as_set();
+        let actual_names = actual.get_names_as_set();
+        let table = table.cloned();
+
+        for name in expected_names.union(&actual_names) {
+            let drift = match (
+                expected.get_definition(name).cloned(),
+                actual.get_definition(name).cloned(),
+            ) {
+                (Some(expected), None) => ResourceDrift::Missing {
+                    kind,
+                    table: table.clone(),
+                    name: name.clone(),
+                    expected,
+                },
+                (None, Some(actual)) => ResourceDrift::Extra {
+                    kind,
+                    table: table.clone(),
+                    name: name.clone(),
+                    actual,
+                },
+                (Some(expected), Some(actual)) if expected != actual => ResourceDrift::Changed {
+                    kind,
+                    table: table.clone(),
+                    name: name.clone(),
+                    expected,
+                    actual,
+                },
+                _ => continue,
+            };
+            drifts.push(drift);
+        }
+    }
+
+    async fn diff_migration_files(
+        db: Surreal<Any>,
+        file_manager: &MigrationConfig,
+        migration_flag: MigrationFlag,
+    ) -> MigrationResult<Vec<MigrationFileDrift>> {
+        // Applied migrations are recorded by the name of the up/oneway file
+        let mut files: BTreeMap<String, (FileMetadata, Option<FileMetadata>)> = match migration_flag
+        {
+            MigrationFlag::TwoWay => file_manager
+                .get_two_way_migrations_sorted_asc(false)?
+                .into_iter()
+                .map(|m| (m.up.name.to_string(), (m.up, Some(m.down))))
+                .collect(),
+            MigrationFlag::OneWay => file_manager
+                .get_oneway_migrations_sorted_asc(false)?
+                .into_iter()
+                .map(|m| (m.name().to_string(), (m.file_meta().clone(), None)))
+                .collect(),
+        };
+        // Data migrations are checksummed by their declared version
+        for (name, m) in file_manager.data_migrations.sorted_asc()? {
+            files.insert(
+                name.to_string(),
+                (FileMetadata::new(name, m.content()), None),
+            );
+        }
+        let applied = Migration::get_all_desc(db).await;
+
+        let mut drifts = vec![];
+        for migration in applied.iter().rev() {
+            let Some((up, down)) = files.get(&migration.name) else {
+                drifts.push(MigrationFileDrift::FileMissing {
+                    migration_name: migration.name.clone(),
+                });
+                continue;
+            };
+
+            if file_manager.mode() == Mode::Lax {
+                continue;
+            }
+
+            let checksums = [
+                (Some(&migration.checksum_up), Some(up)),
+                (migration.checksum_down.as_ref(), down.as_ref()),
+            ];
+            for (expected_checksum, file) in checksums {
+                let (Some(expected_checksum), Some(file)) = (expected_checksum, file) else {
+                    continue;
+                };
+                let actual_checksum = file.content.as_checksum()?;
+                if *expected_checksum != actual_checksum {
+                    drifts.push(MigrationFileDrift::ChecksumMismatch {
+                        migration_name: file.name.to_string(),
+                        expected_checksum: expected_checksum.clone(),
+                        actual_checksum,
+                    });
+                }
+            }
+        }
+
+        for name in files.keys() {
+            if !applied.iter().any(|m| &m.name == name) {
+                drifts.push(MigrationFileDrift::Pending {
+                    migration_name: name.clone(),
+                });
+            }
+        }
+
+        Ok(drifts)
+    }
+}
+
+impl Display for DriftReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.has_drift() {
+            return write!(
+                f,
+                "No drift between the codebase, the migration directory and the live database"
+            );
+        }
+
+        let sections: [(&str, Vec<String>); 3] = [
+            (
+                "Codebase vs migration directory (generate a migration to fix)",
+                self.codebase_vs_migrations
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            ),
+            (
+                "Migration directory vs live database",
+                self.migrations_vs_live
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            ),
+            (
+                "Migration files vs applied migrations",
+                self.migration_files
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            ),
+        ];
+
+        for (title, drifts) in sections {
+            if drifts.is_empty() {
+                writeln!(f, "{title}: in sync")?;
+                continue;
+            }
+
+            writeln!(f, "{title}: {} drift(s)", drifts.len())?;
+            for drift in drifts {
+                writeln!(f, "  - {drift}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -8,8 +8,8 @@
 use surrealdb::{engine::any::Any, Surreal};
 
 use crate::{
-    FileMetadata, MigrationConfig, MigrationFileOneWay, MigrationFileTwoWayPair, MigrationResult,
-    Mode, UpdateStrategy,
+    DataMigrations, FileMetadata, MigrationConfig, MigrationFileOneWay, MigrationFileTwoWayPair,
+    MigrationResult, Mode, UpdateStrategy,
 };
 
 #[derive(Clone, Debug)]
@@ -22,11 +22,27 @@ pub struct EmbeddedMigrationTwoWay {
 #[derive(Debug, Clone)]
 pub struct EmbeddedMigrationsTwoWay {
     migrations: &'static [EmbeddedMigrationTwoWay],
+    data_migrations: DataMigrations,
 }
 
 impl EmbeddedMigrationsTwoWay {
     pub const fn new(migrations: &'static [EmbeddedMigrationTwoWay]) -> Self {
-        Self { migrations }
+        Self::with_data_migrations(migrations, DataMigrations::new(&[]))
+    }
+
+    /// Embedded migrations run in order with Rust data migrations
+    pub const fn with_data_migrations(
+        migrations: &'static [EmbeddedMigrationTwoWay],
+        data_migrations: DataMigrations,
+    ) -> Self {
+        Self {
+            migrations,
+            data_migrations,
+        }
+    }
+
+    pub fn data_migrations(&self) -> &DataMigrations {
+        &self.data_migrations
     }
 
     pub async fn run(
@@ -82,6 +98,7 @@ impl EmbeddedMigrationsTwoWay {
 #[derive(Debug, Clone)]
 pub struct EmbeddedMigrationsOneWay {
     migrations: &'static [EmbeddedMigrationOneWay],
+    data_migrations: DataMigrations,
 }
 
 impl EmbeddedMigrationsOneWay {
@@ -124,7 +141,22 @@ impl EmbeddedMigrationsOneWay {
 
 impl EmbeddedMigrationsOneWay {
     pub const fn new(migrations: &'static [EmbeddedMigrationOneWay]) -> Self {
-        Self { migrations }
+        Self::with_data_migrations(migrations, DataMigrations::new(&[]))
+    }
+
+    /// Embedded migrations run in order with Rust data migrations
+    pub const fn with_data_migrations(
+        migrations: &'static [EmbeddedMigrationOneWay],
+        data_migrations: DataMigrations,
+    ) -> Self {
+        Self {
+            migrations,
+            data_migrations,
+        }
+    }
+
+    pub fn data_migrations(&self) -> &DataMigrations {
+        &self.data_migrations
     }
 }
 
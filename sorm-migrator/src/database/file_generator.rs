@@ -5,7 +5,7 @@
  * Licensed under the MIT license
  */
 
-use std::{collections::BTreeMap, ops::Deref};
+use std::ops::Deref;
 
 use sorm_query::{statements::info_for, *};
 use surrealdb::{
@@ -41,8 +41,8 @@ impl Deref for RightFullDbInfo {
 
 #[derive(Debug, Clone)]
 pub struct ComparisonDatabase {
-    left: LeftDatabase,
-    right: RightDatabase,
+    pub(crate) left: LeftDatabase,
+    pub(crate) right: RightDatabase,
 }
 
 impl ComparisonDatabase {
@@ -88,17 +88,7 @@ impl MigratorDatabase {
     }
 
     pub async fn get_all_resources(&self) -> MigrationResult<FullDbInfo> {
-        let top_level_resources = self.get_db_info().await?;
-        let mut fields_by_table = BTreeMap::new();
-        for table in top_level_resources.tables().get_names() {
-            let table_info = self.get_table_info(table.clone()).await?;
-            fields_by_table.insert(table.into(), table_info);
-        }
-        let all_resources = FullDbInfo {
-            all_resources: top_level_resources,
-            table_resources: fields_by_table,
-        };
-        Ok(all_resources)
+        FullDbInfo::from_db(self.db()).await
     }
 
     pub async fn execute(&self, query: String) -> MigrationResult<()> {
@@ -141,15 +131,64 @@ impl MigratorDatabase {
             prompter: &prompter,
         };
 
-        let tables = init.new_tables(&codebase_resources).queries()?;
+        let migration_reset = Self::get_migration_reset_queries(file_manager)?;
+        let (up_queries_str, down_queries_str, changes) =
+            Self::diff_queries(&init, &codebase_resources, migration_reset)?;
+        let acknowledged_tables = codebase_resources
+            .tables_allowing_destructive_changes()
+            .into_iter()
+            .chain(file_manager.acknowledged_destructive_tables.to_vec())
+            .collect::<Vec<_>>();
+        file_manager
+            .destructive_change_policy
+            .enforce(&changes, &acknowledged_tables)?;
+
+        let query_str = format!("{up_queries_str}{down_queries_str}");
+
+        let migration_file = MigrationFile::new(
+            &migration_basename,
+            &file_manager.migration_flag_checked()?,
+            &up_queries_str.into(),
+            &down_queries_str.into(),
+        )?;
+
+        if query_str.trim().is_empty() {
+            match prompter.prompt_empty_migrations_trigger() {
+                Ok(true) => {
+                    migration_file.create_file(file_manager)?;
+                    log::info!("New migration generated.");
+                }
+                Ok(false) => {
+                    log::info!("No migration created");
+                }
+                Err(e) => {
+                    return Err(MigrationError::PromptError(e));
+                }
+            };
+        } else {
+            migration_file.create_file(file_manager)?;
+            log::info!("New migration generated.");
+            log_data_losing_changes(&changes);
+        };
+
+        Ok(())
+    }
+
+    /// Up and down queries that take the left resources to the right resources,
+    /// with the classification of the table and field changes in up
+    pub(crate) fn diff_queries(
+        init: &ComparisonsInit,
+        codebase_resources: &impl DbResources,
+        migration_reset: Queries,
+    ) -> MigrationResult<(String, String, Vec<SchemaChange>)> {
+        let tables = init.new_tables(codebase_resources).queries()?;
         let analyzers = init.new_analyzers().queries()?.intersperse_new_lines();
         let params = init.new_params().queries()?.intersperse_new_lines();
         let functions = init.new_functions().queries()?.intersperse_new_lines();
         let scopes = init.new_scopes().queries()?.intersperse_new_lines();
         let tokens = init.new_tokens().queries()?.intersperse_new_lines();
         let users = init.new_users().queries()?.intersperse_new_lines();
-        let migration_reset =
-            Self::get_migration_reset_queries(file_manager)?.intersperse_new_lines();
+        let migration_reset = migration_reset.intersperse_new_lines();
 
         let resources = vec![
             migration_reset,
@@ -164,6 +203,7 @@ impl MigratorDatabase {
 
         let mut up_queries = vec![];
         let mut down_queries = vec![];
+        let mut changes = vec![];
         for resource in resources {
             let up_is_empty = resource.up_is_empty();
             let down_is_empty = resource.down_is_empty();
@@ -181,6 +221,8 @@ impl MigratorDatabase {
                 down_queries.push(QueryType::NewLine);
                 down_queries.push(QueryType::NewLine);
             }
+
+            changes.extend(resource.changes);
         }
 
         let up_queries_str = up_queries
@@ -199,57 +241,33 @@ impl MigratorDatabase {
             .trim()
             .to_string();
 
-        let query_str = format!("{up_queries_str}{down_queries_str}");
-
-        let migration_file = MigrationFile::new(
-            &migration_basename,
-            &file_manager.migration_flag_checked()?,
-            &up_queries_str.into(),
-            &down_queries_str.into(),
-        )?;
-
-        if query_str.trim().is_empty() {
-            match prompter.prompt_empty_migrations_trigger() {
-                Ok(true) => {
-                    migration_file.create_file(file_manager)?;
-                    log::info!("New migration generated.");
-                }
-                Ok(false) => {
-                    log::info!("No migration created");
-                }
-                Err(e) => {
-                    return Err(MigrationError::PromptError(e));
-                }
-            };
-        } else {
-            migration_file.create_file(file_manager)?;
-            log::info!("New migration generated.");
-        };
-
-        Ok(())
+        Ok((up_queries_str, down_queries_str, changes))
     }
 
     fn get_migration_reset_queries(file_manager: &MigrationConfig) -> MigrationResult<Queries> {
         if file_manager.is_first_migration()? {
-            // Defining before removing is important because
-            // removing a table that doesn't exist will throw an error
-            // and the transaction will be rolled back
-            // so we define the table first, then remove it
-            // then define the table again, to be sure it exists
-            Ok(Queries {
-                up: vec![
-                    QueryType::Comment(
-                        "Resetting migrations metadata table at initialization".into(),
-                    ),
-                    QueryType::DeleteAll(Migration::delete_all()),
-                    QueryType::Comment(
-                        "Resetting migrations metadata table at initialization ending".into(),
-                    ),
-                ],
-                down: vec![],
-            })
+            Ok(Self::migration_reset_queries())
         } else {
             Ok(Queries::default())
         }
     }
+
+    pub(crate) fn migration_reset_queries() -> Queries {
+        // Defining before removing is important because
+        // removing a table that doesn't exist will throw an error
+        // and the transaction will be rolled back
+        // so we define the table first, then remove it
+        // then define the table again, to be sure it exists
+        Queries {
+            up: vec![
+                QueryType::Comment("Resetting migrations metadata table at initialization".into()),
+                QueryType::DeleteAll(Migration::delete_all()),
+                QueryType::Comment(
+                    "Resetting migrations metadata table at initialization ending".into(),
+                ),
+            ],
+            down: vec![],
+            changes: vec![],
+        }
+    }
 }
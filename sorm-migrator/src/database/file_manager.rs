@@ -9,7 +9,7 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use sorm_query::DbResources;
+use sorm_query::{DbResources, Table};
 use surrealdb::{engine::any::Any, Connection, Surreal};
 use typed_builder::TypedBuilder;
 
@@ -35,6 +35,23 @@ pub struct MigrationConfig {
     // After init => Some(MigrationFlag)
     #[builder(default)]
     pub migration_flag: Option<MigrationFlag>,
+    /// Owner and ttl of the advisory lock taken while applying or rolling back migrations
+    #[builder(default)]
+    pub lock: LockOptions,
+    /// Label recorded in the migration history for the migrations applied or rolled back.
+    /// Defaults to the lock owner
+    #[builder(default)]
+    pub applied_by: Option<String>,
+    /// Rust data migrations run in order with the migration files
+    #[builder(default)]
+    pub data_migrations: DataMigrations,
+    /// What generating a migration does when it removes or narrows existing data
+    #[builder(default)]
+    pub destructive_change_policy: DestructiveChangePolicy,
+    /// Tables whose data-losing changes pass the `require-ack` policy, on top of the ones whose
+    /// models are marked `#[sorm(allow_destructive)]`. Needed for tables with no model left.
+    #[builder(default)]
+    pub acknowledged_destructive_tables: Vec<Table>,
 }
 
 impl MigrationConfig {
@@ -66,6 +83,38 @@ impl MigrationConfig {
         self
     }
 
+    pub fn set_lock(mut self, lock: LockOptions) -> Self {
+        self.lock = lock;
+        self
+    }
+
+    pub fn set_applied_by(mut self, applied_by: impl Into<String>) -> Self {
+        self.applied_by = Some(applied_by.into());
+        self
+    }
+
+    pub fn applied_by(&self) -> &str {
+        self.applied_by.as_deref().unwrap_or(&self.lock.owner)
+    }
+
+    pub fn set_data_migrations(mut self, data_migrations: DataMigrations) -> Self {
+        self.data_migrations = data_migrations;
+        self
+    }
+
+    pub fn set_destructive_change_policy(mut self, policy: DestructiveChangePolicy) -> Self {
+        self.destructive_change_policy = policy;
+        self
+    }
+
+    pub fn set_acknowledged_destructive_tables(
+        mut self,
+        tables: impl IntoIterator<Item = impl Into<Table>>,
+    ) -> Self {
+        self.acknowledged_destructive_tables = tables.into_iter().map(Into::into).collect();
+        self
+    }
+
     pub fn relax(mut self) -> Self {
         self.mode = Mode::Lax;
         self
@@ -214,6 +263,31 @@ impl MigrationConfig {
         self.get_migrations_filenames(create_dir_if_not_exists)?
             .unidirectional_pair_meta(&self.resolve_migration_directory(false)?)
     }
+
+    /// Squashes a range of migrations into a single baseline migration and
+    /// rewrites the registered migrations of the database to match.
+    /// The database is checked before any file is touched, so a range that is
+    /// only partially applied leaves the migration directory as is. The registered
+    /// migrations are only rewritten once the baseline has been written, so that
+    /// the database never points at a baseline file that does not exist.
+    pub async fn squash_migrations(
+        &self,
+        db: Surreal<Any>,
+        migration_basename: &Basename,
+        range: &SquashRange,
+        prompter: impl Prompter,
+    ) -> MigrationResult<SquashedMigration> {
+        let squashed =
+            SquashedMigration::generate(self, migration_basename, range, prompter).await?;
+        MigrationLock::run_locked(db.clone(), &self.lock, async {
+            squashed.is_range_applied(db.clone()).await?;
+            squashed.write(self)?;
+            squashed.sync_applied_migrations(db.clone()).await
+        })
+        .await?;
+
+        Ok(squashed)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -257,23 +331,63 @@ impl FileManagerUni {
     /// Make sure the migration directory exists when running migrations
     pub async fn run_pending_migrations(
         &self,
-        db: Surreal<impl Connection>,
+        db: Surreal<Any>,
         update_strategy: UpdateStrategy,
     ) -> MigrationResult<()> {
         let migrations = self.get_migrations()?;
-        MigrationRunner::apply_pending_migrations(db, migrations, update_strategy).await?;
+        MigrationLock::run_locked(
+            db.clone(),
+            &self.lock,
+            MigrationRunner::apply_pending_migrations(
+                db.clone(),
+                migrations,
+                &self.data_migrations,
+                update_strategy,
+                self.applied_by(),
+            ),
+        )
+        .await?;
 
         Ok(())
     }
 
-    pub async fn run_embedded_pending_migrations(
+    /// Resolves the pending migrations that `run_pending_migrations` would apply
+    /// and the SurrealQL it would run, without touching the database schema.
+    pub async fn plan_pending_migrations(
         &self,
         db: Surreal<impl Connection>,
+        update_strategy: UpdateStrategy,
+    ) -> MigrationResult<MigrationPlan> {
+        let migrations = self.get_migrations()?;
+        MigrationRunner::plan_pending_migrations(
+            db,
+            migrations,
+            &self.data_migrations,
+            update_strategy,
+            self.applied_by(),
+        )
+        .await
+    }
+
+    pub async fn run_embedded_pending_migrations(
+        &self,
+        db: Surreal<Any>,
         one_way_embedded_migrations: EmbeddedMigrationsOneWay,
         update_strategy: UpdateStrategy,
     ) -> MigrationResult<()> {
         let migrations = one_way_embedded_migrations.to_migrations_one_way()?;
-        MigrationRunner::apply_pending_migrations(db, migrations, update_strategy).await?;
+        MigrationLock::run_locked(
+            db.clone(),
+            &self.lock,
+            MigrationRunner::apply_pending_migrations(
+                db.clone(),
+                migrations,
+                one_way_embedded_migrations.data_migrations(),
+                update_strategy,
+                self.applied_by(),
+            ),
+        )
+        .await?;
 
         Ok(())
     }
@@ -291,8 +405,14 @@ impl FileManagerUni {
             .map(|m| m.name().to_owned())
             .collect::<Vec<_>>();
 
-        let migrations =
-            MigrationRunner::list_migrations(db.clone(), migrations, status, mode).await?;
+        let migrations = MigrationRunner::list_migrations(
+            db.clone(),
+            migrations,
+            &self.data_migrations,
+            status,
+            mode,
+        )
+        .await?;
 
         Ok(migrations)
     }
@@ -343,24 +463,64 @@ impl FileManagerBi {
     /// Make sure the migration directory exists when running migrations
     pub async fn run_up_pending_migrations(
         &self,
-        db: Surreal<impl Connection>,
+        db: Surreal<Any>,
         update_strategy: UpdateStrategy,
     ) -> MigrationResult<()> {
         let migrations = self.get_migrations()?;
-        MigrationRunner::apply_pending_migrations(db.clone(), migrations, update_strategy).await?;
+        MigrationLock::run_locked(
+            db.clone(),
+            &self.lock,
+            MigrationRunner::apply_pending_migrations(
+                db.clone(),
+                migrations,
+                &self.data_migrations,
+                update_strategy,
+                self.applied_by(),
+            ),
+        )
+        .await?;
 
         Ok(())
     }
 
+    /// Resolves the pending migrations that `run_up_pending_migrations` would apply
+    /// and the SurrealQL it would run, without touching the database schema.
+    pub async fn plan_up_pending_migrations(
+        &self,
+        db: Surreal<impl Connection>,
+        update_strategy: UpdateStrategy,
+    ) -> MigrationResult<MigrationPlan> {
+        let migrations = self.get_migrations()?;
+        MigrationRunner::plan_pending_migrations(
+            db.clone(),
+            migrations,
+            &self.data_migrations,
+            update_strategy,
+            self.applied_by(),
+        )
+        .await
+    }
+
     /// For running embedded migrations
     pub async fn run_up_embedded_pending_migrations(
         &self,
-        db: Surreal<impl Connection>,
+        db: Surreal<Any>,
         two_way_embedded_migrations: EmbeddedMigrationsTwoWay,
         update_strategy: UpdateStrategy,
     ) -> MigrationResult<()> {
         let migrations = two_way_embedded_migrations.to_migrations_two_way()?;
-        MigrationRunner::apply_pending_migrations(db.clone(), migrations, update_strategy).await?;
+        MigrationLock::run_locked(
+            db.clone(),
+            &self.lock,
+            MigrationRunner::apply_pending_migrations(
+                db.clone(),
+                migrations,
+                two_way_embedded_migrations.data_migrations(),
+                update_strategy,
+                self.applied_by(),
+            ),
+        )
+        .await?;
 
         Ok(())
     }
@@ -371,11 +531,26 @@ impl FileManagerBi {
         db: Surreal<Any>,
         rollback_options: RollbackOptions,
     ) -> MigrationResult<()> {
-        MigrationRunner::rollback_migrations(db.clone(), self, rollback_options).await?;
+        MigrationLock::run_locked(
+            db.clone(),
+            &self.lock,
+            MigrationRunner::rollback_migrations(db.clone(), self, rollback_options),
+        )
+        .await?;
 
         Ok(())
     }
 
+    /// Resolves the migrations that `run_down_migrations` would roll back
+    /// and the SurrealQL it would run, without touching the database schema.
+    pub async fn plan_down_migrations(
+        &self,
+        db: Surreal<Any>,
+        rollback_options: RollbackOptions,
+    ) -> MigrationResult<MigrationPlan> {
+        MigrationRunner::plan_rollback_migrations(db.clone(), self, rollback_options).await
+    }
+
     /// List all migrations
     pub async fn list_migrations(
         &self,
@@ -389,8 +564,14 @@ impl FileManagerBi {
             .map(|m| m.up.name)
             .collect::<Vec<_>>();
 
-        let migrations =
-            MigrationRunner::list_migrations(db.clone(), migrations, status, mode).await?;
+        let migrations = MigrationRunner::list_migrations(
+            db.clone(),
+            migrations,
+            &self.data_migrations,
+            status,
+            mode,
+        )
+        .await?;
 
         Ok(migrations)
     }
@@ -36,6 +36,8 @@ pub enum MigrationFilename {
     Up(MigrationNameBasicInfo),
     Down(MigrationNameBasicInfo),
     Unidirectional(MigrationNameBasicInfo),
+    /// Rust data migration registered in `DataMigrations` rather than a file
+    Data(MigrationNameBasicInfo),
 }
 
 impl std::hash::Hash for MigrationFilename {
@@ -295,6 +297,7 @@ impl MigrationFilename {
             MigrationFilename::Up(_) => 1,
             MigrationFilename::Down(_) => 2,
             MigrationFilename::Unidirectional(_) => 3,
+            MigrationFilename::Data(_) => 4,
         }
     }
 
@@ -310,6 +313,10 @@ impl MigrationFilename {
         matches!(self, MigrationFilename::Unidirectional(_))
     }
 
+    pub fn is_data(&self) -> bool {
+        matches!(self, MigrationFilename::Data(_))
+    }
+
     pub fn timestamp(&self) -> Timestamp {
         match self {
             MigrationFilename::Up(MigrationNameBasicInfo { timestamp, .. }) => *timestamp,
@@ -317,6 +324,7 @@ impl MigrationFilename {
             MigrationFilename::Unidirectional(MigrationNameBasicInfo { timestamp, .. }) => {
                 *timestamp
             }
+            MigrationFilename::Data(MigrationNameBasicInfo { timestamp, .. }) => *timestamp,
         }
         .into()
     }
@@ -326,6 +334,7 @@ impl MigrationFilename {
             MigrationFilename::Up(_) => "up.surql".to_string(),
             MigrationFilename::Down(_) => "down.surql".to_string(),
             MigrationFilename::Unidirectional(_) => "surql".to_string(),
+            MigrationFilename::Data(_) => "rs".to_string(),
         }
         .into()
     }
@@ -341,6 +350,9 @@ impl MigrationFilename {
             MigrationFilename::Unidirectional(MigrationNameBasicInfo { basename, .. }) => {
                 format!("{basename}")
             }
+            MigrationFilename::Data(MigrationNameBasicInfo { basename, .. }) => {
+                format!("{basename}")
+            }
         }
         .into()
     }
@@ -358,6 +370,10 @@ impl MigrationFilename {
             MigrationFilename::Unidirectional(MigrationNameBasicInfo {
                 timestamp,
                 basename: name,
+            })
+            | MigrationFilename::Data(MigrationNameBasicInfo {
+                timestamp,
+                basename: name,
             }) => MigrationFilename::Up(MigrationNameBasicInfo {
                 timestamp: *timestamp,
                 basename: name.clone(),
@@ -378,6 +394,10 @@ impl MigrationFilename {
             MigrationFilename::Unidirectional(MigrationNameBasicInfo {
                 timestamp,
                 basename: name,
+            })
+            | MigrationFilename::Data(MigrationNameBasicInfo {
+                timestamp,
+                basename: name,
             }) => MigrationFilename::Down(MigrationNameBasicInfo {
                 timestamp: *timestamp,
                 basename: name.clone(),
@@ -397,6 +417,10 @@ impl MigrationFilename {
             MigrationFilename::Down(MigrationNameBasicInfo {
                 timestamp,
                 basename: name,
+            })
+            | MigrationFilename::Data(MigrationNameBasicInfo {
+                timestamp,
+                basename: name,
             }) => MigrationFilename::Unidirectional(MigrationNameBasicInfo {
                 timestamp: *timestamp,
                 basename: name.clone(),
@@ -405,6 +429,21 @@ impl MigrationFilename {
         }
     }
 
+    /// Same timestamp and direction with a different basename
+    pub fn with_basename(&self, basename: &Basename) -> MigrationFilename {
+        let info = MigrationNameBasicInfo {
+            timestamp: self.timestamp().into_inner(),
+            basename: basename.clone(),
+        };
+
+        match self {
+            MigrationFilename::Up(_) => MigrationFilename::Up(info),
+            MigrationFilename::Down(_) => MigrationFilename::Down(info),
+            MigrationFilename::Unidirectional(_) => MigrationFilename::Unidirectional(info),
+            MigrationFilename::Data(_) => MigrationFilename::Data(info),
+        }
+    }
+
     pub fn create_file(
         &self,
         query: &FileContent,
@@ -502,8 +541,9 @@ enum Direction {
     Up,
     Down,
     OneWay,
+    Data,
 }
-// .up.surql or .down.surql or .surql
+// .up.surql or .down.surql or .surql or .rs
 fn parse_direction(input: &str) -> IResult<&str, Direction> {
     use nom::combinator::value;
 
@@ -511,6 +551,7 @@ fn parse_direction(input: &str) -> IResult<&str, Direction> {
         value(Direction::Up, tag(".up.surql")),
         value(Direction::Down, tag(".down.surql")),
         value(Direction::OneWay, tag(".surql")),
+        value(Direction::Data, tag(".rs")),
     ))(input)?;
     Ok((input, direction))
 }
@@ -542,6 +583,7 @@ fn parse_migration_name_unconsumed(input: &str) -> IResult<&str, MigrationFilena
         Direction::Up => MigrationFilename::Up(basic_info),
         Direction::Down => MigrationFilename::Down(basic_info),
         Direction::OneWay => MigrationFilename::Unidirectional(basic_info),
+        Direction::Data => MigrationFilename::Data(basic_info),
     };
 
     Ok((input, m2))
@@ -575,6 +617,12 @@ impl Display for MigrationFilename {
             }) => {
                 format!("{timestamp}_{name}.surql")
             }
+            MigrationFilename::Data(MigrationNameBasicInfo {
+                timestamp,
+                basename: name,
+            }) => {
+                format!("{timestamp}_{name}.rs")
+            }
         };
         write!(f, "{file_name_str}")
     }
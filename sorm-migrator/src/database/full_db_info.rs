@@ -7,7 +7,8 @@
 
 use std::collections::{BTreeMap, BTreeSet};
 
-use sorm_query::{Field, Table};
+use sorm_query::{statements::info_for, Field, Runnable, Table};
+use surrealdb::{Connection, Surreal};
 
 use crate::*;
 
@@ -18,6 +19,30 @@ pub struct FullDbInfo {
 }
 
 impl FullDbInfo {
+    /// Top level and per table resources of the database the connection is scoped to
+    pub async fn from_db(db: Surreal<impl Connection>) -> MigrationResult<Self> {
+        let all_resources = info_for()
+            .database()
+            .get_data::<DbInfo>(db.clone())
+            .await?
+            .expect("Database not found");
+
+        let mut table_resources = BTreeMap::new();
+        for table in all_resources.tables().get_names() {
+            let table_info = info_for()
+                .table(table.clone())
+                .get_data::<TableResourcesData>(db.clone())
+                .await?
+                .expect("Table not found");
+            table_resources.insert(table.into(), table_info);
+        }
+
+        Ok(Self {
+            all_resources,
+            table_resources,
+        })
+    }
+
     pub fn analyzers(&self) -> Analyzers {
         self.all_resources.analyzers()
     }
@@ -0,0 +1,593 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::{collections::BTreeMap, fmt::Write, str::FromStr};
+
+use convert_case::{Case, Casing};
+use sorm_query::{
+    sql::{self, statements::DefineStatement, Statement},
+    FieldType, Table,
+};
+use surrealdb::{Connection, Surreal};
+
+use crate::*;
+
+/// Generates `Node`, `Edge` and `Object` structs and a `DbResources` impl
+/// from the schema of an existing database.
+#[derive(Debug, Clone)]
+pub struct Introspection {
+    db_info: FullDbInfo,
+}
+
+#[derive(Debug, Clone)]
+enum ModelKind {
+    Node,
+    Edge {
+        in_table: Option<String>,
+        out_table: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct IntrospectedField {
+    name: String,
+    field_type: Option<String>,
+    definition: sql::statements::DefineFieldStatement,
+}
+
+impl IntrospectedField {
+    fn parsed_type(&self) -> Option<FieldType> {
+        self.field_type
+            .as_ref()
+            .and_then(|ty| FieldType::from_str(ty).ok())
+    }
+
+    /// VALUE, ASSERT and DEFAULT clauses cannot be expressed as attributes
+    /// and are kept as doc comments for the user to port by hand.
+    fn has_unmapped_clauses(&self) -> bool {
+        self.definition.value.is_some()
+            || self.definition.assert.is_some()
+            || self.definition.default.is_some()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IntrospectedTable {
+    name: String,
+    schemafull: bool,
+    kind: ModelKind,
+    // All fields including nested ones e.g `address.city`, keyed by field name
+    fields: BTreeMap<String, IntrospectedField>,
+    indexes: Vec<DefineStatementRaw>,
+    events: Vec<DefineStatementRaw>,
+}
+
+struct RustField {
+    ty: String,
+    attribute: Option<String>,
+}
+
+impl Introspection {
+    pub fn new(db_info: FullDbInfo) -> Self {
+        Self { db_info }
+    }
+
+    pub async fn from_db(db: Surreal<impl Connection>) -> MigrationResult<Self> {
+        Ok(Self::new(FullDbInfo::from_db(db).await?))
+    }
+
+    /// Rust source for all the tables and top level resources of the database.
+    /// The migrator's own tables are left out.
+    pub fn generate(&self) -> MigrationResult<String> {
+        let tables = self.tables()?;
+        let mut code = String::new();
+
+        writeln!(
+            code,
+            "// Generated by `introspect`. Review before use: VALUE, ASSERT and DEFAULT\n\
+            // clauses of fields are kept as doc comments and need to be ported by hand."
+        )?;
+        writeln!(code, "use serde::{{Deserialize, Serialize}};")?;
+        writeln!(code, "use sorm::*;")?;
+
+        for table in tables.values() {
+            code.push('\n');
+            self.generate_table(&mut code, table, &tables)?;
+        }
+
+        code.push('\n');
+        self.generate_db_resources(&mut code, &tables)?;
+
+        Ok(code)
+    }
+
+    fn tables(&self) -> MigrationResult<BTreeMap<String, IntrospectedTable>> {
+        let excluded = [
+            Migration::table(),
+            MigrationLock::table(),
+            MigrationHistory::table(),
+        ];
+        let mut tables = BTreeMap::new();
+
+        for table_name in self.db_info.tables().get_names() {
+            let table = Table::new(table_name.clone());
+            if excluded.contains(&table) {
+                continue;
+            }
+
+            let Some(table_def) = self.db_info.tables().get_definition(&table_name).cloned() else {
+                continue;
+            };
+            let schemafull = match Self::parse_definition(&table_def)? {
+                DefineStatement::Table(t) => t.full,
+                _ => false,
+            };
+
+            let table_info = self
+                .db_info
+                .get_table_info(table.clone())
+                .cloned()
+                .unwrap_or_default();
+
+            let mut fields = BTreeMap::new();
+            for field_def in table_info.fields().get_all_definitions() {
+                if let DefineStatement::Field(definition) = Self::parse_definition(&field_def)? {
+                    let field = IntrospectedField {
+                        name: definition.name.to_string(),
+                        field_type: definition.kind.as_ref().map(ToString::to_string),
+                        definition,
+                    };
+                    fields.insert(field.name.clone(), field);
+                }
+            }
+
+            let single_record_table = |field: &str| {
+                fields
+                    .get(field)
+                    .and_then(IntrospectedField::parsed_type)
+                    .and_then(|ft| match ft {
+                        FieldType::Record(tables) if tables.len() == 1 => {
+                            Some(tables[0].to_string())
+                        }
+                        _ => None,
+                    })
+            };
+            let kind = if fields.contains_key("in") && fields.contains_key("out") {
+                ModelKind::Edge {
+                    in_table: single_record_table("in"),
+                    out_table: single_record_table("out"),
+                }
+            } else {
+                ModelKind::Node
+            };
+
+            tables.insert(
+                table_name.clone(),
+                IntrospectedTable {
+                    name: table_name,
+                    schemafull,
+                    kind,
+                    fields,
+                    indexes: table_info.indexes().get_all_definitions(),
+                    events: table_info.events().get_all_definitions(),
+                },
+            );
+        }
+
+        Ok(tables)
+    }
+
+    fn parse_definition(definition: &DefineStatementRaw) -> MigrationResult<DefineStatement> {
+        let query = sql::parse(&definition.to_string()).map_err(|e| {
+            MigrationError::InvalidDefineStatement(format!("{definition}. Error: {e}"))
+        })?;
+
+        match query.into_iter().next() {
+            Some(Statement::Define(stmt)) => Ok(stmt),
+            _ => Err(MigrationError::InvalidDefineStatement(format!(
+                "Not a define statement: {definition}"
+            ))),
+        }
+    }
+
+    fn generate_table(
+        &self,
+        code: &mut String,
+        table: &IntrospectedTable,
+        tables: &BTreeMap<String, IntrospectedTable>,
+    ) -> MigrationResult<()> {
+        let struct_name = Self::struct_name(&table.name);
+        let has_custom_resources = !table.indexes.is_empty() || !table.events.is_empty();
+        let mut nested_objects = vec![];
+
+        let (derive, generics) = match table.kind {
+            ModelKind::Node => ("Node", ""),
+            ModelKind::Edge { .. } => ("Edge", "<In: Node, Out: Node>"),
+        };
+        let table_resources_derive = if has_custom_resources {
+            ""
+        } else {
+            "TableResources, "
+        };
+        let schemafull = if table.schemafull { ", schemafull" } else { "" };
+
+        writeln!(
+            code,
+            "#[derive({derive}, {table_resources_derive}Serialize, Deserialize, Debug, Clone)]"
+        )?;
+        writeln!(code, "#[sorm(table = {}{schemafull})]", table.name)?;
+        writeln!(code, "pub struct {struct_name}{generics} {{")?;
+        writeln!(code, "    pub id: SurrealSimpleId<Self>,")?;
+
+        for field in Self::direct_children(&table.fields, None) {
+            if field.name == "id" {
+                continue;
+            }
+
+            let rust_field = match (&table.kind, field.name.as_str()) {
+                (ModelKind::Edge { .. }, "in") => RustField {
+                    ty: "LinkOne<In>".into(),
+                    attribute: Some("link_one = In".into()),
+                },
+                (ModelKind::Edge { .. }, "out") => RustField {
+                    ty: "LinkOne<Out>".into(),
+                    attribute: Some("link_one = Out".into()),
+                },
+                _ => self.rust_field(
+                    field,
+                    &table.fields,
+                    &table.name,
+                    &struct_name,
+                    tables,
+                    &mut nested_objects,
+                ),
+            };
+            Self::write_field(code, field, &rust_field, "    ")?;
+        }
+        writeln!(code, "}}")?;
+
+        if let ModelKind::Edge {
+            in_table: Some(in_table),
+            out_table: Some(out_table),
+        } = &table.kind
+        {
+            if tables.contains_key(in_table) && tables.contains_key(out_table) {
+                writeln!(
+                    code,
+                    "\npub type {} = {struct_name}<{}, {}>;",
+                    Self::edge_alias(&table.name, in_table, out_table),
+                    Self::struct_name(in_table),
+                    Self::struct_name(out_table),
+                )?;
+            }
+        }
+
+        while let Some((object_name, field_prefix)) = nested_objects.pop() {
+            writeln!(
+                code,
+                "\n#[derive(Object, Serialize, Deserialize, Debug, Clone)]"
+            )?;
+            writeln!(code, "pub struct {object_name} {{")?;
+            for field in Self::direct_children(&table.fields, Some(&field_prefix)) {
+                let rust_field = self.rust_field(
+                    field,
+                    &table.fields,
+                    &table.name,
+                    &object_name,
+                    tables,
+                    &mut nested_objects,
+                );
+                Self::write_field(code, field, &rust_field, "    ")?;
+            }
+            writeln!(code, "}}")?;
+        }
+
+        if has_custom_resources {
+            let impl_generics = match table.kind {
+                ModelKind::Node => format!("impl TableResources for {struct_name}"),
+                ModelKind::Edge { .. } => {
+                    format!("impl<In: Node, Out: Node> TableResources for {struct_name}<In, Out>")
+                }
+            };
+            writeln!(code, "\n{impl_generics} {{")?;
+            Self::write_raw_definitions_fn(code, "events_definitions", &table.events, "    ")?;
+            Self::write_raw_definitions_fn(code, "indexes_definitions", &table.indexes, "    ")?;
+            writeln!(code, "}}")?;
+        }
+
+        Ok(())
+    }
+
+    fn generate_db_resources(
+        &self,
+        code: &mut String,
+        tables: &BTreeMap<String, IntrospectedTable>,
+    ) -> MigrationResult<()> {
+        let mut table_resources = vec![];
+        let mut skipped_edges = vec![];
+        for table in tables.values() {
+            match &table.kind {
+                ModelKind::Node => table_resources.push(Self::struct_name(&table.name)),
+                ModelKind::Edge {
+                    in_table: Some(in_table),
+                    out_table: Some(out_table),
+                } if tables.contains_key(in_table) && tables.contains_key(out_table) => {
+                    table_resources.push(Self::edge_alias(&table.name, in_table, out_table))
+                }
+                ModelKind::Edge { .. } => skipped_edges.push(table.name.clone()),
+            }
+        }
+
+        writeln!(code, "#[derive(Debug, Clone)]")?;
+        writeln!(code, "pub struct Resources;")?;
+        writeln!(code, "\nimpl DbResources for Resources {{")?;
+        for edge in skipped_edges {
+            writeln!(
+                code,
+                "    // Edge `{edge}` is left out because its in/out tables could not be inferred"
+            )?;
+        }
+        writeln!(
+            code,
+            "    create_table_resources!({});",
+            table_resources.join(", ")
+        )?;
+
+        let top_level_resources = [
+            ("analyzers", self.db_info.analyzers().get_all_definitions()),
+            ("functions", self.db_info.functions().get_all_definitions()),
+            ("params", self.db_info.params().get_all_definitions()),
+            ("scopes", self.db_info.scopes().get_all_definitions()),
+            ("tokens", self.db_info.tokens().get_all_definitions()),
+            ("users", self.db_info.users().get_all_definitions()),
+        ];
+        for (fn_name, definitions) in top_level_resources {
+            if definitions.is_empty() {
+                continue;
+            }
+            writeln!(code, "\n    fn {fn_name}(&self) -> Vec<Raw> {{")?;
+            Self::write_raw_definitions(code, &definitions, "        ")?;
+            writeln!(code, "    }}")?;
+        }
+        writeln!(code, "}}")?;
+
+        Ok(())
+    }
+
+    /// Maps a database field type to the Rust type and the link/nest attribute of the field.
+    /// Links are only generated for records of a single table that is also introspected.
+    fn rust_field(
+        &self,
+        field: &IntrospectedField,
+        all_fields: &BTreeMap<String, IntrospectedField>,
+        table_name: &str,
+        parent_struct_name: &str,
+        tables: &BTreeMap<String, IntrospectedTable>,
+        nested_objects: &mut Vec<(String, String)>,
+    ) -> RustField {
+        let Some(field_type) = field.parsed_type() else {
+            return RustField {
+                ty: "sql::Value".into(),
+                attribute: None,
+            };
+        };
+
+        let linked_struct = |ft: &FieldType| match ft {
+            FieldType::Record(record_tables) if record_tables.len() == 1 => {
+                let record_table = record_tables[0].to_string();
+                tables
+                    .contains_key(&record_table)
+                    .then(|| (record_table.clone(), Self::struct_name(&record_table)))
+            }
+            _ => None,
+        };
+        let unwrapped = match &field_type {
+            FieldType::Option(inner) => inner.as_ref(),
+            ft => ft,
+        };
+        let has_children = !Self::direct_children(all_fields, Some(&field.name)).is_empty();
+        let db_name = field.name.rsplit('.').next().unwrap_or(&field.name);
+        let nested_name = format!("{parent_struct_name}{}", Self::struct_name(db_name));
+
+        match unwrapped {
+            ft @ FieldType::Record(_) => match linked_struct(ft) {
+                Some((record_table, linked)) if record_table == table_name => RustField {
+                    ty: format!("LinkSelf<{linked}>"),
+                    attribute: Some(format!("link_self = {linked}")),
+                },
+                Some((_, linked)) => RustField {
+                    ty: format!("LinkOne<{linked}>"),
+                    attribute: Some(format!("link_one = {linked}")),
+                },
+                None => RustField {
+                    ty: Self::rust_type(&field_type),
+                    attribute: None,
+                },
+            },
+            FieldType::Array(item, _) | FieldType::Set(item, _) => {
+                if let Some((_, linked)) = linked_struct(item) {
+                    return RustField {
+                        ty: format!("LinkMany<{linked}>"),
+                        attribute: Some(format!("link_many = {linked}")),
+                    };
+                }
+
+                let item_path = format!("{}[*]", field.name);
+                let item_has_children =
+                    !Self::direct_children(all_fields, Some(&item_path)).is_empty();
+                if matches!(item.as_ref(), FieldType::Object) && item_has_children {
+                    nested_objects.push((nested_name.clone(), item_path));
+                    return RustField {
+                        ty: format!("Vec<{nested_name}>"),
+                        attribute: Some(format!("nest_array = {nested_name}")),
+                    };
+                }
+
+                RustField {
+                    ty: Self::rust_type(&field_type),
+                    attribute: None,
+                }
+            }
+            // Optional objects are left as plain objects
+            FieldType::Object if has_children && matches!(field_type, FieldType::Object) => {
+                nested_objects.push((nested_name.clone(), field.name.clone()));
+                RustField {
+                    ty: nested_name.clone(),
+                    attribute: Some(format!("nest_object = {nested_name}")),
+                }
+            }
+            _ => RustField {
+                ty: Self::rust_type(&field_type),
+                attribute: None,
+            },
+        }
+    }
+
+    fn rust_type(field_type: &FieldType) -> String {
+        match field_type {
+            FieldType::Any | FieldType::Null | FieldType::Union(_) => "sql::Value".into(),
+            FieldType::Bool => "bool".into(),
+            FieldType::Bytes => "Vec<u8>".into(),
+            FieldType::Datetime => "chrono::DateTime<chrono::Utc>".into(),
+            FieldType::Decimal | FieldType::Number => "sql::Number".into(),
+            FieldType::Duration => "std::time::Duration".into(),
+            FieldType::Float => "f64".into(),
+            FieldType::Int => "i64".into(),
+            FieldType::Object => "sql::Object".into(),
+            FieldType::String => "String".into(),
+            FieldType::Uuid => "sql::Uuid".into(),
+            FieldType::Record(_) => "sql::Thing".into(),
+            FieldType::Geometry(_) => "sql::Geometry".into(),
+            FieldType::Option(inner) => format!("Option<{}>", Self::rust_type(inner)),
+            FieldType::Array(item, _) | FieldType::Set(item, _) => {
+                format!("Vec<{}>", Self::rust_type(item))
+            }
+        }
+    }
+
+    /// Fields one level below the prefix e.g `address.city` is a direct child of `address`
+    /// and `tags[*]` is a direct child of `tags`. Top level fields when there is no prefix.
+    fn direct_children<'a>(
+        fields: &'a BTreeMap<String, IntrospectedField>,
+        prefix: Option<&str>,
+    ) -> Vec<&'a IntrospectedField> {
+        fields
+            .values()
+            .filter(|f| match prefix {
+                None => !f.name.contains(['.', '[']),
+                Some(prefix) => f
+                    .name
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_prefix('.'))
+                    .is_some_and(|rest| !rest.is_empty() && !rest.contains(['.', '['])),
+            })
+            .collect()
+    }
+
+    fn write_field(
+        code: &mut String,
+        field: &IntrospectedField,
+        rust_field: &RustField,
+        indent: &str,
+    ) -> MigrationResult<()> {
+        let db_name = field.name.rsplit('.').next().unwrap_or(&field.name);
+        let ident = Self::field_ident(db_name);
+
+        if field.has_unmapped_clauses() {
+            writeln!(code, "{indent}/// {}", field.definition)?;
+        }
+        if ident.trim_start_matches("r#") != db_name {
+            writeln!(code, "{indent}#[serde(rename = \"{db_name}\")]")?;
+        }
+
+        let mut attributes = rust_field.attribute.iter().cloned().collect::<Vec<_>>();
+        if let Some(ty) = &field.field_type {
+            attributes.push(format!("ty = \"{ty}\""));
+        }
+        if !attributes.is_empty() {
+            writeln!(code, "{indent}#[sorm({})]", attributes.join(", "))?;
+        }
+        writeln!(code, "{indent}pub {ident}: {},", rust_field.ty)?;
+
+        Ok(())
+    }
+
+    fn write_raw_definitions_fn(
+        code: &mut String,
+        fn_name: &str,
+        definitions: &[DefineStatementRaw],
+        indent: &str,
+    ) -> MigrationResult<()> {
+        if definitions.is_empty() {
+            return Ok(());
+        }
+        writeln!(code, "{indent}fn {fn_name}() -> Vec<Raw> {{")?;
+        Self::write_raw_definitions(code, definitions, &format!("{indent}    "))?;
+        writeln!(code, "{indent}}}")?;
+        Ok(())
+    }
+
+    fn write_raw_definitions(
+        code: &mut String,
+        definitions: &[DefineStatementRaw],
+        indent: &str,
+    ) -> MigrationResult<()> {
+        writeln!(code, "{indent}vec![")?;
+        for definition in definitions {
+            let definition = definition.trim();
+            // Pick enough hashes for the raw string not to be closed by the definition itself
+            let hashes = (1..)
+                .map(|n| "#".repeat(n))
+                .find(|h| !definition.contains(&format!("\"{h}")))
+                .unwrap_or_default();
+            writeln!(
+                code,
+                "{indent}    Raw::new(r{hashes}\"{definition}\"{hashes}),"
+            )?;
+        }
+        writeln!(code, "{indent}]")?;
+        Ok(())
+    }
+
+    fn struct_name(table_name: &str) -> String {
+        table_name.to_case(Case::Pascal)
+    }
+
+    fn edge_alias(edge_table: &str, in_table: &str, out_table: &str) -> String {
+        format!(
+            "{}{}{}",
+            Self::struct_name(in_table),
+            Self::struct_name(edge_table),
+            Self::struct_name(out_table)
+        )
+    }
+
+    fn field_ident(db_name: &str) -> String {
+        const KEYWORDS: [&str; 38] = [
+            "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern",
+            "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+            "pub", "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use",
+            "where", "while", "abstract", "box", "try", "yield",
+        ];
+        const NON_RAW_KEYWORDS: [&str; 4] = ["self", "Self", "crate", "super"];
+
+        let ident = db_name.to_case(Case::Snake);
+        let ident = if ident.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("_{ident}")
+        } else {
+            ident
+        };
+
+        if KEYWORDS.contains(&ident.as_str()) {
+            format!("r#{ident}")
+        } else if NON_RAW_KEYWORDS.contains(&ident.as_str()) {
+            format!("{ident}_")
+        } else {
+            ident
+        }
+    }
+}
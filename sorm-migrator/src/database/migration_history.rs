@@ -0,0 +1,224 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sorm_query::{Field, Raw, Table};
+use surrealdb::{
+    sql::{self, Thing},
+    Connection, Surreal,
+};
+
+use crate::*;
+
+/// Audit trail entry written every time a migration is applied or rolled back.
+/// Unlike `migration` records, which are deleted on rollback, history entries
+/// are never removed, so the table keeps the full record of every run.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MigrationHistory {
+    pub id: Thing,
+    pub name: String,
+    pub direction: Direction,
+    pub outcome: MigrationOutcome,
+    pub applied_by: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub duration_ms: i64,
+    pub sorm_version: String,
+    pub error: Option<String>,
+}
+
+pub struct MigrationHistorySchema {
+    pub id: Field,
+    pub name: Field,
+    pub direction: Field,
+    pub outcome: Field,
+    pub applied_by: Field,
+    pub started_at: Field,
+    pub finished_at: Field,
+    pub duration_ms: Field,
+    pub sorm_version: Field,
+    pub error: Field,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationOutcome {
+    Succeeded,
+    Failed,
+}
+
+impl Display for MigrationOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let outcome = match self {
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        };
+        write!(f, "{outcome}")
+    }
+}
+
+impl Display for MigrationHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {} by {} in {}ms (sorm {})",
+            self.started_at.to_rfc3339(),
+            self.direction,
+            self.name,
+            self.outcome,
+            self.applied_by,
+            self.duration_ms,
+            self.sorm_version
+        )?;
+
+        match &self.error {
+            Some(error) => write!(f, ": {error}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl MigrationHistory {
+    pub const SORM_VERSION: &'static str = env!("CARGO_PKG_VERSION");
+
+    // Set before each migration file of a transaction so its own duration can be recorded
+    const STARTED_AT_PARAM: &'static str = "$migration_started_at";
+
+    pub fn table() -> Table {
+        Table::new("migration_history")
+    }
+
+    pub fn schema() -> MigrationHistorySchema {
+        MigrationHistorySchema {
+            id: "id".into(),
+            name: Field::new("name"),
+            direction: Field::new("direction"),
+            outcome: Field::new("outcome"),
+            applied_by: Field::new("applied_by"),
+            started_at: Field::new("started_at"),
+            finished_at: Field::new("finished_at"),
+            duration_ms: Field::new("duration_ms"),
+            sorm_version: Field::new("sorm_version"),
+            error: Field::new("error"),
+        }
+    }
+
+    /// Wraps the content of a migration file so that running it also records
+    /// a succeeded history entry in the same transaction
+    pub fn wrap_raw(
+        filename: &MigrationFilename,
+        content: impl Display,
+        direction: Direction,
+        applied_by: &str,
+    ) -> Raw {
+        let MigrationHistorySchema {
+            name,
+            direction: direction_field,
+            outcome,
+            applied_by: applied_by_field,
+            started_at,
+            finished_at,
+            duration_ms,
+            sorm_version,
+            ..
+        } = Self::schema();
+        let table = Self::table();
+        let started_at_param = Self::STARTED_AT_PARAM;
+        let filename = Self::quote(filename.to_string());
+        let direction = Self::quote(direction.to_string());
+        let succeeded = Self::quote(MigrationOutcome::Succeeded.to_string());
+        let applied_by = Self::quote(applied_by);
+        let version = Self::quote(Self::SORM_VERSION);
+
+        Raw::new(format!(
+            "LET {started_at_param} = time::now();\n\
+            {content}\n\
+            CREATE {table} SET {name} = {filename}, {direction_field} = {direction}, \
+            {outcome} = {succeeded}, {applied_by_field} = {applied_by}, \
+            {started_at} = {started_at_param}, {finished_at} = time::now(), \
+            {duration_ms} = duration::millis(time::now() - {started_at_param}), \
+            {sorm_version} = {version};"
+        ))
+    }
+
+    /// Records history entries for migrations run outside of a migration transaction
+    /// e.g data migrations, or for migrations whose transaction failed.
+    pub(crate) async fn record(
+        db: Surreal<impl Connection>,
+        filenames: &[MigrationFilename],
+        direction: Direction,
+        applied_by: &str,
+        started_at: DateTime<Utc>,
+        error: Option<&MigrationError>,
+    ) -> MigrationResult<()> {
+        let MigrationHistorySchema {
+            name,
+            direction: direction_field,
+            outcome,
+            applied_by: applied_by_field,
+            started_at: started_at_field,
+            finished_at,
+            duration_ms,
+            sorm_version,
+            error: error_field,
+            ..
+        } = Self::schema();
+        let table = Self::table();
+        let finished = Utc::now();
+        let outcome_value = match error {
+            Some(_) => MigrationOutcome::Failed,
+            None => MigrationOutcome::Succeeded,
+        };
+
+        db.query(format!(
+            "FOR $name IN $names {{
+                CREATE {table} SET {name} = $name, {direction_field} = $direction, \
+                {outcome} = $outcome, {applied_by_field} = $applied_by, \
+                {started_at_field} = <datetime> $started_at, {finished_at} = <datetime> $finished_at, \
+                {duration_ms} = $duration_ms, {sorm_version} = $sorm_version, {error_field} = $error;
+            }};"
+        ))
+        .bind((
+            "names",
+            filenames.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        ))
+        .bind(("direction", direction.to_string()))
+        .bind(("outcome", outcome_value.to_string()))
+        .bind(("applied_by", applied_by.to_string()))
+        .bind(("started_at", started_at.to_rfc3339()))
+        .bind(("finished_at", finished.to_rfc3339()))
+        .bind((
+            "duration_ms",
+            (finished - started_at).num_milliseconds(),
+        ))
+        .bind(("sorm_version", Self::SORM_VERSION))
+        .bind(("error", error.map(ToString::to_string)))
+        .await?
+        .check()?;
+
+        Ok(())
+    }
+
+    /// All history entries, oldest first
+    pub async fn get_all_asc(db: Surreal<impl Connection>) -> MigrationResult<Vec<Self>> {
+        let MigrationHistorySchema { started_at, .. } = Self::schema();
+        let table = Self::table();
+
+        let history = db
+            .query(format!("SELECT * FROM {table} ORDER BY {started_at} ASC;"))
+            .await?
+            .take::<Vec<Self>>(0)?;
+
+        Ok(history)
+    }
+
+    fn quote(value: impl Into<String>) -> String {
+        sql::Value::from(value.into()).to_string()
+    }
+}
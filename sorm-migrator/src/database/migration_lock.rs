@@ -0,0 +1,325 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+use std::{future::Future, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sorm_query::{Field, Table};
+use surrealdb::{sql::Thing, Connection, Surreal};
+
+use crate::*;
+
+/// Advisory lock record held while migrations are being applied or rolled back,
+/// so that two deploys cannot run `up`, `down` or `reset` against the same database concurrently.
+/// There is at most one lock record - `migration_lock:lock`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MigrationLock {
+    pub id: Thing,
+    pub owner: String,
+    pub acquired_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub struct MigrationLockSchema {
+    pub id: Field,
+    pub owner: Field,
+    pub acquired_at: Field,
+    pub expires_at: Field,
+}
+
+/// Who is taking the lock and for how long. An expired lock is treated
+/// as abandoned by a crashed holder and can be taken over by another owner.
+#[derive(Debug, Clone)]
+pub struct LockOptions {
+    pub owner: String,
+    pub ttl: Duration,
+}
+
+impl Default for LockOptions {
+    fn default() -> Self {
+        Self {
+            owner: Self::default_owner(),
+            ttl: Self::DEFAULT_TTL,
+        }
+    }
+}
+
+impl LockOptions {
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(600);
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner = owner.into();
+        self
+    }
+
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// <hostname>:<process id>
+    pub fn default_owner() -> String {
+        let host = std::env::var("HOSTNAME")
+            .or_else(|_| std::env::var("COMPUTERNAME"))
+            .unwrap_or_else(|_| "unknown".into());
+        format!("{host}:{}", std::process::id())
+    }
+}
+
+impl MigrationLock {
+    pub fn table() -> Table {
+        Table::new("migration_lock")
+    }
+
+    pub fn lock_id() -> Thing {
+        Thing {
+            tb: Self::table().to_string(),
+            id: "lock".into(),
+        }
+    }
+
+    pub fn schema() -> MigrationLockSchema {
+        MigrationLockSchema {
+            id: "id".into(),
+            owner: Field::new("owner"),
+            acquired_at: Field::new("acquired_at"),
+            expires_at: Field::new("expires_at"),
+        }
+    }
+
+    /// Takes the lock, first clearing it if the previous holder let it expire.
+    /// The lock record is only created when there is none, in a single statement,
+    /// and creating a record with a fixed id fails if a concurrent deploy created it
+    /// in the meantime, which makes the acquisition atomic across concurrent deploys.
+    pub async fn acquire(
+        db: Surreal<impl Connection>,
+        options: &LockOptions,
+    ) -> MigrationResult<Self> {
+        let MigrationLockSchema {
+            owner,
+            acquired_at,
+            expires_at,
+            ..
+        } = Self::schema();
+        let lock_id = Self::lock_id();
+        let ttl_secs = options.ttl.as_secs();
+
+        let mut response = db
+            .query(format!(
+                "DELETE {lock_id} WHERE {expires_at} < time::now();\n\
+                IF (SELECT VALUE id FROM {lock_id}) = [] {{\n\
+                    RETURN CREATE ONLY {lock_id} SET {owner} = $owner, \
+                    {acquired_at} = time::now(), {expires_at} = time::now() + {ttl_secs}s\n\
+                }};"
+            ))
+            .bind(("owner", options.owner.clone()))
+            .await?;
+
+        match response.take::<Option<Self>>(1)? {
+            Some(lock) => {
+                log::info!("Migration lock acquired by {}", lock.owner);
+                Ok(lock)
+            }
+            None => match Self::get_current(db.clone()).await? {
+                Some(held) => Err(MigrationError::MigrationLocked {
+                    owner: held.owner,
+                    acquired_at: held.acquired_at.to_rfc3339(),
+                    expires_at: held.expires_at.to_rfc3339(),
+                }),
+                None => Err(MigrationError::MigrationLockNotAcquired(
+                    options.owner.clone(),
+                )),
+            },
+        }
+    }
+
+    /// Releases the lock if it is still held by the owner. The lock table is removed
+    /// altogether so that it does not linger in the database schema between runs.
+    pub async fn release(
+        db: Surreal<impl Connection>,
+        options: &LockOptions,
+    ) -> MigrationResult<()> {
+        let MigrationLockSchema { owner, .. } = Self::schema();
+        let lock_id = Self::lock_id();
+        let table = Self::table();
+
+        match Self::get_current(db.clone()).await? {
+            Some(held) if held.owner == options.owner => {
+                // Guard against the lock having expired and been taken over in the meantime
+                db.query(format!(
+                    "BEGIN TRANSACTION;
+                    IF (SELECT VALUE {owner} FROM ONLY {lock_id}) != $owner {{
+                        THROW \"Migration lock is no longer held by \" + $owner
+                    }};
+                    REMOVE TABLE {table};
+                    COMMIT TRANSACTION;"
+                ))
+                .bind(("owner", options.owner.clone()))
+                .await?
+                .check()?;
+
+                log::info!("Migration lock released by {}", options.owner);
+            }
+            Some(held) => {
+                log::warn!(
+                    "Migration lock is now held by {} and was not released by {}",
+                    held.owner,
+                    options.owner
+                );
+            }
+            None => {
+                log::warn!("Migration lock was already released");
+            }
+        };
+
+        Ok(())
+    }
+
+    /// Removes the lock regardless of who holds it. Meant for recovering
+    /// from a holder that crashed before releasing it.
+    pub async fn force_unlock(db: Surreal<impl Connection>) -> MigrationResult<Option<Self>> {
+        let table = Self::table();
+        let held = Self::get_current(db.clone()).await?;
+
+        if held.is_some() {
+            db.query(format!("REMOVE TABLE {table};")).await?.check()?;
+        }
+
+        Ok(held)
+    }
+
+    /// Pushes the expiry of the lock forward if it is still held by the owner,
+    /// so that a migration running for longer than the ttl does not lose the lock.
+    pub async fn heartbeat(
+        db: Surreal<impl Connection>,
+        options: &LockOptions,
+    ) -> MigrationResult<Option<Self>> {
+        let MigrationLockSchema {
+            owner, expires_at, ..
+        } = Self::schema();
+        let lock_id = Self::lock_id();
+        let ttl_secs = options.ttl.as_secs();
+
+        let lock = db
+            .query(format!(
+                "UPDATE {lock_id} SET {expires_at} = time::now() + {ttl_secs}s WHERE {owner} = $owner;"
+            ))
+            .bind(("owner", options.owner.clone()))
+            .await?
+            .take::<Option<Self>>(0)?;
+
+        Ok(lock)
+    }
+
+    /// How often the lock is extended while held, a third of the ttl but at least a second
+    fn heartbeat_interval(ttl: Duration) -> Duration {
+        (ttl / 3).max(Duration::from_secs(1))
+    }
+
+    pub async fn get_current(db: Surreal<impl Connection>) -> MigrationResult<Option<Self>> {
+        let lock_id = Self::lock_id();
+
+        let lock = db
+            .query(format!("SELECT * FROM {lock_id};"))
+            .await?
+            .take::<Option<Self>>(0)?;
+
+        Ok(lock)
+    }
+
+    /// Runs the future while holding the lock, extending it periodically until the future
+    /// completes. The lock is released whether or not the future succeeds, and also
+    /// when the future panics or is dropped before completing.
+    pub async fn run_locked<C, T, F>(
+        db: Surreal<C>,
+        options: &LockOptions,
+        f: F,
+    ) -> MigrationResult<T>
+    where
+        C: Connection,
+        F: Future<Output = MigrationResult<T>>,
+    {
+        Self::acquire(db.clone(), options).await?;
+        let guard = LockGuard {
+            db: db.clone(),
+            options: options.clone(),
+            held: true,
+        };
+
+        let heartbeat = async {
+            loop {
+                tokio::time::sleep(Self::heartbeat_interval(options.ttl)).await;
+                match Self::heartbeat(db.clone(), options).await {
+                    Ok(Some(_)) => {}
+                    Ok(None) => {
+                        log::warn!("Migration lock is no longer held by {}", options.owner)
+                    }
+                    Err(e) => log::warn!("Failed to extend migration lock: {e}"),
+                }
+            }
+        };
+        let result = tokio::select! {
+            result = f => result,
+            _ = heartbeat => unreachable!("The lock heartbeat never completes"),
+        };
+        let released = guard.release().await;
+
+        match (result, released) {
+            (Err(e), Err(release_err)) => {
+                log::error!("Failed to release migration lock: {release_err}");
+                Err(e)
+            }
+            (Err(e), Ok(())) => Err(e),
+            (Ok(_), Err(release_err)) => Err(release_err),
+            (Ok(value), Ok(())) => Ok(value),
+        }
+    }
+}
+
+/// Releases the lock taken by `run_locked` if it is dropped while still holding it,
+/// which happens when the future run under the lock panics or is cancelled.
+/// The release is spawned on the current runtime since it cannot be awaited on drop.
+struct LockGuard<C: Connection> {
+    db: Surreal<C>,
+    options: LockOptions,
+    held: bool,
+}
+
+impl<C: Connection> LockGuard<C> {
+    async fn release(mut self) -> MigrationResult<()> {
+        self.held = false;
+        MigrationLock::release(self.db.clone(), &self.options).await
+    }
+}
+
+impl<C: Connection> Drop for LockGuard<C> {
+    fn drop(&mut self) {
+        if !self.held {
+            return;
+        }
+
+        let db = self.db.clone();
+        let options = self.options.clone();
+        match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => {
+                runtime.spawn(async move {
+                    if let Err(e) = MigrationLock::release(db, &options).await {
+                        log::error!("Failed to release migration lock: {e}");
+                    }
+                });
+            }
+            Err(_) => log::error!(
+                "Migration lock held by {} could not be released and is kept until it expires",
+                options.owner
+            ),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::fmt::Display;
+
+use sorm_query::{statements::begin_transaction, Buildable, Raw, ToRaw};
+
+use crate::*;
+
+/// The resolved effect of an `up` or `down` run. It holds the migration files
+/// that would be applied or rolled back, their queries and the `migration` table
+/// bookkeeping queries, without having touched the database.
+/// Data migrations applied by an `up` run are listed in `files` but their Rust code
+/// cannot be printed, so they are not part of the queries. Each of them is run and
+/// registered on its own in between the file migrations around it.
+#[derive(Debug, Clone)]
+pub struct MigrationPlan {
+    pub direction: Direction,
+    pub files: Vec<MigrationFilename>,
+    pub data_migrations: Vec<MigrationFilename>,
+    pub migration_queries: String,
+    pub bookkeeping_queries: String,
+}
+
+impl MigrationPlan {
+    pub fn is_empty(&self) -> bool {
+        self.migration_queries.trim().is_empty()
+            && self.bookkeeping_queries.trim().is_empty()
+            && self.data_migrations.is_empty()
+    }
+
+    /// Migration queries joined with the bookkeeping queries
+    pub fn queries(&self) -> Raw {
+        Raw::new(format!(
+            "{}\n{}",
+            self.migration_queries, self.bookkeeping_queries
+        ))
+    }
+
+    /// The exact transaction that is run against the database
+    pub fn transaction(&self) -> Raw {
+        begin_transaction()
+            .query(self.queries())
+            .commit_transaction()
+            .to_raw()
+    }
+}
+
+impl Display for MigrationPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let action = match self.direction {
+            Direction::Up => "applied",
+            Direction::Down => "rolled back",
+        };
+
+        if self.is_empty() {
+            return write!(f, "-- No migrations would be {action}");
+        }
+
+        writeln!(f, "-- {} migration(s) would be {action}:", self.files.len())?;
+        for file in &self.files {
+            if self.data_migrations.contains(file) {
+                writeln!(f, "-- {file} (data migration, run separately)")?;
+            } else {
+                writeln!(f, "-- {file}")?;
+            }
+        }
+
+        if !self.data_migrations.is_empty() {
+            writeln!(
+                f,
+                "-- Data migrations are not part of the transaction below. Each of them is \
+                run and registered on its own, splitting the transaction where it is listed."
+            )?;
+        }
+
+        if self.migration_queries.trim().is_empty() && self.bookkeeping_queries.trim().is_empty() {
+            return Ok(());
+        }
+        write!(f, "\n{}", self.transaction().build())
+    }
+}
@@ -6,9 +6,11 @@
  */
 
 mod checksum;
+mod data_migration;
 mod db_left_mig_dir;
 mod db_right_codebase;
 mod db_runner;
+mod drift_report;
 mod embedded_migration;
 mod file_content;
 mod file_generator;
@@ -16,15 +18,22 @@ mod file_manager;
 mod file_metadata;
 mod file_name;
 mod full_db_info;
+mod introspection;
+mod migration_history;
+mod migration_lock;
 mod migration_model;
+mod migration_plan;
 mod prompter;
 mod queries;
 mod settings;
+mod squash;
 
 pub use checksum::*;
+pub use data_migration::*;
 pub use db_left_mig_dir::*;
 pub use db_right_codebase::*;
 pub use db_runner::*;
+pub use drift_report::*;
 pub use embedded_migration::*;
 pub use file_content::*;
 pub use file_generator::*;
@@ -32,7 +41,12 @@ pub use file_manager::*;
 pub use file_metadata::*;
 pub use file_name::*;
 pub use full_db_info::*;
+pub use introspection::*;
+pub use migration_history::*;
+pub use migration_lock::*;
 pub use migration_model::*;
+pub use migration_plan::*;
 pub use prompter::*;
 pub use queries::*;
 pub use settings::*;
+pub use squash::*;
@@ -36,6 +36,23 @@ where
         .prompt();
         confirmation
     }
+
+    /// Value for existing rows where a field that becomes required is NONE.
+    /// None leaves those rows as they are.
+    fn prompt_required_field_default(
+        &self,
+        change: &FieldTypeChange,
+    ) -> Result<Option<String>, InquireError> {
+        let default = inquire::Text::new(&format!(
+            "Changing {change} makes it required. What value should existing rows without it get?"
+        ))
+        .with_help_message(
+            "A SurrealQL value e.g 0, 'unknown' or time::now(). Leave empty to leave those rows as they are.",
+        )
+        .prompt()?;
+        let default = default.trim();
+        Ok((!default.is_empty()).then(|| default.to_string()))
+    }
 }
 
 // What is use in the actual codebase and typically just uses the default implementation
@@ -69,6 +86,12 @@ pub struct MockPrompter {
     // to rename - which would transfer the data to the new field,
     // or delete the old field completely without transferring the data.
     pub rename_or_delete_single_field_change: RenameOrDelete,
+
+    // triggered when a field type change makes an optional field required.
+    // The value is set on existing rows where the field is NONE.
+    // If None, those rows are left as they are.
+    #[builder(default, setter(strip_option))]
+    pub required_field_default: Option<&'static str>,
 }
 
 impl Default for MockPrompter {
@@ -76,6 +99,7 @@ impl Default for MockPrompter {
         Self {
             allow_empty_migrations_gen: true,
             rename_or_delete_single_field_change: RenameOrDelete::Rename,
+            required_field_default: None,
         }
     }
 }
@@ -95,4 +119,11 @@ impl Prompter for MockPrompter {
             RenameOrDelete::Delete => Ok(delete_option),
         }
     }
+
+    fn prompt_required_field_default(
+        &self,
+        _change: &FieldTypeChange,
+    ) -> Result<Option<String>, InquireError> {
+        Ok(self.required_field_default.map(ToString::to_string))
+    }
 }
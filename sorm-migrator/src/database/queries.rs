@@ -25,6 +25,7 @@ pub enum QueryType {
     Define(DefineStatementRaw),
     Remove(RemoveStatementRaw),
     Update(UpdateStatementRaw),
+    Insert(Raw),
     DeleteAll(Raw),
     NewLine,
     Comment(String),
@@ -36,6 +37,7 @@ impl Display for QueryType {
             QueryType::Define(def) => def.to_string(),
             QueryType::Remove(rem) => rem.to_string(),
             QueryType::Update(upd) => upd.to_string(),
+            QueryType::Insert(ins) => ins.build(),
             QueryType::DeleteAll(del) => del.build(),
             QueryType::NewLine => "\n".to_string(),
             QueryType::Comment(comment) => format!("-- {comment}"),
@@ -53,13 +55,19 @@ impl Display for QueryType {
 pub struct Queries {
     pub(crate) up: Vec<QueryType>,
     pub(crate) down: Vec<QueryType>,
+    // Classification of the table and field statements in up
+    pub(crate) changes: Vec<SchemaChange>,
 }
 
 impl Queries {
     pub(crate) fn intersperse_new_lines(self) -> Self {
         let up = intersperse(self.up, QueryType::NewLine).collect();
         let down = intersperse(self.down, QueryType::NewLine).collect();
-        Queries { up, down }
+        Queries {
+            up,
+            down,
+            changes: self.changes,
+        }
     }
 
     pub(crate) fn up_is_empty(&self) -> bool {
@@ -87,12 +95,22 @@ impl Queries {
         self.up.push(query);
     }
 
+    pub(crate) fn add_up_change(&mut self, query: QueryType, table: &Table, safety: ChangeSafety) {
+        self.changes.push(SchemaChange {
+            safety,
+            table: table.to_owned(),
+            statement: query.to_string(),
+        });
+        self.add_up(query);
+    }
+
     pub(crate) fn add_down(&mut self, query: QueryType) {
         self.down.push(query);
     }
 
     pub(crate) fn extend_up(&mut self, queries: &Self) {
         self.up.extend(queries.up.to_vec());
+        self.changes.extend(queries.changes.to_vec());
     }
 
     pub(crate) fn extend_down(&mut self, queries: &Self) {
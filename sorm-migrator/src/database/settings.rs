@@ -5,11 +5,13 @@
  * Licensed under the MIT license
  */
 use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
 use std::{fmt::Display, str::FromStr};
 
 use crate::MigrationError;
 
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
 pub enum Direction {
     Up,
     Down,
@@ -131,3 +133,27 @@ impl Mode {
         }
     }
 }
+
+/// What `generate` does when the new migration removes or narrows existing data
+#[derive(ValueEnum, Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DestructiveChangePolicy {
+    /// Generate the migration and list the data-losing operations
+    #[default]
+    Warn,
+    /// Refuse to generate a migration with data-losing operations
+    Fail,
+    /// Refuse unless the models of the affected tables are marked `#[sorm(allow_destructive)]`
+    /// or the tables are acknowledged with `--allow-destructive`
+    RequireAck,
+}
+
+impl Display for DestructiveChangePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let policy = match self {
+            Self::Warn => "warn",
+            Self::Fail => "fail",
+            Self::RequireAck => "require-ack",
+        };
+        write!(f, "{policy}")
+    }
+}
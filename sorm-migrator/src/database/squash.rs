@@ -0,0 +1,293 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use sorm_query::{statements::begin_transaction, DbResources, Raw, Runnable};
+use surrealdb::{engine::any::Any, Surreal};
+
+use crate::*;
+
+/// Inclusive range of migrations to squash. Unset ends default to
+/// the first and the latest migration in the migration directory.
+#[derive(Debug, Clone, Default)]
+pub struct SquashRange {
+    pub from: Option<MigrationFilename>,
+    pub till: Option<MigrationFilename>,
+}
+
+impl SquashRange {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from(mut self, from: MigrationFilename) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    pub fn till(mut self, till: MigrationFilename) -> Self {
+        self.till = Some(till);
+        self
+    }
+}
+
+/// The baseline migration that replaces a range of migrations.
+/// It keeps the timestamp of the last migration in the range so that
+/// migrations after the range still sort after it.
+#[derive(Debug, Clone)]
+pub struct SquashedMigration {
+    pub migration: MigrationFile,
+    pub from: Timestamp,
+    pub till: Timestamp,
+    /// Files removed from the migration directory. Empty when the
+    /// directory had already been squashed.
+    pub replaced: Vec<MigrationFilename>,
+}
+
+// Squashing only diffs database states, so there is no codebase to
+// look up field renames in.
+struct NoCodebaseResources;
+
+impl DbResources for NoCodebaseResources {}
+
+impl SquashedMigration {
+    /// Builds the baseline migration holding the schema difference between the
+    /// state before the range and the state at its end. Nothing is written until
+    /// `write` is called. On an already squashed directory, the existing baseline
+    /// is returned with nothing to replace.
+    pub async fn generate(
+        file_manager: &MigrationConfig,
+        migration_basename: &Basename,
+        range: &SquashRange,
+        prompter: impl Prompter,
+    ) -> MigrationResult<Self> {
+        let migration_basename = migration_basename.normalize_ensure();
+        let migration_flag = file_manager.detect_migration_type()?;
+        let migrations: Vec<MigrationFile> = match migration_flag {
+            MigrationFlag::TwoWay => file_manager
+                .get_two_way_migrations_sorted_asc(false)?
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            MigrationFlag::OneWay => file_manager
+                .get_oneway_migrations_sorted_asc(false)?
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        };
+
+        let from = range
+            .from
+            .as_ref()
+            .map(MigrationFilename::timestamp)
+            .or_else(|| migrations.first().map(|m| m.name_forward().timestamp()))
+            .unwrap_or(0.into());
+        let till = range
+            .till
+            .as_ref()
+            .map(MigrationFilename::timestamp)
+            .or_else(|| migrations.last().map(|m| m.name_forward().timestamp()))
+            .unwrap_or(0.into());
+
+        let in_range = |m: &&MigrationFile| {
+            let timestamp = m.name_forward().timestamp();
+            timestamp >= from && timestamp <= till
+        };
+        let squashed = migrations.iter().filter(in_range).collect::<Vec<_>>();
+
+        let nothing_to_squash = || MigrationError::NothingToSquash {
+            from: from.to_string(),
+            till: till.to_string(),
+        };
+        let last = squashed.last().ok_or_else(nothing_to_squash)?;
+
+        if let [only] = squashed.as_slice() {
+            if only.name_forward().basename().to_string() == migration_basename.to_string() {
+                log::info!(
+                    "Migrations from {from} to {till} are already squashed into {}",
+                    only.name_forward()
+                );
+                return Ok(Self {
+                    migration: (*only).clone(),
+                    from,
+                    till: last.name_forward().timestamp(),
+                    replaced: vec![],
+                });
+            }
+            return Err(nothing_to_squash());
+        }
+
+        let before_range = migrations
+            .iter()
+            .filter(|m| m.name_forward().timestamp() < from)
+            .map(Self::as_oneway)
+            .collect::<Vec<_>>();
+        let through_range = migrations
+            .iter()
+            .filter(|m| m.name_forward().timestamp() <= till)
+            .map(Self::as_oneway)
+            .collect::<Vec<_>>();
+
+        // Left = state before the range
+        // Right = state at the end of the range
+        let ComparisonDatabase { left, right } = ComparisonDatabase::init().await;
+        LeftDatabase::run_local_dir_oneway_content_migrations(left.db(), before_range.clone())
+            .await?;
+        LeftDatabase::run_local_dir_oneway_content_migrations(right.db(), through_range).await?;
+
+        let init = ComparisonsInit {
+            left_resources: &left.resources().await,
+            right_resources: &right.resources().await,
+            prompter: &prompter,
+        };
+
+        let migration_reset = if before_range.is_empty() {
+            MigratorDatabase::migration_reset_queries()
+        } else {
+            Queries::default()
+        };
+        let (up_queries_str, down_queries_str, _) =
+            MigratorDatabase::diff_queries(&init, &NoCodebaseResources, migration_reset)?;
+
+        let migration = match last {
+            MigrationFile::TwoWay(m) => MigrationFileTwoWayPair::new(
+                FileMetadata::new(
+                    m.up.name.with_basename(&migration_basename),
+                    up_queries_str.into(),
+                ),
+                FileMetadata::new(
+                    m.down.name.with_basename(&migration_basename),
+                    down_queries_str.into(),
+                ),
+            )
+            .into(),
+            MigrationFile::OneWay(m) => MigrationFileOneWay::new(FileMetadata::new(
+                m.name().with_basename(&migration_basename),
+                up_queries_str.into(),
+            ))
+            .into(),
+        };
+
+        let new_filenames = Self::filenames(&migration);
+        let replaced = squashed
+            .iter()
+            .flat_map(|m| Self::filenames(m))
+            .filter(|f| !new_filenames.contains(f))
+            .collect::<Vec<_>>();
+
+        Ok(Self {
+            migration,
+            from,
+            till: last.name_forward().timestamp(),
+            replaced,
+        })
+    }
+
+    /// Whether the database has applied the whole squashed range. Fails if it has
+    /// only applied part of it, since it can then neither keep its registered
+    /// migrations nor switch to the baseline.
+    pub async fn is_range_applied(&self, db: Surreal<Any>) -> MigrationResult<bool> {
+        let Some(latest) = Migration::get_latest(db.clone()).await else {
+            return Ok(false);
+        };
+
+        if latest.timestamp < self.from {
+            return Ok(false);
+        }
+
+        if latest.timestamp < self.till {
+            return Err(MigrationError::SquashRangePartiallyApplied {
+                from: self.from.to_string(),
+                till: self.till.to_string(),
+                latest: latest.name,
+            });
+        }
+
+        Ok(true)
+    }
+
+    /// Replaces the registered migrations of the squashed range with the baseline
+    /// on a database that has applied the whole range. A database that has not
+    /// reached the range is left as is, and applies the baseline on its next `up`.
+    /// Returns whether the records were rewritten.
+    pub async fn sync_applied_migrations(&self, db: Surreal<Any>) -> MigrationResult<bool> {
+        if !self.is_range_applied(db.clone()).await? {
+            return Ok(false);
+        }
+
+        let migration::Schema { timestamp, .. } = Migration::schema();
+        let delete_range = Raw::new(format!(
+            "DELETE {} WHERE {timestamp} >= {} AND {timestamp} <= {};",
+            Migration::table(),
+            self.from,
+            self.till
+        ));
+        let register_squashed = Migration::create_raw(
+            self.migration.name_forward(),
+            &self.migration.up_content().as_checksum()?,
+            self.migration
+                .down_content()
+                .map(FileContent::as_checksum)
+                .transpose()?
+                .as_ref(),
+        );
+
+        begin_transaction()
+            .query(delete_range)
+            .query(register_squashed)
+            .commit_transaction()
+            .run(db)
+            .await?;
+
+        Ok(true)
+    }
+
+    /// Writes the baseline migration and deletes the files it replaces
+    pub fn write(&self, file_manager: &MigrationConfig) -> MigrationResult<()> {
+        if self.replaced.is_empty() {
+            return Ok(());
+        }
+
+        self.migration.create_file(file_manager)?;
+
+        let dir = file_manager.get_migration_dir()?;
+        for filename in &self.replaced {
+            let path = filename.fullpath(&dir);
+            let path_str = path.to_string_lossy();
+            std::fs::remove_file(&path).map_err(|e| {
+                MigrationError::IoError(format!(
+                    "Failed to delete migration file: {}. Error: {}",
+                    &path_str, e
+                ))
+            })?;
+            log::info!("Deleted file: {}", &path_str);
+        }
+
+        log::warn!(
+            "Squashed migrations from {} to {} into {}. Only schema definitions are kept; \
+            data statements in the squashed migrations were dropped.",
+            self.from,
+            self.till,
+            self.migration.name_forward()
+        );
+
+        Ok(())
+    }
+
+    fn as_oneway(migration: &MigrationFile) -> MigrationFileOneWay {
+        match migration {
+            MigrationFile::OneWay(m) => m.clone(),
+            MigrationFile::TwoWay(m) => m.clone().into(),
+        }
+    }
+
+    fn filenames(migration: &MigrationFile) -> Vec<MigrationFilename> {
+        match migration {
+            MigrationFile::OneWay(m) => vec![m.name().clone()],
+            MigrationFile::TwoWay(m) => vec![m.up.name.clone(), m.down.name.clone()],
+        }
+    }
+}
@@ -8,7 +8,7 @@
 use sorm_query::{Field, SurrealOrmError, Table};
 use thiserror::Error;
 
-use crate::MigrationFilename;
+use crate::{DestructiveChangePolicy, MigrationFilename};
 
 #[derive(Error, Debug)]
 pub enum MigrationError {
@@ -61,6 +61,9 @@ pub enum MigrationError {
     #[error("Invalid migration name. {0}. Make sure it's in the format - <timestamp>_<migration_name>.<up|down|>.surql if two way or <timestamp>_<migration_name.surql if one way")]
     InvalidMigrationName(String),
 
+    #[error("Invalid data migration name. {0}. Make sure it's in the format - <timestamp>_<migration_name>.rs")]
+    InvalidDataMigrationName(String),
+
     #[error("Migration path not found")]
     MigrationPathNotFound,
 
@@ -148,6 +151,20 @@ pub enum MigrationError {
     )]
     FieldNameReused { field: Field, table: Table },
 
+    #[error("The table - {new_name} - has an invalid old name - '{old_name}'. \
+        It must have already been renamed previously or never existed before or wrongly spelt. \
+         Also, make sure you are using the correct case for the table name. It should be one of these: {renamables}", )]
+    InvalidOldTableName {
+        new_name: Table,
+        old_name: Table,
+        renamables: String,
+    },
+
+    #[error("You are trying to rename the table-'{new_name}' from a table-'{old_name}' \
+        currently still in use in the codebase. \
+        This is likely not intentional. Use a different name for the new table or remove the model of the old one")]
+    CannotRenameFromOldTableInUse { new_name: Table, old_name: Table },
+
     #[error("Invalid DefineStatement: {0}")]
     InvalidDefineStatement(String),
 
@@ -180,6 +197,43 @@ There are {one_way_filecount} up only files and {two_way_filecount} up and down
         using the command 'cargo run -- up' to apply it/them or delete all unapplied migrations using 'cargo run -- prune'.")]
     UnappliedMigrationExists { migration_count: usize },
 
+    #[error(
+        "Migrations are locked by {owner} since {acquired_at}. The lock expires at {expires_at}. \
+        Another deploy may currently be running migrations. If the lock holder crashed, \
+        run 'cargo run -- force-unlock' to remove the lock."
+    )]
+    MigrationLocked {
+        owner: String,
+        acquired_at: String,
+        expires_at: String,
+    },
+
+    #[error("No migrations to squash in the range {from} to {till}.")]
+    NothingToSquash { from: String, till: String },
+
+    #[error(
+        "Cannot squash migrations {from} to {till}. The database has only applied them up to {latest}. \
+        Apply the remaining migrations with 'cargo run -- up' before squashing."
+    )]
+    SquashRangePartiallyApplied {
+        from: String,
+        till: String,
+        latest: String,
+    },
+
+    #[error(
+        "The {policy} destructive change policy rejected the new migration. Data-losing operations:\n{changes}\n\
+        Mark the models of these tables with #[sorm(allow_destructive)] or pass '--allow-destructive <table>' \
+        under the require-ack policy, or generate with '--policy warn'."
+    )]
+    DestructiveChangesRejected {
+        policy: DestructiveChangePolicy,
+        changes: String,
+    },
+
+    #[error("Failed to acquire migration lock for {0}")]
+    MigrationLockNotAcquired(String),
+
     #[error("Invalid migration flag detection: {0}")]
     MigrationFlagDetectionError(String),
 
@@ -200,6 +254,9 @@ There are {one_way_filecount} up only files and {two_way_filecount} up and down
 
     #[error(transparent)]
     DbError(#[from] surrealdb::Error),
+
+    #[error("Failed to generate code: {0}")]
+    CodeGeneration(#[from] std::fmt::Error),
 }
 
 pub type MigrationResult<T> = Result<T, MigrationError>;
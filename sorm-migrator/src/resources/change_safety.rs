@@ -0,0 +1,186 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::fmt::Display;
+
+use sorm_query::{
+    sql::{self, statements::DefineStatement, Kind, Statement},
+    Field, Table,
+};
+
+use crate::*;
+
+/// How a statement in the up migration affects existing data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeSafety {
+    /// Keeps existing data e.g a new table or field, or a widened field type
+    Additive,
+    /// Changes a field type in a way that existing values may not fit
+    TypeNarrowing,
+    /// Drops existing data e.g a removed table or field
+    Destructive,
+}
+
+impl Display for ChangeSafety {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let safety = match self {
+            Self::Additive => "additive",
+            Self::TypeNarrowing => "type-narrowing",
+            Self::Destructive => "destructive",
+        };
+        write!(f, "{safety}")
+    }
+}
+
+impl ChangeSafety {
+    pub fn is_data_losing(&self) -> bool {
+        !matches!(self, Self::Additive)
+    }
+
+    /// Classifies the redefinition of an existing field
+    pub(crate) fn of_field_update(left: &DefineStatementRaw, right: &DefineStatementRaw) -> Self {
+        match (
+            Self::field_name_and_kind(left),
+            Self::field_name_and_kind(right),
+        ) {
+            (Some((_, left)), Some((_, right))) if Self::is_widening(&left, &right) => {
+                Self::Additive
+            }
+            _ => Self::TypeNarrowing,
+        }
+    }
+
+    /// Classifies the redefinition of an existing table. Making a schemaless table
+    /// schemafull drops the values of the fields that are not defined.
+    pub(crate) fn of_table_update(left: &DefineStatementRaw, right: &DefineStatementRaw) -> Self {
+        let is_schemafull = |definition: &DefineStatementRaw| {
+            let query = sql::parse(&definition.to_string()).ok();
+            match query.as_ref().and_then(|query| query.first()) {
+                Some(Statement::Define(DefineStatement::Table(table))) => table.full,
+                _ => false,
+            }
+        };
+
+        if !is_schemafull(left) && is_schemafull(right) {
+            Self::Destructive
+        } else {
+            Self::Additive
+        }
+    }
+
+    // A field without a type accepts any value
+    pub(crate) fn field_name_and_kind(definition: &DefineStatementRaw) -> Option<(Field, Kind)> {
+        let query = sql::parse(&definition.to_string()).ok()?;
+        match query.first()? {
+            Statement::Define(DefineStatement::Field(field)) => Some((
+                field.name.to_string().into(),
+                field.kind.clone().unwrap_or(Kind::Any),
+            )),
+            _ => None,
+        }
+    }
+
+    // Whether every value of the left kind is also a value of the right kind
+    pub(crate) fn is_widening(left: &Kind, right: &Kind) -> bool {
+        let contains_all = |left: &[String], right: &[String]| {
+            right.is_empty() || (!left.is_empty() && left.iter().all(|l| right.contains(l)))
+        };
+        let max_len_widens = |left: &Option<u64>, right: &Option<u64>| match (left, right) {
+            (_, None) => true,
+            (Some(left), Some(right)) => left <= right,
+            (None, Some(_)) => false,
+        };
+
+        match (left, right) {
+            (_, Kind::Any) => true,
+            (left, right) if left == right => true,
+            (Kind::Option(left), Kind::Option(right)) => Self::is_widening(left, right),
+            (left, Kind::Option(right)) => Self::is_widening(left, right),
+            (Kind::Either(left), right) => left.iter().all(|l| Self::is_widening(l, right)),
+            (left, Kind::Either(right)) => right.iter().any(|r| Self::is_widening(left, r)),
+            (Kind::Int | Kind::Float | Kind::Decimal, Kind::Number) => true,
+            (Kind::Record(left), Kind::Record(right)) => contains_all(
+                &left.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                &right.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ),
+            (Kind::Geometry(left), Kind::Geometry(right)) => contains_all(left, right),
+            (Kind::Array(left, left_max), Kind::Array(right, right_max))
+            | (Kind::Set(left, left_max), Kind::Set(right, right_max)) => {
+                Self::is_widening(left, right) && max_len_widens(left_max, right_max)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A classified statement of the up migration
+#[derive(Debug, Clone)]
+pub struct SchemaChange {
+    pub safety: ChangeSafety,
+    pub table: Table,
+    pub statement: String,
+}
+
+impl Display for SchemaChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.safety, self.statement)
+    }
+}
+
+impl DestructiveChangePolicy {
+    /// Fails when the policy rejects a data-losing change. Under `RequireAck`,
+    /// changes on tables whose models are marked `#[sorm(allow_destructive)]` pass.
+    pub(crate) fn enforce(
+        &self,
+        changes: &[SchemaChange],
+        acknowledged_tables: &[Table],
+    ) -> MigrationResult<()> {
+        let rejected = changes
+            .iter()
+            .filter(|change| change.safety.is_data_losing())
+            .filter(|change| match self {
+                Self::Warn => false,
+                Self::Fail => true,
+                Self::RequireAck => !acknowledged_tables
+                    .iter()
+                    .any(|table| table.to_string() == change.table.to_string()),
+            })
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+
+        if rejected.is_empty() {
+            return Ok(());
+        }
+
+        Err(MigrationError::DestructiveChangesRejected {
+            policy: *self,
+            changes: rejected.join("\n"),
+        })
+    }
+}
+
+/// Logs every data-losing statement of a generated migration
+pub(crate) fn log_data_losing_changes(changes: &[SchemaChange]) {
+    let data_losing = changes
+        .iter()
+        .filter(|change| change.safety.is_data_losing())
+        .collect::<Vec<_>>();
+
+    if data_losing.is_empty() {
+        return;
+    }
+
+    let summary = data_losing
+        .iter()
+        .map(|change| format!("  {change}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    log::warn!(
+        "The new migration has {} data-losing operation(s):\n{summary}",
+        data_losing.len()
+    );
+}
@@ -0,0 +1,123 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::fmt::Display;
+
+use sorm_query::{
+    cast::{self, Cast},
+    sql::Kind,
+    Buildable, Field, Table,
+};
+
+use crate::*;
+
+/// A field whose type differs between the migration directory and the codebase
+#[derive(Debug, Clone)]
+pub struct FieldTypeChange {
+    pub table: Table,
+    pub field: Field,
+    pub old_type: Kind,
+    pub new_type: Kind,
+}
+
+impl Display for FieldTypeChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "field '{}' on table '{}' from {} to {}",
+            self.field, self.table, self.old_type, self.new_type
+        )
+    }
+}
+
+impl FieldTypeChange {
+    pub(crate) fn detect(
+        table: &Table,
+        left: &DefineStatementRaw,
+        right: &DefineStatementRaw,
+    ) -> Option<Self> {
+        let (field, old_type) = ChangeSafety::field_name_and_kind(left)?;
+        let (_, new_type) = ChangeSafety::field_name_and_kind(right)?;
+
+        (old_type != new_type).then(|| Self {
+            table: table.to_owned(),
+            field,
+            old_type,
+            new_type,
+        })
+    }
+
+    /// Whether rows where the field is NONE become invalid
+    pub fn makes_required(&self) -> bool {
+        matches!(self.old_type, Kind::Option(_) | Kind::Any)
+            && !matches!(self.new_type, Kind::Option(_) | Kind::Any)
+    }
+
+    pub(crate) fn reverse(&self) -> Self {
+        Self {
+            table: self.table.clone(),
+            field: self.field.clone(),
+            old_type: self.new_type.clone(),
+            new_type: self.old_type.clone(),
+        }
+    }
+
+    /// Updates that convert existing values to the new type, run after the field
+    /// is redefined. Rows where the field is NONE get the default, if any.
+    pub(crate) fn conversion_queries(&self, default: Option<&str>) -> Vec<UpdateStatementRaw> {
+        let Self {
+            table,
+            field,
+            old_type,
+            new_type,
+        } = self;
+        let mut queries = vec![];
+
+        if let Some(default) = default.filter(|_| self.makes_required()) {
+            queries.push(
+                format!("UPDATE {table} SET {field} = {default} WHERE {field} IS NONE").into(),
+            );
+        }
+
+        // NONE values are handled above so only the inner types need to fit
+        let new_inner = Self::non_optional(new_type);
+        let fits = ChangeSafety::is_widening(Self::non_optional(old_type), new_inner);
+        if let (Some(cast), false) = (Self::cast_function(new_inner), fits) {
+            let casted = cast(field.clone()).build();
+            let query = if matches!(old_type, Kind::Option(_) | Kind::Any) {
+                format!("UPDATE {table} SET {field} = {casted} WHERE {field} IS NOT NONE")
+            } else {
+                format!("UPDATE {table} SET {field} = {casted}")
+            };
+            queries.push(query.into());
+        }
+
+        queries
+    }
+
+    fn non_optional(kind: &Kind) -> &Kind {
+        match kind {
+            Kind::Option(kind) => kind,
+            kind => kind,
+        }
+    }
+
+    fn cast_function(kind: &Kind) -> Option<fn(Field) -> Cast> {
+        let cast: fn(Field) -> Cast = match kind {
+            Kind::Bool => |field| cast::bool(field),
+            Kind::Datetime => |field| cast::datetime(field),
+            Kind::Decimal => |field| cast::decimal(field),
+            Kind::Duration => |field| cast::duration(field),
+            Kind::Float => |field| cast::float(field),
+            Kind::Int => |field| cast::int(field),
+            Kind::Number => |field| cast::number(field),
+            Kind::String => |field| cast::string(field),
+            _ => return None,
+        };
+        Some(cast)
+    }
+}
@@ -6,14 +6,20 @@
  */
 
 pub mod change_detection;
+pub mod change_safety;
 pub mod comparison_init;
+pub mod field_type_change;
 pub mod meta;
 pub mod table_events;
 pub mod table_fields;
 pub mod table_indexes;
+pub mod table_rename;
 pub mod tables;
 
 pub use change_detection::*;
+pub use change_safety::*;
 pub use comparison_init::*;
+pub use field_type_change::*;
 pub use meta::*;
+pub use table_rename::*;
 pub use tables::*;
@@ -84,7 +84,7 @@ impl<'a, R: DbResources> TableResourcesMeta<Fields> for ComparisonFields<'a, R>
                     self.handle_create(&mut acc, &right)?;
                 }
                 DeltaTypeField::Update { left, right } => {
-                    self.handle_update(&mut acc, &left, &right);
+                    self.handle_update(&mut acc, &left, &right)?;
                 }
                 DeltaTypeField::Remove { left } => {
                     self.handle_remove(&mut acc, &left)?;
@@ -140,13 +140,26 @@ impl<'a, R: DbResources> ComparisonFields<'a, R> {
         })?;
 
         // Field name change defs
+        // The old field is dropped once copied, so the rename loses data if the copy does not fit
         acc.add_comment_to_up(format!("Rename field {old_name} to {new_name}"));
-        acc.add_up(QueryType::Define(new_field_def.clone()));
+        acc.add_up_change(
+            QueryType::Define(new_field_def.clone()),
+            self.table,
+            ChangeSafety::Additive,
+        );
         let copy_old_to_new = UpdateStatementRaw::from(
             Raw::new(format!("UPDATE {table} SET {new_name} = {old_name}")).build(),
         );
-        acc.add_up(QueryType::Update(copy_old_to_new));
-        acc.add_up(QueryType::Remove(old_field_def.as_remove_statement()?));
+        acc.add_up_change(
+            QueryType::Update(copy_old_to_new),
+            self.table,
+            ChangeSafety::Destructive,
+        );
+        acc.add_up_change(
+            QueryType::Remove(old_field_def.as_remove_statement()?),
+            self.table,
+            ChangeSafety::Destructive,
+        );
         acc.add_comment_to_up("Rename field ending");
 
         // Field name change reversal defs
@@ -168,7 +181,11 @@ impl<'a, R: DbResources> ComparisonFields<'a, R> {
         acc: &'a mut Queries,
         right: &DefineStatementRaw,
     ) -> MigrationResult<&'a mut Queries> {
-        acc.add_up(QueryType::Define(right.clone()));
+        acc.add_up_change(
+            QueryType::Define(right.clone()),
+            self.table,
+            ChangeSafety::Additive,
+        );
         acc.add_down(QueryType::Remove(right.as_remove_statement()?));
         Ok(acc)
     }
@@ -178,7 +195,11 @@ impl<'a, R: DbResources> ComparisonFields<'a, R> {
         acc: &'a mut Queries,
         left: &DefineStatementRaw,
     ) -> MigrationResult<&'a mut Queries> {
-        acc.add_up(QueryType::Remove(left.as_remove_statement()?));
+        acc.add_up_change(
+            QueryType::Remove(left.as_remove_statement()?),
+            self.table,
+            ChangeSafety::Destructive,
+        );
         acc.add_down(QueryType::Define(left.clone()));
         Ok(acc)
     }
@@ -188,10 +209,31 @@ impl<'a, R: DbResources> ComparisonFields<'a, R> {
         acc: &'a mut Queries,
         left: &DefineStatementRaw,
         right: &DefineStatementRaw,
-    ) -> &'a mut Queries {
-        acc.add_up(QueryType::Define(right.clone()));
+    ) -> MigrationResult<&'a mut Queries> {
+        acc.add_up_change(
+            QueryType::Define(right.clone()),
+            self.table,
+            ChangeSafety::of_field_update(left, right),
+        );
         acc.add_down(QueryType::Define(left.clone()));
-        acc
+
+        // Existing values are converted so rows stay valid under the new type
+        if let Some(change) = FieldTypeChange::detect(self.table, left, right) {
+            let default = if change.makes_required() {
+                self.prompter.prompt_required_field_default(&change)?
+            } else {
+                None
+            };
+
+            for query in change.conversion_queries(default.as_deref()) {
+                acc.add_up(QueryType::Update(query));
+            }
+            for query in change.reverse().conversion_queries(None) {
+                acc.add_down(QueryType::Update(query));
+            }
+        }
+
+        Ok(acc)
     }
 
     fn handle_prompt_field_renaming_or_deletion(
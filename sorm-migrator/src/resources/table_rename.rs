@@ -0,0 +1,216 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use sorm_query::{sql::Kind, DbResources, Raw, Table};
+
+use crate::*;
+
+/// A table renamed with `#[sorm(old_name = "old_table")]` on its model
+#[derive(Debug, Clone)]
+pub struct TableRename {
+    pub old_name: Table,
+    pub new_name: Table,
+}
+
+impl Display for TableRename {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "table '{}' to '{}'", self.old_name, self.new_name)
+    }
+}
+
+impl TableRename {
+    fn reverse(&self) -> Self {
+        Self {
+            old_name: self.new_name.clone(),
+            new_name: self.old_name.clone(),
+        }
+    }
+}
+
+// Record links rewritten per field. Field name and the expression of its new value
+type LinkRewrites = BTreeMap<Table, Vec<(String, String)>>;
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TableRenames(Vec<TableRename>);
+
+impl TableRenames {
+    /// Renames declared in the codebase. Left is from migration dir, right is from codebase
+    pub(crate) fn detect(
+        codebase_resources: &impl DbResources,
+        left: &Tables,
+        right: &Tables,
+    ) -> MigrationResult<Self> {
+        let left_names = left.get_names_as_set();
+        let right_names = right.get_names_as_set();
+
+        let mut renames = codebase_resources
+            .tables_old_names()
+            .into_iter()
+            .map(|(new_name, old_name)| {
+                if right_names.contains(&old_name.to_string()) {
+                    return Err(MigrationError::CannotRenameFromOldTableInUse {
+                        new_name,
+                        old_name,
+                    });
+                }
+                if !left_names.contains(&old_name.to_string()) {
+                    return Err(MigrationError::InvalidOldTableName {
+                        new_name,
+                        old_name,
+                        renamables: left.get_names().join(", "),
+                    });
+                }
+                Ok(TableRename { old_name, new_name })
+            })
+            .collect::<MigrationResult<Vec<_>>>()?;
+        renames.sort_by_key(|rename| rename.new_name.to_string());
+
+        Ok(Self(renames))
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &TableRename> {
+        self.0.iter()
+    }
+
+    /// Whether the table is the old or new name of a rename
+    pub(crate) fn involves(&self, table_name: &str) -> bool {
+        self.0.iter().any(|rename| {
+            rename.old_name.to_string() == table_name || rename.new_name.to_string() == table_name
+        })
+    }
+
+    pub(crate) fn reverse(&self) -> Self {
+        Self(self.0.iter().map(TableRename::reverse).collect())
+    }
+
+    /// Copies the data of the renamed tables, then points the record links of
+    /// every table at the new names. `from` is the schema before the renames and
+    /// `to` the schema after them. Must run before the old tables are removed.
+    pub(crate) fn data_queries(
+        &self,
+        from: &FullDbInfo,
+        to: &FullDbInfo,
+    ) -> Vec<(Table, QueryType)> {
+        let mut rewrites = self.link_rewrites(from, to);
+        let mut queries = vec![];
+
+        for rename in &self.0 {
+            let TableRename { old_name, new_name } = rename;
+            let rewritten = rewrites
+                .remove(old_name)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(field, value)| format!(", {value} AS {field}"))
+                .collect::<String>();
+            queries.push((
+                new_name.clone(),
+                QueryType::Insert(Raw::new(format!(
+                    "INSERT INTO {new_name} (SELECT *{rewritten} FROM {old_name})"
+                ))),
+            ));
+        }
+
+        for (table, fields) in rewrites {
+            let assignments = fields
+                .into_iter()
+                // Relations cannot be moved to other records
+                .filter(|(field, _)| field != "in" && field != "out")
+                .map(|(field, value)| format!("{field} = {value}"))
+                .collect::<Vec<_>>();
+            if !assignments.is_empty() {
+                let query = QueryType::Update(UpdateStatementRaw::from(format!(
+                    "UPDATE {table} SET {}",
+                    assignments.join(", ")
+                )));
+                queries.push((table, query));
+            }
+        }
+
+        queries
+    }
+
+    // Fields of the `from` schema that link to a renamed table and still exist in the `to` schema.
+    // All links of a table are rewritten in one statement since every statement validates the whole record.
+    fn link_rewrites(&self, from: &FullDbInfo, to: &FullDbInfo) -> LinkRewrites {
+        let mut rewrites = LinkRewrites::new();
+
+        for table_name in from.tables().get_names() {
+            let table = Table::from(table_name);
+            let current_table = self
+                .0
+                .iter()
+                .find(|rename| rename.old_name == table)
+                .map_or_else(|| table.clone(), |rename| rename.new_name.clone());
+            let current_fields = to.get_table_fields(&current_table).unwrap_or_default();
+
+            let table_rewrites = from
+                .get_table_fields(&table)
+                .unwrap_or_default()
+                .get_all_definitions()
+                .iter()
+                .filter_map(ChangeSafety::field_name_and_kind)
+                .map(|(field, kind)| (field.to_string(), kind))
+                // Nested fields are rewritten through their top level field
+                .filter(|(field, _)| !field.contains(['.', '[']))
+                .filter(|(field, _)| current_fields.get_definition(field).is_some())
+                .filter_map(|(field, kind)| {
+                    let value = self.rewrite_field(&field, &kind)?;
+                    Some((field, value))
+                })
+                .collect::<Vec<_>>();
+
+            if !table_rewrites.is_empty() {
+                rewrites.insert(table, table_rewrites);
+            }
+        }
+
+        rewrites
+    }
+
+    fn rewrite_field(&self, field: &str, kind: &Kind) -> Option<String> {
+        match kind {
+            Kind::Option(kind) => self.rewrite_field(field, kind),
+            Kind::Record(tables) => self.rewrite_link(field, tables),
+            Kind::Array(kind, _) | Kind::Set(kind, _) => match kind.as_ref() {
+                Kind::Record(tables) => {
+                    let link = self.rewrite_link("id", tables)?;
+                    Some(format!(
+                        "IF type::is::array({field}) THEN (SELECT VALUE {link} FROM $parent.{field}) ELSE {field} END"
+                    ))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn rewrite_link(&self, value: &str, tables: &[sorm_query::sql::Table]) -> Option<String> {
+        let branches = self
+            .0
+            .iter()
+            .filter(|rename| {
+                tables
+                    .iter()
+                    .any(|table| table.to_string() == rename.old_name.to_string())
+            })
+            .map(|TableRename { old_name, new_name }| {
+                format!(
+                    "IF type::is::record({value}) AND meta::tb({value}) = '{old_name}' \
+                    THEN type::thing('{new_name}', meta::id({value}))"
+                )
+            })
+            .collect::<Vec<_>>();
+
+        (!branches.is_empty()).then(|| format!("{} ELSE {value} END", branches.join(" ELSE ")))
+    }
+}
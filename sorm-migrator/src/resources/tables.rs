@@ -56,76 +56,25 @@ impl<R: DbResources> DbResourcesMeta<Tables> for ComparisonTables<'_, R> {
     }
 
     fn queries(&self) -> MigrationResult<Queries> {
+        let renames =
+            TableRenames::detect(self.codebase_resources, &self.get_left(), &self.get_right())?;
         let left = self.get_left().get_names_as_set();
         let right = self.get_right().get_names_as_set();
         let tables = left.union(&right);
         let mut all_tables_with_sub_resources = vec![];
 
-        for table in tables {
-            let mut queries = Queries::default();
-            let def_left = self.get_left().get_definition(table).cloned();
-            let def_right = self.get_right().get_definition(table).cloned();
-
-            let events = ComparisonEvents {
-                table: &Table::from(table.clone()),
-                resources: self.resources,
-            };
-
-            let indexes = ComparisonIndexes {
-                table: &Table::from(table.clone()),
-                resources: self.resources,
-            };
-
-            let fields = ComparisonFields {
-                table: &Table::from(table.clone()),
-                resources: self.resources,
-                codebase_resources: self.codebase_resources,
-                prompter: self.prompter,
-            };
-
-            let fields = fields.queries()?;
-            let indexes = indexes.queries()?;
-            let events = events.queries()?;
-
-            let extend_table_resources_up = |acc: &mut Queries| {
-                acc.extend_up(&fields);
-                acc.extend_up(&indexes);
-                acc.extend_up(&events);
-            };
-
-            let extend_table_resources_down = |acc: &mut Queries| {
-                acc.extend_down(&fields);
-                acc.extend_down(&indexes);
-                acc.extend_down(&events);
-            };
-
-            match DeltaTypeResource::from((def_left, def_right)) {
-                DeltaTypeResource::NoChange => {
-                    extend_table_resources_up(&mut queries);
-                    extend_table_resources_down(&mut queries);
-                }
-                DeltaTypeResource::Update { left, right } => {
-                    queries.add_up(QueryType::Define(right));
-                    extend_table_resources_up(&mut queries);
-                    extend_table_resources_down(&mut queries);
-
-                    queries.add_down(QueryType::Define(left));
-                }
-                DeltaTypeResource::Create { right } => {
-                    queries.add_down(QueryType::Remove(right.as_remove_statement()?));
-
-                    queries.add_up(QueryType::Define(right));
-                    extend_table_resources_up(&mut queries);
-                }
-                DeltaTypeResource::Remove { left } => {
-                    queries.add_up(QueryType::Remove(left.as_remove_statement()?));
-                    queries.add_down(QueryType::Define(left));
-                    extend_table_resources_down(&mut queries);
-                }
-            };
+        for table_name in tables {
+            // Renamed tables are handled together after all other tables have been updated
+            if renames.involves(table_name) {
+                continue;
+            }
+            all_tables_with_sub_resources.push(self.table_queries(table_name)?);
+        }
 
-            all_tables_with_sub_resources.push(queries);
+        if !renames.is_empty() {
+            all_tables_with_sub_resources.push(self.rename_queries(&renames)?);
         }
+
         let last_table_index = all_tables_with_sub_resources.len() - 1;
         let spaced_queries = all_tables_with_sub_resources.into_iter().enumerate().fold(
             Queries::default(),
@@ -146,6 +95,120 @@ impl<R: DbResources> DbResourcesMeta<Tables> for ComparisonTables<'_, R> {
     }
 }
 
+impl<R: DbResources> ComparisonTables<'_, R> {
+    fn table_queries(&self, table_name: &String) -> MigrationResult<Queries> {
+        let table = Table::from(table_name.clone());
+        let mut queries = Queries::default();
+        let def_left = self.get_left().get_definition(table_name).cloned();
+        let def_right = self.get_right().get_definition(table_name).cloned();
+
+        let events = ComparisonEvents {
+            table: &table,
+            resources: self.resources,
+        };
+
+        let indexes = ComparisonIndexes {
+            table: &table,
+            resources: self.resources,
+        };
+
+        let fields = ComparisonFields {
+            table: &table,
+            resources: self.resources,
+            codebase_resources: self.codebase_resources,
+            prompter: self.prompter,
+        };
+
+        let fields = fields.queries()?;
+        let indexes = indexes.queries()?;
+        let events = events.queries()?;
+
+        let extend_table_resources_up = |acc: &mut Queries| {
+            acc.extend_up(&fields);
+            acc.extend_up(&indexes);
+            acc.extend_up(&events);
+        };
+
+        let extend_table_resources_down = |acc: &mut Queries| {
+            acc.extend_down(&fields);
+            acc.extend_down(&indexes);
+            acc.extend_down(&events);
+        };
+
+        match DeltaTypeResource::from((def_left, def_right)) {
+            DeltaTypeResource::NoChange => {
+                extend_table_resources_up(&mut queries);
+                extend_table_resources_down(&mut queries);
+            }
+            DeltaTypeResource::Update { left, right } => {
+                let safety = ChangeSafety::of_table_update(&left, &right);
+                queries.add_up_change(QueryType::Define(right), &table, safety);
+                extend_table_resources_up(&mut queries);
+                extend_table_resources_down(&mut queries);
+
+                queries.add_down(QueryType::Define(left));
+            }
+            DeltaTypeResource::Create { right } => {
+                queries.add_down(QueryType::Remove(right.as_remove_statement()?));
+
+                queries.add_up_change(QueryType::Define(right), &table, ChangeSafety::Additive);
+                extend_table_resources_up(&mut queries);
+            }
+            DeltaTypeResource::Remove { left } => {
+                queries.add_up_change(
+                    QueryType::Remove(left.as_remove_statement()?),
+                    &table,
+                    ChangeSafety::Destructive,
+                );
+                queries.add_down(QueryType::Define(left));
+                extend_table_resources_down(&mut queries);
+            }
+        };
+
+        Ok(queries)
+    }
+
+    // The new tables are defined and filled before links are rewritten and the old tables
+    // removed, so links between renamed tables and array links can still be resolved.
+    fn rename_queries(&self, renames: &TableRenames) -> MigrationResult<Queries> {
+        let left = &self.resources.left_resources;
+        let right = &self.resources.right_resources;
+        let mut queries = Queries::default();
+        let mut removals = Queries::default();
+
+        for rename in renames.iter() {
+            let TableRename { old_name, new_name } = rename;
+            let old_table = self.table_queries(&old_name.to_string())?;
+            let new_table = self.table_queries(&new_name.to_string())?;
+
+            queries.add_comment_to_up(format!("Rename table {old_name} to {new_name}"));
+            queries.extend_up(&new_table);
+            queries.add_comment_to_down(format!(
+                "Revert table name change. Change table {new_name} back to {old_name}"
+            ));
+            queries.extend_down(&old_table);
+
+            removals.extend_up(&old_table);
+            removals.extend_down(&new_table);
+        }
+
+        // Copying the records and rewriting the links to them keeps the existing data
+        for (table, query) in renames.data_queries(left, right) {
+            queries.add_up_change(query, &table, ChangeSafety::Additive);
+        }
+        for (_, query) in renames.reverse().data_queries(right, left) {
+            queries.add_down(query);
+        }
+
+        queries.extend_up(&removals);
+        queries.extend_down(&removals);
+        queries.add_comment_to_up("Rename table ending");
+        queries.add_comment_to_down("Revert table name change ending");
+
+        Ok(queries)
+    }
+}
+
 pub trait TableResourcesMeta<T>
 where
     T: Informational,
@@ -167,17 +230,23 @@ where
             let def_right = self.get_right().get_definition(name).cloned();
             let def_left = self.get_left().get_definition(name).cloned();
 
+            let table = self.get_table();
             match DeltaTypeResource::from((def_left, def_right)) {
                 DeltaTypeResource::Create { right } => {
                     queries.add_down(QueryType::Remove(right.as_remove_statement()?));
-                    queries.add_up(QueryType::Define(right));
+                    queries.add_up_change(QueryType::Define(right), table, ChangeSafety::Additive);
                 }
+                // Indexes and events hold no data of their own, so removing them keeps the records
                 DeltaTypeResource::Remove { left } => {
-                    queries.add_up(QueryType::Remove(left.as_remove_statement()?));
+                    queries.add_up_change(
+                        QueryType::Remove(left.as_remove_statement()?),
+                        table,
+                        ChangeSafety::Additive,
+                    );
                     queries.add_down(QueryType::Define(left));
                 }
                 DeltaTypeResource::Update { left, right } => {
-                    queries.add_up(QueryType::Define(right));
+                    queries.add_up_change(QueryType::Define(right), table, ChangeSafety::Additive);
                     queries.add_down(QueryType::Define(left));
                 }
                 DeltaTypeResource::NoChange => {}
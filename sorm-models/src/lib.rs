@@ -14,8 +14,15 @@ pub mod migrations;
 mod models;
 // pub use migrations::*;
 pub use models::alien::*;
+pub use models::astronaut::*;
 pub use models::attributes::*;
 pub use models::configuration::*;
+pub use models::crew_member::*;
+pub use models::fleet::*;
+pub use models::flight_log::*;
+pub use models::hangar::*;
+pub use models::mission::*;
+pub use models::pilot::*;
 pub use models::planet::*;
 pub use models::spaceship::*;
 pub use models::user::*;
@@ -0,0 +1,30 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use serde::{Deserialize, Serialize};
+use sorm::{Model, Node, SurrealEnum, SurrealSimpleId};
+
+#[derive(SurrealEnum, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DutyStatus {
+    #[default]
+    Active,
+    OnLeave,
+    Retired {
+        year: u16,
+    },
+}
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[sorm(table = astronaut)]
+pub struct Astronaut {
+    pub id: SurrealSimpleId<Self>,
+    pub name: String,
+    pub status: DutyStatus,
+    pub previous_status: Option<DutyStatus>,
+}
@@ -0,0 +1,18 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use serde::{Deserialize, Serialize};
+use sorm::{Node, SurrealSimpleId};
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[sorm(table = crew_member, soft_delete)]
+pub struct CrewMember {
+    pub id: SurrealSimpleId<Self>,
+    pub name: String,
+    pub rank: u8,
+}
@@ -0,0 +1,19 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use serde::{Deserialize, Serialize};
+use sorm::{Node, SurrealSimpleId};
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[sorm(table = fleet)]
+pub struct Fleet {
+    pub id: SurrealSimpleId<Self>,
+    pub name: String,
+    #[sorm(version)]
+    pub version: u64,
+}
@@ -0,0 +1,42 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::collections::HashMap;
+
+use crate::DutyStatus;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sorm::{sql, Model, Node, SurrealFieldType, SurrealSimpleId};
+
+#[derive(SurrealFieldType, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct CallSign(pub String);
+
+pub mod layover {
+    use serde::{Deserialize, Serialize};
+    use sorm::SurrealFieldType;
+
+    /// Minutes spent on the ground. Named like `std::time::Duration` but stored as an int.
+    #[derive(SurrealFieldType, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+    pub struct Duration(pub u32);
+}
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[sorm(table = flight_log)]
+pub struct FlightLog {
+    pub id: SurrealSimpleId<Self>,
+    pub call_sign: CallSign,
+    pub flight_date: NaiveDate,
+    pub fuel_cost: Decimal,
+    pub crew_size: Box<u8>,
+    pub pilot_status: Box<DutyStatus>,
+    pub past_statuses: Vec<DutyStatus>,
+    pub layover: layover::Duration,
+    pub checkpoints: HashMap<String, u32>,
+    pub tracking_id: sql::Uuid,
+}
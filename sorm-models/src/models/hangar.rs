@@ -0,0 +1,37 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use crate::SpaceShip;
+use serde::{Deserialize, Serialize};
+use sorm::{LinkMany, LinkOne, Node, NodeUnion, SurrealSimpleId};
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[sorm(table = shuttle)]
+pub struct Shuttle {
+    pub id: SurrealSimpleId<Self>,
+    pub name: String,
+    pub seats: u8,
+}
+
+#[derive(NodeUnion, Debug, Clone)]
+pub enum Craft {
+    SpaceShip(SpaceShip),
+    Shuttle(Shuttle),
+}
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[sorm(table = hangar)]
+pub struct Hangar {
+    pub id: SurrealSimpleId<Self>,
+    pub name: String,
+    #[sorm(link_one = "Craft")]
+    pub docked_craft: LinkOne<Craft>,
+    #[sorm(link_many = "Craft")]
+    pub visiting_crafts: LinkMany<Craft>,
+}
@@ -0,0 +1,22 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sorm::{Node, SurrealSimpleId};
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[sorm(table = mission)]
+pub struct Mission {
+    pub id: SurrealSimpleId<Self>,
+    pub name: String,
+    #[sorm(created_at)]
+    pub created_at: DateTime<Utc>,
+    #[sorm(updated_at)]
+    pub updated_at: DateTime<Utc>,
+}
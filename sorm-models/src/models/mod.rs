@@ -6,9 +6,16 @@
  */
 
 pub mod alien;
+pub mod astronaut;
 pub mod attributes;
 pub mod combined;
 pub mod configuration;
+pub mod crew_member;
+pub mod fleet;
+pub mod flight_log;
+pub mod hangar;
+pub mod mission;
+pub mod pilot;
 pub mod planet;
 pub mod spaceship;
 pub mod user;
@@ -0,0 +1,69 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use serde::{Deserialize, Serialize};
+use sorm::{
+    after, before, cond,
+    statements::{create, delete, CreateStatement},
+    Model, Node, Operatable, SchemaGetter, SetterAssignable, SurrealSimpleId, TableResources, E,
+};
+
+#[derive(Node, TableResources, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[sorm(
+    table = pilot,
+    index(name = "call_sign_rank_idx", fields = [call_sign, rank], unique),
+    event(
+        name = "rank_changed",
+        when = cond(event.eq("UPDATE")).and(before.rank.not_equal(after.rank)),
+        then = log_rank_change
+    ),
+    event(
+        name = "pilot_removed",
+        when = event.eq("DELETE"),
+        then = delete::<PilotRankChange>(PilotRankChange::table())
+            .where_(PilotRankChange::schema().callSign.eq(before.callSign))
+    )
+)]
+pub struct Pilot {
+    pub id: SurrealSimpleId<Self>,
+    #[sorm(unique)]
+    pub email: String,
+    #[sorm(index)]
+    pub call_sign: String,
+    pub rank: u8,
+    #[sorm(search(analyzer = "ascii", bm25(1.2, 0.75), highlights))]
+    pub bio: String,
+}
+
+#[allow(non_snake_case)]
+fn log_rank_change() -> CreateStatement<PilotRankChange> {
+    let pilot_rank_change::Schema {
+        callSign,
+        oldRank,
+        newRank,
+        ..
+    } = PilotRankChange::schema();
+    let before = before().with_path::<Pilot>(E);
+    let after = after().with_path::<Pilot>(E);
+
+    create().set([
+        callSign.equal_to(after.callSign),
+        oldRank.equal_to(before.rank),
+        newRank.equal_to(after.rank),
+    ])
+}
+
+#[derive(Node, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+#[sorm(table = pilot_rank_change)]
+pub struct PilotRankChange {
+    pub id: SurrealSimpleId<Self>,
+    pub call_sign: String,
+    pub old_rank: u8,
+    pub new_rank: u8,
+}
@@ -55,11 +55,32 @@ pub enum SurrealOrmError {
     #[error("The following fields could not be fetched as they are not linked to a foreign table: {0}. Please ensure that all fields provided are of types 'link_self', 'link_one' or 'link_many' to allow fetching of linked values from other tables.")]
     FieldsUnfetchableNotARecordLink(String),
 
+    #[error("The following aliases could not be fetched as they are not relations of the model: {0}. Please ensure that all aliases provided are of fields of type 'Relate' to allow fetching of related nodes.")]
+    AliasesUnfetchableNotARelation(String),
+
     #[error("No record returned from {0}. Check that all fields in this table are selected.")]
     RecordNotFound(String),
 
     #[error("Invalid subquery. {0}")]
     InvalidSubquery(String),
+
+    #[error("Unable to read live query notification. {0}")]
+    LiveNotification(String),
+
+    #[error("Statement {0} of the query failed. {1}")]
+    StatementFailed(usize, #[source] surrealdb::Error),
+
+    #[error("Unable to parse the result of statement {0} of the query. Check that the declared output type matches the statement. {1}")]
+    StatementDeserialization(usize, #[source] surrealdb::Error),
+
+    #[error("Invalid pagination cursor - {0}. Use the next or previous cursor of a page of the same paginated query.")]
+    InvalidCursor(String),
+
+    #[error("Stale write to {0}. The record was modified after version {1} of it was read. Fetch the record again and retry the write.")]
+    StaleWrite(IdString, u64),
+
+    #[error("Expected results of {0} statements, but {1} returned. Check that an output type is declared for every statement including LET statements.")]
+    StatementCountMismatch(usize, usize),
 }
 
 pub type SurrealOrmResult<T> = std::result::Result<T, SurrealOrmError>;
@@ -76,5 +76,6 @@ pub use surrealdb::sql;
 #[doc(hidden)]
 pub mod internal_tools {
     pub use paste::paste;
+    pub use serde_json;
     pub use typed_builder;
 }
@@ -0,0 +1,328 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use futures::{stream, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use surrealdb::{sql, Connection, Surreal};
+
+use crate::{
+    statements::{begin_transaction, insert, update, RelateStatement, TransactionCompletion},
+    traits::statements::check_statements,
+    Edge, Node, Runnable, SurrealOrmError, SurrealOrmResult,
+};
+
+/// Writes nodes in chunks, each chunk inserted with a single INSERT statement in its own
+/// transaction.
+///
+/// # Examples
+///
+/// ```rust, ignore
+/// let report = bulk_insert(weapons)
+///     .chunk_size(500)
+///     .concurrency(4)
+///     .run(db.clone())
+///     .await;
+///
+/// let inserted_ids = report.ids();
+/// for failed in report.failed() {
+///     println!("chunk {} failed: {:?}", failed.index, failed.result);
+/// }
+/// ```
+pub fn bulk_insert<'a, T, N>(nodes: N) -> BulkWrite<'a, T>
+where
+    T: Serialize + DeserializeOwned + Node + 'a,
+    N: IntoIterator<Item = T>,
+    N::IntoIter: Send + 'a,
+{
+    BulkWrite::new(nodes, |chunk: Vec<T>| {
+        begin_transaction()
+            .query(insert(chunk))
+            .commit_transaction()
+    })
+}
+
+/// Replaces the content of existing nodes by their ids in chunks, each chunk updated in its own
+/// transaction.
+pub fn bulk_update<'a, T, N>(nodes: N) -> BulkWrite<'a, T>
+where
+    T: Serialize + DeserializeOwned + Node + 'a,
+    N: IntoIterator<Item = T>,
+    N::IntoIter: Send + 'a,
+{
+    BulkWrite::new(nodes, |chunk: Vec<T>| {
+        chunk
+            .into_iter()
+            .fold(begin_transaction(), |transaction, node| {
+                transaction.query(update::<T>(node.get_id_as_thing()).content(node))
+            })
+            .commit_transaction()
+    })
+}
+
+/// Runs relate statements in chunks, each chunk in its own transaction.
+///
+/// # Examples
+///
+/// ```rust, ignore
+/// let relations = books.iter().map(|book| {
+///     relate(Student::with(&student_id).writes__(E).book(&book.id)).content(write.clone())
+/// });
+///
+/// let report = bulk_relate(relations).chunk_size(100).run(db.clone()).await;
+/// ```
+pub fn bulk_relate<'a, E, R>(relations: R) -> BulkWrite<'a, RelateStatement<E>>
+where
+    E: Serialize + DeserializeOwned + Edge + 'a,
+    R: IntoIterator<Item = RelateStatement<E>>,
+    R::IntoIter: Send + 'a,
+{
+    BulkWrite::new(relations, |chunk: Vec<RelateStatement<E>>| {
+        chunk
+            .into_iter()
+            .fold(begin_transaction(), |transaction, relation| {
+                transaction.query(relation)
+            })
+            .commit_transaction()
+    })
+}
+
+/// Bulk write of items split into chunks. A failed chunk is rolled back without affecting
+/// the other chunks. Items are only taken from the iterator when their chunk is about to
+/// be written.
+pub struct BulkWrite<'a, I> {
+    items: Box<dyn Iterator<Item = I> + Send + 'a>,
+    chunk_size: usize,
+    concurrency: usize,
+    transaction: fn(Vec<I>) -> TransactionCompletion,
+}
+
+impl<'a, I: 'a> BulkWrite<'a, I> {
+    const DEFAULT_CHUNK_SIZE: usize = 1000;
+
+    fn new<T>(items: T, transaction: fn(Vec<I>) -> TransactionCompletion) -> Self
+    where
+        T: IntoIterator<Item = I>,
+        T::IntoIter: Send + 'a,
+    {
+        Self {
+            items: Box::new(items.into_iter()),
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+            concurrency: 1,
+            transaction,
+        }
+    }
+
+    /// Maximum number of items written per transaction. Defaults to 1000.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Maximum number of chunks written at the same time. Defaults to 1 i.e one chunk after
+    /// the other.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Builds the transaction of each chunk
+    pub fn transactions(self) -> Vec<TransactionCompletion> {
+        self.into_chunks()
+            .map(|(_, transaction)| transaction)
+            .collect()
+    }
+
+    /// Takes the items and builds the transaction of a chunk on each step
+    fn into_chunks(self) -> impl Iterator<Item = (usize, TransactionCompletion)> + 'a {
+        let Self {
+            items,
+            chunk_size,
+            transaction,
+            ..
+        } = self;
+        let mut items = items.peekable();
+
+        std::iter::from_fn(move || {
+            items.peek()?;
+            let chunk = items.by_ref().take(chunk_size).collect::<Vec<_>>();
+            Some((chunk.len(), transaction(chunk)))
+        })
+    }
+
+    /// Runs the chunks against the database and reports the outcome of each of them.
+    pub async fn run(self, db: Surreal<impl Connection>) -> BulkReport {
+        let concurrency = self.concurrency;
+        let chunks = self
+            .into_chunks()
+            .enumerate()
+            .map(|(index, (size, transaction))| {
+                let db = db.clone();
+                async move {
+                    BulkChunk {
+                        index,
+                        size,
+                        result: run_chunk(transaction, db).await,
+                    }
+                }
+            });
+
+        BulkReport {
+            chunks: stream::iter(chunks).buffered(concurrency).collect().await,
+        }
+    }
+}
+
+async fn run_chunk(
+    transaction: TransactionCompletion,
+    db: Surreal<impl Connection>,
+) -> SurrealOrmResult<Vec<sql::Thing>> {
+    let mut response = transaction.run(db).await?;
+    check_statements(&mut response, transaction.queries_count())?;
+
+    let mut ids = vec![];
+    for index in 0..transaction.queries_count() {
+        let written = response
+            .take::<sql::Value>(index)
+            .map_err(|e| SurrealOrmError::StatementFailed(index, e))?;
+        let written = match written {
+            sql::Value::Array(records) => records.0,
+            record => vec![record],
+        };
+        ids.extend(written.iter().filter_map(|record| match record.rid() {
+            sql::Value::Thing(id) => Some(id),
+            _ => None,
+        }));
+    }
+
+    Ok(ids)
+}
+
+/// Outcome of a chunk of a bulk write
+#[derive(Debug)]
+pub struct BulkChunk {
+    /// Position of the chunk in the bulk write
+    pub index: usize,
+    /// Number of items in the chunk
+    pub size: usize,
+    /// Ids of the records written by the chunk or why the chunk failed
+    pub result: SurrealOrmResult<Vec<sql::Thing>>,
+}
+
+impl BulkChunk {
+    /// Whether the chunk was written
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Outcome of every chunk of a bulk write, in the order of the chunks
+#[derive(Debug, Default)]
+pub struct BulkReport {
+    /// The chunks of the bulk write
+    pub chunks: Vec<BulkChunk>,
+}
+
+impl BulkReport {
+    /// Ids of all the records written by the chunks that succeeded
+    pub fn ids(&self) -> Vec<sql::Thing> {
+        self.succeeded()
+            .filter_map(|chunk| chunk.result.as_ref().ok())
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// Chunks that were written
+    pub fn succeeded(&self) -> impl Iterator<Item = &BulkChunk> {
+        self.chunks.iter().filter(|chunk| chunk.is_ok())
+    }
+
+    /// Chunks that failed and were rolled back
+    pub fn failed(&self) -> impl Iterator<Item = &BulkChunk> {
+        self.chunks.iter().filter(|chunk| !chunk.is_ok())
+    }
+
+    /// Whether every chunk was written
+    pub fn is_success(&self) -> bool {
+        self.failed().next().is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{statements::select, All, Buildable, Table, ToRaw};
+
+    #[test]
+    fn test_bulk_splits_items_into_chunks() {
+        let table = Table::new("user");
+        let ids = (1..=5)
+            .map(|i| sql::Thing::from((table.to_string(), sql::Id::from(i))))
+            .collect::<Vec<_>>();
+
+        let bulk = BulkWrite::new(ids, |chunk: Vec<sql::Thing>| {
+            chunk
+                .into_iter()
+                .fold(begin_transaction(), |transaction, id| {
+                    transaction.query(select(All).from(id))
+                })
+                .commit_transaction()
+        })
+        .chunk_size(2);
+
+        let transactions = bulk.transactions();
+        assert_eq!(transactions.len(), 3);
+        assert_eq!(
+            transactions
+                .iter()
+                .map(|t| t.queries_count())
+                .collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        assert!(transactions[2]
+            .to_raw()
+            .build()
+            .contains("SELECT * FROM user:5"));
+    }
+
+    #[test]
+    fn test_bulk_takes_items_per_chunk() {
+        let taken = AtomicUsize::new(0);
+        let ids = (1..=5).map(|i| {
+            taken.fetch_add(1, Ordering::SeqCst);
+            sql::Thing::from(("user".to_string(), sql::Id::from(i)))
+        });
+
+        let mut chunks = BulkWrite::new(ids, |chunk: Vec<sql::Thing>| {
+            chunk
+                .into_iter()
+                .fold(begin_transaction(), |transaction, id| {
+                    transaction.query(select(All).from(id))
+                })
+                .commit_transaction()
+        })
+        .chunk_size(2)
+        .into_chunks();
+        assert_eq!(taken.load(Ordering::SeqCst), 0);
+
+        assert_eq!(chunks.next().map(|(size, _)| size), Some(2));
+        assert_eq!(taken.load(Ordering::SeqCst), 2);
+
+        assert_eq!(chunks.next().map(|(size, _)| size), Some(2));
+        assert_eq!(taken.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_bulk_without_items_has_no_chunks() {
+        let bulk = BulkWrite::new(Vec::<sql::Thing>::new(), |_| {
+            begin_transaction().commit_transaction()
+        });
+        assert!(bulk.transactions().is_empty());
+    }
+}
@@ -112,6 +112,31 @@ impl DefineFieldStatement {
     pub fn value(mut self, default_value: impl Into<ValueLike>) -> Self {
         // pub fn value<T, U>(mut self, default_value: U) where T: Deref<Target = U>, T: Into<U>, U: Into<T>, T: Into<ValueLike>, T: Into<sql::Value> -> Self {
         let value: ValueLike = default_value.into();
+        self.value = Some(format!("$value OR {}", value.build()));
+        self.bindings.extend(value.get_bindings());
+        self
+    }
+
+    /// Set the value the field is computed to on every write, ignoring the value written to it.
+    ///
+    /// Examples:
+    ///
+    /// ```rust
+    ///     # use sorm_query as surreal_orm;
+    ///     use surreal_orm::{*, statements::define_field};
+    ///
+    ///     let statement = define_field(Field::new("created_at"))
+    ///         .on_table(Table::from("user"))
+    ///         .type_(FieldType::Datetime)
+    ///         .computed_value(ValueLike::new(Raw::new("$before OR time::now()")));
+    ///
+    ///     assert_eq!(
+    ///         statement.to_raw().build(),
+    ///         "DEFINE FIELD created_at ON TABLE user TYPE datetime VALUE $before OR time::now();"
+    ///     );
+    /// ```
+    pub fn computed_value(mut self, value: impl Into<ValueLike>) -> Self {
+        let value: ValueLike = value.into();
         self.value = Some(value.build());
         self.bindings.extend(value.get_bindings());
         self
@@ -246,7 +271,7 @@ impl Buildable for DefineFieldStatement {
         }
 
         if let Some(value) = &self.value {
-            query = format!("{query} VALUE $value OR {value}");
+            query = format!("{query} VALUE {value}");
         }
 
         if let Some(assertion) = &self.assert {
@@ -275,7 +300,7 @@ impl Display for DefineFieldStatement {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{cond, value, Operatable, ToRaw, NONE};
+    use crate::{cond, value, Operatable, Raw, ToRaw, NONE};
     use crate::{statements::for_permission, CrudType::*};
 
     #[test]
@@ -338,4 +363,22 @@ mod tests {
         insta::assert_snapshot!(statement.fine_tune_params());
         assert_eq!(statement.get_bindings().len(), 0);
     }
+
+    #[test]
+    fn test_define_field_statement_computed_value() {
+        use FieldType::*;
+
+        let updated_at = Field::new("updated_at");
+        let user_table = Table::from("user");
+        let statement = define_field(updated_at)
+            .on_table(user_table)
+            .type_(Datetime)
+            .computed_value(ValueLike::new(Raw::new("time::now()")));
+
+        assert_eq!(
+            statement.build(),
+            "DEFINE FIELD updated_at ON TABLE user TYPE datetime VALUE time::now();"
+        );
+        assert_eq!(statement.get_bindings().len(), 0);
+    }
 }
@@ -15,7 +15,7 @@ use crate::{
     Binding, Conditional, ErrorList, ReturnableDefault, ReturnableStandard, ToRaw,
 };
 
-use super::update::TargettablesForUpdate;
+use super::update::{TargettablesForUpdate, UpdateStatement};
 
 /*
 Statement syntax
@@ -316,18 +316,42 @@ where
     }
 }
 
-/// Used in model helper methods for deleting records
+/// What the model delete helpers run
 #[derive(Debug, Clone)]
-pub struct DeleteStatementMini<T>(DeleteStatement<T>)
+enum Deletion<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    /// Deletes the records
+    Hard(DeleteStatement<T>),
+    /// Marks the records of a `#[sorm(soft_delete)]` table as deleted
+    Soft(UpdateStatement<T>),
+}
+
+/// Used in model helper methods for deleting records. Records of `#[sorm(soft_delete)]`
+/// tables are marked as deleted rather than deleted.
+#[derive(Debug, Clone)]
+pub struct DeleteStatementMini<T>(Deletion<T>)
 where
     T: Serialize + DeserializeOwned + Model;
 
+impl<T> DeleteStatementMini<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    /// Soft deletes the records updated by the statement. Returns nothing by default
+    /// like a DELETE statement.
+    pub(crate) fn soft(statement: UpdateStatement<T>) -> Self {
+        Self(Deletion::Soft(statement.return_type(ReturnType::None)))
+    }
+}
+
 impl<T> From<DeleteStatement<T>> for DeleteStatementMini<T>
 where
     T: Serialize + DeserializeOwned + Model,
 {
     fn from(statement: DeleteStatement<T>) -> Self {
-        Self(statement)
+        Self(Deletion::Hard(statement))
     }
 }
 
@@ -336,7 +360,10 @@ where
     T: Serialize + DeserializeOwned + Model,
 {
     fn get_bindings(&self) -> BindingsList {
-        self.0.get_bindings()
+        match &self.0 {
+            Deletion::Hard(statement) => statement.get_bindings(),
+            Deletion::Soft(statement) => statement.get_bindings(),
+        }
     }
 }
 
@@ -345,7 +372,10 @@ where
     T: Serialize + DeserializeOwned + Model,
 {
     fn get_errors(&self) -> ErrorList {
-        self.0.get_errors()
+        match &self.0 {
+            Deletion::Hard(statement) => statement.get_errors(),
+            Deletion::Soft(statement) => statement.get_errors(),
+        }
     }
 }
 
@@ -358,13 +388,18 @@ impl<T> ReturnableStandard<T> for DeleteStatementMini<T>
 where
     T: Serialize + DeserializeOwned + Model + Send + Sync,
 {
-    fn set_return_type(mut self, return_type: ReturnType) -> Self {
-        self.0.return_type = Some(return_type);
-        self
+    fn set_return_type(self, return_type: ReturnType) -> Self {
+        Self(match self.0 {
+            Deletion::Hard(statement) => Deletion::Hard(statement.set_return_type(return_type)),
+            Deletion::Soft(statement) => Deletion::Soft(statement.set_return_type(return_type)),
+        })
     }
 
     fn get_return_type(&self) -> ReturnType {
-        self.0.return_type.clone().unwrap_or(ReturnType::None)
+        match &self.0 {
+            Deletion::Hard(statement) => statement.get_return_type(),
+            Deletion::Soft(statement) => statement.get_return_type(),
+        }
     }
 }
 
@@ -373,7 +408,10 @@ where
     T: Serialize + DeserializeOwned + Model,
 {
     fn build(&self) -> String {
-        self.0.build()
+        match &self.0 {
+            Deletion::Hard(statement) => statement.build(),
+            Deletion::Soft(statement) => statement.build(),
+        }
     }
 }
 
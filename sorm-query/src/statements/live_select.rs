@@ -5,11 +5,25 @@
  * Licensed under the MIT license
  */
 
-use std::fmt;
+use std::{
+    fmt,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::{Stream, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize};
+use surrealdb::{
+    method::QueryStream,
+    sql::{self, Thing, Value},
+    Action, Connection, Notification, Surreal,
+};
 
 use crate::{
     statements::SelectStatement,
     traits::{BindingsList, Buildable, Erroneous, Parametric, Queryable},
+    Model, Runnable, SurrealOrmError, SurrealOrmResult,
 };
 
 /// Creates a LIVE SELECT statement builder.
@@ -32,6 +46,47 @@ pub struct LiveSelectStatement {
     select: SelectStatement,
 }
 
+impl LiveSelectStatement {
+    /// Notifies with the changes made to the records as JSON patches rather than the records.
+    /// The projections of the select statement are ignored.
+    ///
+    /// Examples
+    /// ```rust
+    /// # use sorm_query as surreal_orm;
+    /// use surreal_orm::{*, statements::{live, select}};
+    /// let person = Table::new("person");
+    /// let statement = live(select(All).from(person)).diff();
+    /// assert_eq!(statement.build(), "LIVE SELECT DIFF FROM person;");
+    /// ```
+    pub fn diff(self) -> LiveSelectDiffStatement {
+        LiveSelectDiffStatement(Self {
+            select: self.select.diff(),
+        })
+    }
+
+    /// Starts the live query and streams its notifications as typed events.
+    /// The live query is killed when the stream is dropped.
+    ///
+    /// Examples
+    /// ```rust, ignore
+    /// use futures::StreamExt;
+    ///
+    /// let mut animals = live(select(All).from(Animal::table())).stream::<Animal>(db.clone()).await?;
+    /// while let Some(event) = animals.next().await {
+    ///     match event? {
+    ///         LiveEvent::Create(animal) | LiveEvent::Update(animal) => println!("{animal:?}"),
+    ///         LiveEvent::Delete(id) => println!("deleted {id}"),
+    ///     }
+    /// }
+    /// ```
+    pub async fn stream<T>(&self, db: Surreal<impl Connection>) -> SurrealOrmResult<LiveStream<T>>
+    where
+        T: Model + DeserializeOwned,
+    {
+        LiveStream::start(self, db).await
+    }
+}
+
 impl Queryable for LiveSelectStatement {}
 
 impl Erroneous for LiveSelectStatement {}
@@ -55,6 +110,185 @@ impl fmt::Display for LiveSelectStatement {
     }
 }
 
+/// Represents the LIVE SELECT DIFF statement.
+pub struct LiveSelectDiffStatement(LiveSelectStatement);
+
+impl LiveSelectDiffStatement {
+    /// Starts the live query and streams the patches of each change.
+    /// The live query is killed when the stream is dropped.
+    pub async fn stream(
+        &self,
+        db: Surreal<impl Connection>,
+    ) -> SurrealOrmResult<LiveStream<Vec<LivePatch>>> {
+        LiveStream::start(self, db).await
+    }
+}
+
+impl Queryable for LiveSelectDiffStatement {}
+
+impl Erroneous for LiveSelectDiffStatement {}
+
+impl Parametric for LiveSelectDiffStatement {
+    fn get_bindings(&self) -> BindingsList {
+        self.0.get_bindings()
+    }
+}
+
+impl Buildable for LiveSelectDiffStatement {
+    fn build(&self) -> String {
+        self.0.build()
+    }
+}
+
+impl fmt::Display for LiveSelectDiffStatement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.build())
+    }
+}
+
+/// A change notified by a live query
+#[derive(Debug, Clone, PartialEq)]
+pub enum LiveEvent<T> {
+    /// A record was created. Holds the record or its patches in diff mode.
+    Create(T),
+    /// A record was updated. Holds the record or its patches in diff mode.
+    Update(T),
+    /// A record was deleted
+    Delete(Thing),
+}
+
+/// JSON patch operation notified by a live select in diff mode.
+/// Values are in simplified JSON, so record ids are strings e.g `"person:oyelowo"`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum LivePatch {
+    /// Adds the value at the path
+    Add {
+        /// JSON pointer to the changed value
+        path: String,
+        /// The added value
+        value: serde_json::Value,
+    },
+    /// Removes the value at the path
+    Remove {
+        /// JSON pointer to the removed value
+        path: String,
+    },
+    /// Replaces the value at the path. A path of `/` replaces the whole record.
+    Replace {
+        /// JSON pointer to the replaced value
+        path: String,
+        /// The new value
+        value: serde_json::Value,
+    },
+    /// Changes the string at the path with a diff-match-patch text patch
+    Change {
+        /// JSON pointer to the changed string
+        path: String,
+        /// The text patch
+        value: String,
+    },
+    /// Copies the value from a path to another
+    Copy {
+        /// JSON pointer to the copied value
+        from: String,
+        /// JSON pointer to the copy
+        path: String,
+    },
+    /// Moves the value from a path to another
+    Move {
+        /// JSON pointer to the moved value
+        from: String,
+        /// JSON pointer the value is moved to
+        path: String,
+    },
+    /// Tests that the value at the path is equal to the value
+    Test {
+        /// JSON pointer to the tested value
+        path: String,
+        /// The expected value
+        value: serde_json::Value,
+    },
+}
+
+/// Stream of the typed events of a live query.
+/// The live query is killed when the stream is dropped.
+pub struct LiveStream<P> {
+    notifications: QueryStream<Value>,
+    payload: PhantomData<fn() -> P>,
+}
+
+impl<P: LivePayload> LiveStream<P> {
+    async fn start(
+        statement: &(impl Runnable + Sync),
+        db: Surreal<impl Connection>,
+    ) -> SurrealOrmResult<Self> {
+        let mut response = statement.run(db).await?;
+        let notifications = response
+            .stream::<Value>(0)
+            .map_err(SurrealOrmError::QueryRun)?;
+
+        Ok(Self {
+            notifications,
+            payload: PhantomData,
+        })
+    }
+
+    fn event(notification: Notification<Value>) -> SurrealOrmResult<LiveEvent<P>> {
+        let Notification { action, data, .. } = notification;
+
+        match action {
+            Action::Create => Ok(LiveEvent::Create(P::from_notification(data)?)),
+            Action::Update => Ok(LiveEvent::Update(P::from_notification(data)?)),
+            // The deleted record is notified in both normal and diff mode
+            Action::Delete => match data {
+                Value::Thing(id) => Ok(LiveEvent::Delete(id)),
+                Value::Object(mut record) => match record.remove("id") {
+                    Some(Value::Thing(id)) => Ok(LiveEvent::Delete(id)),
+                    _ => Err(SurrealOrmError::LiveNotification(format!(
+                        "Deleted record has no id: {record}"
+                    ))),
+                },
+                data => Err(SurrealOrmError::LiveNotification(format!(
+                    "Unexpected deleted record: {data}"
+                ))),
+            },
+            action => Err(SurrealOrmError::LiveNotification(format!(
+                "Unsupported action: {action:?}"
+            ))),
+        }
+    }
+}
+
+impl<P: LivePayload> Stream for LiveStream<P> {
+    type Item = SurrealOrmResult<LiveEvent<P>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.notifications
+            .poll_next_unpin(cx)
+            .map(|notification| notification.map(Self::event))
+    }
+}
+
+/// Content of the create and update events of a live query
+pub trait LivePayload: Sized {
+    /// Reads the content from the data of a notification
+    fn from_notification(data: Value) -> SurrealOrmResult<Self>;
+}
+
+impl<T: Model + DeserializeOwned> LivePayload for T {
+    fn from_notification(data: Value) -> SurrealOrmResult<Self> {
+        sql::from_value(data).map_err(|e| SurrealOrmError::Deserialization(e.into()))
+    }
+}
+
+impl LivePayload for Vec<LivePatch> {
+    fn from_notification(data: Value) -> SurrealOrmResult<Self> {
+        serde_json::from_value(data.into_json())
+            .map_err(|e| SurrealOrmError::LiveNotification(e.to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -67,4 +301,12 @@ mod tests {
 
         assert_eq!(statement, "LIVE SELECT * FROM person;");
     }
+
+    #[test]
+    fn test_live_select_diff_build() {
+        let person = Table::new("person");
+        let statement = live(select(All).from(person)).diff().build();
+
+        assert_eq!(statement, "LIVE SELECT DIFF FROM person;");
+    }
 }
@@ -8,6 +8,7 @@
 use std::fmt::Display;
 
 pub(crate) mod break_;
+pub(crate) mod bulk;
 pub(crate) mod continue_;
 pub(crate) mod create;
 pub(crate) mod create_v2;
@@ -49,6 +50,7 @@ pub(crate) mod remove_token;
 pub(crate) mod remove_user;
 pub(crate) mod return_;
 pub(crate) mod select;
+pub(crate) mod select_pagination;
 pub(crate) mod select_surreal_id_range;
 pub(crate) mod show;
 pub(crate) mod sleep;
@@ -56,11 +58,13 @@ pub(crate) mod subquery;
 pub(crate) mod throw;
 pub(crate) mod transaction;
 pub(crate) mod update;
+pub(crate) mod upsert;
 pub(crate) mod use_;
 pub(crate) mod utils_block;
 pub(crate) mod utils_chain;
 
 pub use break_::{break_, BreakStatement};
+pub use bulk::{bulk_insert, bulk_relate, bulk_update, BulkChunk, BulkReport, BulkWrite};
 pub use continue_::{continue_, ContinueStatement};
 pub use create::{create, create_only, CreateStatement};
 pub use create_v2::{create_v2, CreateStatementV2};
@@ -86,7 +90,10 @@ pub use ifelse::{if_, IfElseStatement};
 pub use info::{info_for, InfoStatement};
 pub use insert::{insert, InsertStatement, Insertables};
 pub use let_::{let_, LetStatement};
-pub use live_select::{live, LiveSelectStatement};
+pub use live_select::{
+    live, LiveEvent, LivePatch, LivePayload, LiveSelectDiffStatement, LiveSelectStatement,
+    LiveStream,
+};
 pub use relate::{relate, relate_only, RelateStatement};
 pub use remove_analyzer::{remove_analyzer, RemoveAnalyzerStatement};
 pub use remove_database::{remove_database, RemoveDatabaseStatement};
@@ -107,6 +114,7 @@ pub use select::{
     order, select, select_diff, select_value, Order, Orderables, SelectStatement,
     SelectStatementMini, Selectables, Splittables,
 };
+pub use select_pagination::{Cursor, Page, Paginate};
 pub use show::{show_changes_for_table, ShowChangesStatement};
 pub use sleep::{sleep, SleepStatement};
 pub use subquery::Subquery;
@@ -115,6 +123,7 @@ pub use transaction::{
     begin_transaction, transaction_deprecated, BeginTransactionStatement, TransactionCompletion,
 };
 pub use update::{update, update_only, UpdateStatement};
+pub use upsert::UpsertStatement;
 pub use use_::{use_, UseStatement};
 
 /// helpers for statements
@@ -36,9 +36,9 @@ use surrealdb::{engine::local::Db, sql, Surreal};
 
 use crate::{
     Aliasable, All, Binding, BindingsList, Buildable, Conditional, DurationLike, Erroneous,
-    ErrorList, Field, Filter, Function, IndexName, Model, NumberLike, Parametric, Queryable,
-    ReturnableSelect, ReturnableStandard, SurrealId, SurrealOrmResult, SurrealSimpleId,
-    SurrealUlid, SurrealUuid, Table, ToRaw, ValueLike,
+    ErrorList, Field, Filter, Function, IndexName, LinkedModel, Model, NumberLike, Operatable,
+    Parametric, Queryable, ReturnableSelect, ReturnableStandard, SurrealId, SurrealOrmResult,
+    SurrealSimpleId, SurrealUlid, SurrealUuid, Table, ToRaw, ValueLike, NONE,
 };
 
 use super::Subquery;
@@ -183,6 +183,24 @@ impl Order {
         self.option = Some(OrderOption::Numeric);
         self
     }
+
+    pub(crate) fn get_field(&self) -> &Field {
+        &self.field
+    }
+
+    pub(crate) fn is_desc(&self) -> bool {
+        matches!(self.direction, Some(OrderDirection::Desc))
+    }
+
+    /// Same ordering in the opposite direction
+    pub(crate) fn reversed(mut self) -> Self {
+        self.direction = Some(if self.is_desc() {
+            OrderDirection::Asc
+        } else {
+            OrderDirection::Desc
+        });
+        self
+    }
 }
 
 impl Display for &Order {
@@ -1124,6 +1142,42 @@ impl From<SelectStatementInit> for SelectStatement {
 }
 
 impl SelectStatement {
+    /// Selects the changes made to the records as JSON patches in place of the projections.
+    /// Only meaningful for live selects.
+    pub(crate) fn diff(mut self) -> Self {
+        self.0.selection_type = SelectionType::SelectDiff;
+        self.0.projections = String::new();
+        self
+    }
+
+    /// Replaces the ordering, start and limit of the statement with the ones of a keyset page
+    /// and adds the keyset condition to its filter.
+    pub(crate) fn keyset(
+        mut self,
+        condition: Option<ValueLike>,
+        order_by: Vec<Order>,
+        limit: u64,
+    ) -> Self {
+        if let Some(condition) = condition {
+            self = self.and_where(condition);
+        }
+
+        self.0.order_by = vec![];
+        self.0.start = None;
+        self.order_by(order_by).limit(limit)
+    }
+
+    /// Adds a condition to the existing `WHERE` clause, if any, with `AND`.
+    pub(crate) fn and_where(mut self, condition: impl Parametric + Buildable + Erroneous) -> Self {
+        self.update_bindings(condition.get_bindings());
+        self.0.errors.extend(condition.get_errors());
+        self.0.where_ = Some(match self.0.where_.take() {
+            Some(filter) => format!("({filter}) AND ({})", condition.build()),
+            None => condition.build(),
+        });
+        self
+    }
+
     /// Adds a condition to the `` clause of the SQL query.
     ///
     /// # Arguments
@@ -1486,6 +1540,42 @@ impl SelectStatement {
         self
     }
 
+    /// Fetches nested record links in place. Each path is validated at every hop
+    /// against the linked fields of the model it goes through, starting from `T`,
+    /// and all its hops are added to the FETCH clause.
+    ///
+    /// # Arguments
+    ///
+    /// * `include_paths` - record link paths built from the schema
+    ///
+    /// # Example
+    ///
+    /// ```rust, ignore
+    /// select(All)
+    ///     .from(Book::table())
+    ///     // Generates FETCH author, author.company, author.company.address
+    ///     .include::<Book>(arr![Book::schema().author().company().address()])?;
+    ///
+    /// // link_many fields can be part of the path
+    /// select(All)
+    ///     .from(Alien::table())
+    ///     .include::<Alien>(arr![Alien::schema().spaceShips(All).captain()])?;
+    /// ```
+    pub fn include<T: Model>(
+        mut self,
+        include_paths: Vec<impl Into<ValueLike>>,
+    ) -> SurrealOrmResult<Self> {
+        for path in include_paths {
+            let path: ValueLike = path.into();
+            for fetch_path in LinkedModel::of::<T>().fetch_paths(&path.build())? {
+                if !self.0.fetch.contains(&fetch_path) {
+                    self.0.fetch.push(fetch_path);
+                }
+            }
+        }
+        Ok(self)
+    }
+
     /// Sets the timeout duration for the query.
     ///
     /// # Arguments
@@ -1559,10 +1649,16 @@ impl Buildable for SelectStatement {
             "".to_string()
         };
 
+        let projections = if statement.projections.is_empty() {
+            "".to_string()
+        } else {
+            format!("{} ", statement.projections)
+        };
+
         let only = if statement.only { "ONLY " } else { "" };
         let mut query = format!(
-            "{select} {} {}FROM {}{}",
-            statement.projections,
+            "{select} {}{}FROM {}{}",
+            projections,
             omitted_fields,
             only,
             statement.targets.join(", ")
@@ -1641,11 +1737,78 @@ impl Buildable for SelectStatement {
     }
 }
 
+/// Which records of a `#[sorm(soft_delete)]` table the model helpers query
+#[derive(Debug, Clone, Copy, Default)]
+enum DeletedScope {
+    /// Records that are not soft deleted
+    #[default]
+    Excluded,
+    /// Soft deleted records as well
+    Included,
+    /// Soft deleted records only
+    Only,
+}
+
+/// Narrows the records queried by the model helpers down to the soft deleted scope
+#[derive(Debug, Clone, Default)]
+struct SoftDeleteScope {
+    /// The `deleted_at` field of the model if its table is soft deleted
+    deleted_at: Option<Field>,
+    scope: DeletedScope,
+}
+
+impl SoftDeleteScope {
+    fn of<T: Model>() -> Self {
+        Self {
+            deleted_at: T::soft_delete_field(),
+            scope: DeletedScope::default(),
+        }
+    }
+
+    fn with_deleted(&mut self) {
+        self.scope = DeletedScope::Included;
+    }
+
+    fn only_deleted(&mut self) -> Result<(), String> {
+        if self.deleted_at.is_none() {
+            return Err(
+                "Only tables with #[sorm(soft_delete)] have soft deleted records to query"
+                    .to_string(),
+            );
+        }
+        self.scope = DeletedScope::Only;
+        Ok(())
+    }
+
+    fn apply(&self, statement: SelectStatement) -> SelectStatement {
+        match (&self.deleted_at, self.scope) {
+            (Some(deleted_at), DeletedScope::Excluded) => statement.and_where(deleted_at.is(NONE)),
+            (Some(deleted_at), DeletedScope::Only) => statement.and_where(deleted_at.is_not(NONE)),
+            _ => statement,
+        }
+    }
+}
+
 /// A mini version of the select statement used as Model convenience method for building select statements.
+/// Soft deleted records are left out unless `with_deleted` or `only_deleted` is used.
 #[derive(Debug, Clone)]
-pub struct SelectStatementMini<T: Model>(SelectStatement, PhantomData<T>);
+pub struct SelectStatementMini<T: Model>(SelectStatement, SoftDeleteScope, PhantomData<T>);
 
 impl<T: Model> SelectStatementMini<T> {
+    /// Includes the soft deleted records of a `#[sorm(soft_delete)]` table
+    pub fn with_deleted(mut self) -> Self {
+        self.1.with_deleted();
+        self
+    }
+
+    /// Only returns the soft deleted records of a `#[sorm(soft_delete)]` table
+    pub fn only_deleted(mut self) -> Self {
+        if let Err(e) = self.1.only_deleted() {
+            self.0 .0.errors.push(e);
+        }
+        self
+    }
+
     /// Order the results by the given fields
     pub fn order_by(mut self, orderables: impl Into<Orderables>) -> Self {
         let orderables: Orderables = orderables.into();
@@ -1677,6 +1840,20 @@ impl<T: Model> SelectStatementMini<T> {
         self.0 .0.parallel = true;
         self
     }
+
+    /// Fetches nested record links in place, validating every hop of the paths.
+    /// See [`SelectStatement::include`].
+    pub fn include(self, include_paths: Vec<impl Into<ValueLike>>) -> SurrealOrmResult<Self> {
+        Ok(Self(
+            self.0.include::<T>(include_paths)?,
+            self.1,
+            PhantomData,
+        ))
+    }
+
+    pub(crate) fn into_inner(self) -> SelectStatement {
+        self.1.apply(self.0)
+    }
 }
 
 impl<T> From<SelectStatement> for SelectStatementMini<T>
@@ -1684,18 +1861,25 @@ where
     T: Serialize + DeserializeOwned + Model,
 {
     fn from(value: SelectStatement) -> Self {
-        Self(value, PhantomData)
+        Self(value, SoftDeleteScope::of::<T>(), PhantomData)
     }
 }
 
-impl<T> Erroneous for SelectStatementMini<T> where T: Serialize + DeserializeOwned + Model {}
+impl<T> Erroneous for SelectStatementMini<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn get_errors(&self) -> ErrorList {
+        self.0.get_errors()
+    }
+}
 
 impl<T> Parametric for SelectStatementMini<T>
 where
     T: Serialize + DeserializeOwned + Model,
 {
     fn get_bindings(&self) -> crate::BindingsList {
-        self.0.get_bindings()
+        self.1.apply(self.0.clone()).get_bindings()
     }
 }
 impl<T> Buildable for SelectStatementMini<T>
@@ -1703,7 +1887,7 @@ where
     T: Serialize + DeserializeOwned + Model,
 {
     fn build(&self) -> String {
-        self.0.build()
+        self.1.apply(self.0.clone()).build()
     }
 }
 
@@ -1725,28 +1909,71 @@ where
     }
 }
 
+/// Counts the records of a model. Soft deleted records are left out unless `with_deleted`
+/// or `only_deleted` is used.
+/// Holds the counting statement e.g `SELECT count() FROM user GROUP ALL` of a soft deleted
+/// table, so that it can be scoped, and the whole statement otherwise.
 #[derive(Debug, Clone)]
-pub struct SelectStatementCount(SelectStatement);
+pub struct SelectStatementCount(SelectStatement, SoftDeleteScope);
 
 impl SelectStatementCount {
+    /// Counts the records grouped by the counting statement e.g
+    /// `SELECT count() FROM user GROUP ALL`
+    pub(crate) fn of<T: Model>(counting: SelectStatement) -> Self {
+        let scope = SoftDeleteScope::of::<T>();
+        match scope.deleted_at {
+            Some(_) => Self(counting, scope),
+            None => Self::from(Self::count(counting)),
+        }
+    }
+
+    fn count(counting: SelectStatement) -> SelectStatement {
+        select_value(Field::new("count")).from(counting)
+    }
+
+    /// Includes the soft deleted records of a `#[sorm(soft_delete)]` table
+    pub fn with_deleted(mut self) -> Self {
+        self.1.with_deleted();
+        self
+    }
+
+    /// Only counts the soft deleted records of a `#[sorm(soft_delete)]` table
+    pub fn only_deleted(mut self) -> Self {
+        if let Err(e) = self.1.only_deleted() {
+            self.0 .0.errors.push(e);
+        }
+        self
+    }
+
+    fn statement(&self) -> SelectStatement {
+        match self.1.deleted_at {
+            Some(_) => Self::count(self.1.apply(self.0.clone())),
+            None => self.0.clone(),
+        }
+    }
+
     // /// Gets count of the records that would be returned by the select statement.
     // /// Defaults to zero if there is no result or query is invalid.
     pub async fn get(&self, db: Surreal<Db>) -> SurrealOrmResult<usize> {
-        Ok(self.0.return_one(db).await?.unwrap_or_default())
+        Ok(self.statement().return_one(db).await?.unwrap_or_default())
     }
 }
 
-impl Erroneous for SelectStatementCount {}
+impl Erroneous for SelectStatementCount {
+    fn get_errors(&self) -> ErrorList {
+        self.0.get_errors()
+    }
+}
 
 impl Parametric for SelectStatementCount {
     fn get_bindings(&self) -> crate::BindingsList {
-        self.0.get_bindings()
+        self.statement().get_bindings()
     }
 }
 
 impl Buildable for SelectStatementCount {
     fn build(&self) -> String {
-        self.0.build()
+        self.statement().build()
     }
 }
 
@@ -1756,7 +1983,7 @@ impl ReturnableSelect for SelectStatementCount {}
 
 impl From<SelectStatement> for SelectStatementCount {
     fn from(value: SelectStatement) -> Self {
-        Self(value)
+        Self(value, SoftDeleteScope::default())
     }
 }
 
@@ -1765,6 +1992,19 @@ mod tests {
     use super::*;
     use crate::*;
 
+    #[test]
+    fn test_count_from_select_statement() {
+        let user = Table::new("user");
+        let count = SelectStatementCount::from(
+            select_value(Field::new("count")).from(select(count!()).from(user).group_all()),
+        );
+
+        assert_eq!(
+            count.to_raw().build(),
+            "SELECT VALUE count FROM (SELECT count() FROM user GROUP ALL);"
+        );
+    }
+
     #[test]
     fn test_statement_with_alias() {
         let canadian_cities = AliasName::new("legal_age");
@@ -0,0 +1,601 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::{fmt, marker::PhantomData};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use surrealdb::{sql, Connection, Surreal};
+
+use crate::{
+    statements::{Order, Orderables, SelectStatement, SelectStatementMini},
+    Binding, BindingsList, Buildable, CanOrder, Erroneous, ErrorList, Field, Model, Parametric,
+    Runnable, SurrealOrmError, SurrealOrmResult, ValueLike,
+};
+
+/// Opaque position of a paginated select, pointing to the rows after or before a row.
+/// It is encoded from the ordering values and the id of the row, so it stays valid
+/// when rows are inserted or deleted, unlike an offset.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Cursor(String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CursorDirection {
+    After,
+    Before,
+}
+
+/// Position encoded in a cursor
+#[derive(Debug, Serialize, Deserialize)]
+struct CursorPosition {
+    direction: CursorDirection,
+    keys: Vec<CursorKey>,
+}
+
+/// Value of a pagination key in a cursor. A cursor comes from the client, so it only holds
+/// literal scalars and record ids, which can never run as an expression once bound.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CursorKey {
+    None,
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Decimal(String),
+    String(String),
+    Datetime(String),
+    Uuid(String),
+    Duration(String),
+    Thing { tb: String, id: CursorKeyId },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CursorKeyId {
+    Int(i64),
+    String(String),
+}
+
+impl TryFrom<sql::Value> for CursorKey {
+    type Error = SurrealOrmError;
+
+    fn try_from(value: sql::Value) -> Result<Self, Self::Error> {
+        let key = match value {
+            sql::Value::None => Self::None,
+            sql::Value::Null => Self::Null,
+            sql::Value::Bool(value) => Self::Bool(value),
+            sql::Value::Number(sql::Number::Int(value)) => Self::Int(value),
+            sql::Value::Number(sql::Number::Float(value)) => Self::Float(value),
+            sql::Value::Number(sql::Number::Decimal(value)) => Self::Decimal(value.to_string()),
+            sql::Value::Strand(value) => Self::String(value.as_string()),
+            sql::Value::Datetime(value) => Self::Datetime(value.0.to_rfc3339()),
+            sql::Value::Uuid(value) => Self::Uuid(value.0.to_string()),
+            sql::Value::Duration(value) => Self::Duration(value.to_raw()),
+            sql::Value::Thing(sql::Thing { tb, id }) => {
+                let id = match id {
+                    sql::Id::Number(id) => CursorKeyId::Int(id),
+                    sql::Id::String(id) => CursorKeyId::String(id),
+                    id => {
+                        return Err(SurrealOrmError::QueryBuilder(format!(
+                            "The record id {tb}:{id} cannot be a pagination key. \
+                            Only number and string ids are supported"
+                        )))
+                    }
+                };
+                Self::Thing { tb, id }
+            }
+            value => {
+                return Err(SurrealOrmError::QueryBuilder(format!(
+                    "{value} cannot be a pagination key. \
+                    Only scalar values and record ids are supported"
+                )))
+            }
+        };
+        Ok(key)
+    }
+}
+
+impl TryFrom<CursorKey> for sql::Value {
+    type Error = ();
+
+    fn try_from(key: CursorKey) -> Result<Self, Self::Error> {
+        let value = match key {
+            CursorKey::None => sql::Value::None,
+            CursorKey::Null => sql::Value::Null,
+            CursorKey::Bool(value) => value.into(),
+            CursorKey::Int(value) => value.into(),
+            CursorKey::Float(value) => value.into(),
+            CursorKey::Decimal(value) => {
+                sql::Number::Decimal(value.parse().map_err(|_| ())?).into()
+            }
+            CursorKey::String(value) => value.into(),
+            CursorKey::Datetime(value) => sql::Datetime::try_from(value.as_str())?.into(),
+            CursorKey::Uuid(value) => sql::Uuid::try_from(value.as_str())?.into(),
+            CursorKey::Duration(value) => sql::Duration::try_from(value.as_str())?.into(),
+            CursorKey::Thing { tb, id } => {
+                let id = match id {
+                    CursorKeyId::Int(id) => sql::Id::Number(id),
+                    CursorKeyId::String(id) => sql::Id::String(id),
+                };
+                sql::Thing { tb, id }.into()
+            }
+        };
+        Ok(value)
+    }
+}
+
+impl Cursor {
+    fn encode(direction: CursorDirection, keys: Vec<sql::Value>) -> SurrealOrmResult<Self> {
+        let keys = keys
+            .into_iter()
+            .map(CursorKey::try_from)
+            .collect::<SurrealOrmResult<Vec<_>>>()?;
+        let position = serde_json::to_vec(&CursorPosition { direction, keys })
+            .map_err(|e| SurrealOrmError::QueryBuilder(e.to_string()))?;
+
+        let encoded = position.iter().map(|b| format!("{b:02x}")).collect();
+        Ok(Self(encoded))
+    }
+
+    fn decode(&self) -> SurrealOrmResult<(CursorDirection, Vec<sql::Value>)> {
+        let invalid = || SurrealOrmError::InvalidCursor(self.0.clone());
+
+        let bytes = (0..self.0.len())
+            .step_by(2)
+            .map(|i| {
+                self.0
+                    .get(i..i + 2)
+                    .and_then(|b| u8::from_str_radix(b, 16).ok())
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+        let position: CursorPosition = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+
+        let keys = position
+            .keys
+            .into_iter()
+            .map(sql::Value::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid())?;
+        Ok((position.direction, keys))
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for Cursor {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Cursor {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+/// A page of a paginated select
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// Rows of the page, in the order of the pagination keys
+    pub items: Vec<T>,
+    /// Cursor of the page after this one, if any
+    pub next_cursor: Option<Cursor>,
+    /// Cursor of the page before this one, if any
+    pub prev_cursor: Option<Cursor>,
+    /// Whether there are more rows in the direction of the page i.e after the page
+    /// for a next cursor and before it for a previous cursor
+    pub has_more: bool,
+}
+
+/// Keyset (cursor) pagination of a select statement.
+/// Rows are ordered by the pagination keys and then by id, and each page is filtered
+/// to the rows after or before the cursor instead of skipping an offset.
+#[derive(Debug, Clone)]
+pub struct Paginate<T> {
+    statement: SelectStatement,
+    keys: Vec<Order>,
+    page_size: u64,
+    cursor: Option<Cursor>,
+    direction: CursorDirection,
+    // Built once so that the params of its bindings stay the same
+    page: SelectStatement,
+    errors: ErrorList,
+    output: PhantomData<fn() -> T>,
+}
+
+impl SelectStatement {
+    /// Paginates the select statement with the keyset of the ordering fields and record id.
+    /// Replaces the ordering, start and limit of the statement. The projections must include
+    /// the ordering fields and id.
+    ///
+    /// Examples
+    /// ```rust
+    /// # use sorm_query as surreal_orm;
+    /// use surreal_orm::{*, statements::select};
+    /// # let user = Table::new("user");
+    /// # let age = Field::new("age");
+    ///
+    /// let first_page = select(All).from(user).paginate::<TestUser>(age.desc(), 20);
+    /// assert_eq!(
+    ///     first_page.to_raw().build(),
+    ///     "SELECT * FROM user ORDER BY age DESC, id ASC LIMIT 21;"
+    /// );
+    /// ```
+    ///
+    /// ```rust, ignore
+    /// let page = select(All)
+    ///     .from(User::table())
+    ///     .paginate::<User>(user.createdAt.desc(), 20)
+    ///     .cursor(cursor_from_client)
+    ///     .get_page(db.clone())
+    ///     .await?;
+    /// ```
+    pub fn paginate<T>(self, keys: impl Into<Orderables>, page_size: u64) -> Paginate<T> {
+        let keys: Orderables = keys.into();
+        let mut keys: Vec<Order> = keys.into();
+        let id = Field::new("id");
+
+        if !keys.iter().any(|k| k.get_field().build() == id.build()) {
+            keys.push(id.asc());
+        }
+
+        Paginate {
+            page: self.clone(),
+            statement: self,
+            keys,
+            page_size,
+            cursor: None,
+            direction: CursorDirection::After,
+            errors: vec![],
+            output: PhantomData,
+        }
+        .with_page()
+    }
+}
+
+impl<T: Model> SelectStatementMini<T> {
+    /// Paginates the select statement with the keyset of the ordering fields and record id.
+    /// See [`SelectStatement::paginate`].
+    pub fn paginate(self, keys: impl Into<Orderables>, page_size: u64) -> Paginate<T> {
+        self.into_inner().paginate(keys, page_size)
+    }
+}
+
+impl<T> Paginate<T> {
+    /// Starts the page from the next or previous cursor of another page.
+    /// Starts from the first page if there is no cursor.
+    pub fn cursor(mut self, cursor: impl Into<Option<Cursor>>) -> Self {
+        self.cursor = cursor.into();
+        self.with_page()
+    }
+
+    /// Builds the select statement of the page
+    fn with_page(mut self) -> Self {
+        match self.page_statement() {
+            Ok((direction, page)) => {
+                self.direction = direction;
+                self.errors = page.get_errors();
+                self.page = page;
+            }
+            Err(e) => self.errors = vec![e.to_string()],
+        }
+        self
+    }
+
+    fn page_statement(&self) -> SurrealOrmResult<(CursorDirection, SelectStatement)> {
+        let (direction, values) = match &self.cursor {
+            Some(cursor) => {
+                let (direction, values) = cursor.decode()?;
+                if values.len() != self.keys.len() {
+                    return Err(SurrealOrmError::InvalidCursor(cursor.to_string()));
+                }
+                (direction, Some(values))
+            }
+            None => (CursorDirection::After, None),
+        };
+
+        // Walking backwards orders by the reversed keys and the rows are reversed afterwards
+        let keys = match direction {
+            CursorDirection::After => self.keys.clone(),
+            CursorDirection::Before => self.keys.iter().cloned().map(Order::reversed).collect(),
+        };
+        let condition = values.map(|values| Self::keyset_condition(&keys, values));
+
+        let page = self
+            .statement
+            .clone()
+            .keyset(condition, keys, self.page_size + 1);
+
+        Ok((direction, page))
+    }
+
+    /// `(k1 > v1) OR (k1 = v1 AND k2 > v2) OR ...` with `<` for descending keys
+    fn keyset_condition(keys: &[Order], values: Vec<sql::Value>) -> ValueLike {
+        let mut bindings = BindingsList::new();
+        let params = values
+            .into_iter()
+            .map(|value| {
+                let binding = Binding::new(value);
+                let param = binding.get_param_dollarised();
+                bindings.push(binding);
+                param
+            })
+            .collect::<Vec<_>>();
+
+        let fields = keys
+            .iter()
+            .map(|key| {
+                bindings.extend(key.get_field().get_bindings());
+                key.get_field().build()
+            })
+            .collect::<Vec<_>>();
+
+        let string = keys
+            .iter()
+            .enumerate()
+            .map(|(i, key)| {
+                let operator = if key.is_desc() { "<" } else { ">" };
+                let equal_before = (0..i).map(|j| format!("{} = {}", fields[j], params[j]));
+                let condition = equal_before
+                    .chain(std::iter::once(format!(
+                        "{} {operator} {}",
+                        fields[i], params[i]
+                    )))
+                    .collect::<Vec<_>>()
+                    .join(" AND ");
+                format!("({condition})")
+            })
+            .collect::<Vec<_>>()
+            .join(" OR ");
+
+        ValueLike {
+            string,
+            bindings,
+            errors: vec![],
+        }
+    }
+
+    fn cursor_of(&self, direction: CursorDirection, row: &sql::Value) -> SurrealOrmResult<Cursor> {
+        let keys = self
+            .keys
+            .iter()
+            .map(|key| {
+                let field = key.get_field().build();
+                let idiom = sql::idiom(&field)
+                    .map_err(|e| SurrealOrmError::QueryBuilder(format!("{field}. {e}")))?;
+                Ok(row.pick(&idiom))
+            })
+            .collect::<SurrealOrmResult<Vec<_>>>()?;
+
+        Cursor::encode(direction, keys)
+    }
+}
+
+impl<T: DeserializeOwned> Paginate<T> {
+    /// Runs the select statement of the page and returns its rows and cursors
+    pub async fn get_page(&self, db: Surreal<impl Connection>) -> SurrealOrmResult<Page<T>> {
+        if !self.errors.is_empty() {
+            // Surfaces an invalid cursor as such
+            self.page_statement()?;
+            return Err(SurrealOrmError::QueryBuilder(self.errors.join(". \n")));
+        }
+
+        let direction = self.direction;
+        let mut response = self.page.run(db).await?;
+        let mut rows = match response
+            .take::<sql::Value>(0)
+            .map_err(SurrealOrmError::Deserialization)?
+        {
+            sql::Value::Array(rows) => rows.0,
+            sql::Value::None | sql::Value::Null => vec![],
+            row => vec![row],
+        };
+
+        let has_more = rows.len() as u64 > self.page_size;
+        rows.truncate(self.page_size as usize);
+        if direction == CursorDirection::Before {
+            rows.reverse();
+        }
+
+        // A page reached from a cursor always has rows on the side it came from
+        let (has_next, has_prev) = match direction {
+            CursorDirection::After => (has_more, self.cursor.is_some()),
+            CursorDirection::Before => (true, has_more),
+        };
+        let next_cursor = match rows.last() {
+            Some(last) if has_next => Some(self.cursor_of(CursorDirection::After, last)?),
+            _ => None,
+        };
+        let prev_cursor = match rows.first() {
+            Some(first) if has_prev => Some(self.cursor_of(CursorDirection::Before, first)?),
+            _ => None,
+        };
+
+        let items = rows
+            .into_iter()
+            .map(|row| sql::from_value(row).map_err(|e| SurrealOrmError::Deserialization(e.into())))
+            .collect::<SurrealOrmResult<Vec<T>>>()?;
+
+        Ok(Page {
+            items,
+            next_cursor,
+            prev_cursor,
+            has_more,
+        })
+    }
+}
+
+impl<T> Erroneous for Paginate<T> {
+    fn get_errors(&self) -> ErrorList {
+        self.errors.to_vec()
+    }
+}
+
+impl<T> Parametric for Paginate<T> {
+    fn get_bindings(&self) -> BindingsList {
+        self.page.get_bindings()
+    }
+}
+
+impl<T> Buildable for Paginate<T> {
+    fn build(&self) -> String {
+        self.page.build()
+    }
+}
+
+impl<T> fmt::Display for Paginate<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{statements::select, All, Operatable, Table, ToRaw};
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let keys = vec![
+            sql::Value::from(27),
+            sql::Value::from("Oyelowo"),
+            sql::Thing::from(("user".to_string(), "oyelowo".to_string())).into(),
+        ];
+        let cursor = Cursor::encode(CursorDirection::Before, keys.clone()).unwrap();
+
+        assert!(cursor.to_string().chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(cursor.decode().unwrap(), (CursorDirection::Before, keys));
+        assert!(Cursor::from("not a cursor").decode().is_err());
+
+        let keys = vec![
+            sql::Value::None,
+            sql::Value::Null,
+            sql::Value::from(true),
+            sql::Value::from(2.5),
+            sql::Number::Decimal(10.into()).into(),
+            sql::Datetime::try_from("2023-07-21T10:30:00.123Z")
+                .unwrap()
+                .into(),
+            sql::Uuid::try_from("018a6680-bef9-701b-9025-e1754f296a0f")
+                .unwrap()
+                .into(),
+            sql::Duration::from_secs(90).into(),
+            sql::Thing::from(("user".to_string(), sql::Id::Number(1))).into(),
+        ];
+        let cursor = Cursor::encode(CursorDirection::After, keys.clone()).unwrap();
+        assert_eq!(cursor.decode().unwrap(), (CursorDirection::After, keys));
+    }
+
+    #[test]
+    fn test_cursor_rejects_expressions() {
+        let hex = |position: &str| {
+            Cursor::from(
+                position
+                    .bytes()
+                    .map(|b| format!("{b:02x}"))
+                    .collect::<String>(),
+            )
+        };
+        let expressions = [
+            r#"["after", [(DELETE user)]]"#,
+            r#"{"direction": "after", "keys": ["(DELETE user)"]}"#,
+            r#"{"direction": "after", "keys": [{"datetime": "time::now()"}]}"#,
+            r#"{"direction": "after", "keys": [{"duration": "(DELETE user)"}]}"#,
+            r#"{"direction": "after", "keys": [{"subquery": "DELETE user"}]}"#,
+            r#"{"direction": "after", "keys": [{"thing": {"tb": "user", "id": {"array": ["$auth"]}}}]}"#,
+        ];
+
+        for expression in expressions {
+            let cursor = hex(expression);
+            assert!(matches!(
+                cursor.decode(),
+                Err(SurrealOrmError::InvalidCursor(c)) if c == cursor.to_string()
+            ));
+        }
+
+        // A string that looks like an expression stays a string
+        let cursor = hex(
+            r#"{"direction": "after", "keys": [{"string": "(DELETE user)"}, {"thing": {"tb": "user", "id": {"string": "oyelowo"}}}]}"#,
+        );
+        assert_eq!(
+            cursor.decode().unwrap(),
+            (
+                CursorDirection::After,
+                vec![
+                    sql::Value::from("(DELETE user)"),
+                    sql::Thing::from(("user".to_string(), "oyelowo".to_string())).into(),
+                ]
+            )
+        );
+        let paginated = select(All)
+            .from(Table::new("user"))
+            .paginate::<sql::Value>(Field::new("name").asc(), 10)
+            .cursor(cursor);
+        assert_eq!(
+            paginated.to_raw().build(),
+            "SELECT * FROM user WHERE (name > '(DELETE user)') \
+                OR (name = '(DELETE user)' AND id > user:oyelowo) \
+                ORDER BY name ASC, id ASC LIMIT 11;"
+        );
+    }
+
+    #[test]
+    fn test_cursor_rejects_non_literal_keys() {
+        let keys = vec![sql::Value::Subquery(Box::new(sql::Subquery::Value(
+            sql::Value::from(1),
+        )))];
+        assert!(matches!(
+            Cursor::encode(CursorDirection::After, keys),
+            Err(SurrealOrmError::QueryBuilder(_))
+        ));
+    }
+
+    #[test]
+    fn test_paginate_keyset_condition() {
+        let user = Table::new("user");
+        let age = Field::new("age");
+        let name = Field::new("name");
+        let keys = vec![
+            sql::Value::from(27),
+            sql::Value::from("Oyelowo"),
+            sql::Thing::from(("user".to_string(), "oyelowo".to_string())).into(),
+        ];
+
+        let next = select(All)
+            .from(user.clone())
+            .where_(age.gte(18))
+            .paginate::<sql::Value>(vec![age.desc(), name.asc()], 10)
+            .cursor(Cursor::encode(CursorDirection::After, keys.clone()).unwrap());
+
+        assert_eq!(
+            next.to_raw().build(),
+            "SELECT * FROM user WHERE (age >= 18) AND ((age < 27) OR (age = 27 AND name > 'Oyelowo') \
+                OR (age = 27 AND name = 'Oyelowo' AND id > user:oyelowo)) \
+                ORDER BY age DESC, name ASC, id ASC LIMIT 11;"
+        );
+
+        let prev = select(All)
+            .from(user)
+            .paginate::<sql::Value>(vec![age.desc(), name.asc()], 10)
+            .cursor(Cursor::encode(CursorDirection::Before, keys).unwrap());
+
+        assert_eq!(
+            prev.to_raw().build(),
+            "SELECT * FROM user WHERE (age > 27) OR (age = 27 AND name < 'Oyelowo') \
+                OR (age = 27 AND name = 'Oyelowo' AND id < user:oyelowo) \
+                ORDER BY age ASC, name DESC, id DESC LIMIT 11;"
+        );
+    }
+}
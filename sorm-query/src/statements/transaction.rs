@@ -178,6 +178,13 @@ pub struct TransactionCompletion {
     data: TransactionData,
 }
 
+impl TransactionCompletion {
+    /// Number of statements between BEGIN and COMMIT/CANCEL
+    pub(crate) fn queries_count(&self) -> usize {
+        self.data.queries.len()
+    }
+}
+
 impl Parametric for TransactionCompletion {
     fn get_bindings(&self) -> BindingsList {
         self.data.bindings.to_vec()
@@ -23,12 +23,31 @@ use serde::{de::DeserializeOwned, Serialize};
 use surrealdb::sql;
 
 use crate::{
-    derive_binding_and_errors_from_value, Binding, BindingsList, Buildable, Conditional,
-    DurationLike, Erroneous, ErrorList, Filter, Model, Parametric, PatchOp, Queryable, ReturnType,
-    ReturnableDefault, ReturnableStandard, Setter, SurrealId, SurrealSimpleId, SurrealUlid,
-    SurrealUuid, ToRaw,
+    cond, derive_binding_and_errors_from_value, patch_replace, Binding, BindingsList, Buildable,
+    Conditional, DurationLike, Erroneous, ErrorList, Field, Filter, Model, Operatable, Parametric,
+    PatchOp, Queryable, ReturnType, ReturnableDefault, ReturnableStandard, Setter, SurrealId,
+    SurrealOrmError, SurrealOrmResult, SurrealSimpleId, SurrealUlid, SurrealUuid, ToRaw, NONE,
 };
 
+/// Thrown by the version check of an update when the record is not at the expected version,
+/// followed by the record and the version so that the error can be read from any response
+const STALE_WRITE: &str = "stale write of";
+
+/// Reads the stale write thrown by the version check of an update, wherever the update ran
+pub(crate) fn as_stale_write(error: &surrealdb::Error) -> Option<SurrealOrmError> {
+    let surrealdb::Error::Db(surrealdb::error::Db::Thrown(message)) = error else {
+        return None;
+    };
+    let (id, expected) = message
+        .strip_prefix(STALE_WRITE)?
+        .trim_start()
+        .rsplit_once(" at version ")?;
+    Some(SurrealOrmError::StaleWrite(
+        id.to_string(),
+        expected.parse().ok()?,
+    ))
+}
+
 /// Creates a new UPDATE statement.
 /// The UPDATE statement can be used to update or modify records in the database.
 ///
@@ -92,6 +111,7 @@ where
     let targettables: TargettablesForUpdate = targettables.into();
     let mut bindings = vec![];
     let mut errors = vec![];
+    let mut version = None;
     let param = match targettables {
         TargettablesForUpdate::Table(table) => {
             let table = table.to_string();
@@ -111,6 +131,12 @@ where
                     "id - {id} does not belong to {table_name} table from the surreal model struct provided"
                 ));
             }
+            version = T::version_field().map(|field| RecordVersion {
+                id: id.clone(),
+                field,
+                expected: None,
+                param: None,
+            });
             let binding = Binding::new(id);
             let param = binding.get_param_dollarised();
             bindings.push(binding);
@@ -130,6 +156,7 @@ where
         return_type: None,
         timeout: None,
         parallel: false,
+        version,
         bindings,
         errors,
         __model_return_type: PhantomData,
@@ -222,10 +249,40 @@ where
     bindings: BindingsList,
     errors: ErrorList,
     parallel: bool,
+    version: Option<RecordVersion>,
     __model_return_type: PhantomData<T>,
 }
 
-impl<T> Queryable for UpdateStatement<T> where T: Serialize + DeserializeOwned + Model {}
+/// Version of a record of a model with a version field, checked when updating the record
+#[derive(Debug, Clone)]
+struct RecordVersion {
+    id: sql::Thing,
+    field: Field,
+    expected: Option<u64>,
+    /// Param the expected version is bound to
+    param: Option<String>,
+}
+
+impl<T> Queryable for UpdateStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn check_response(
+        &self,
+        response: surrealdb::Response,
+    ) -> SurrealOrmResult<surrealdb::Response> {
+        let Some(RecordVersion {
+            expected: Some(_), ..
+        }) = &self.0.version
+        else {
+            return Ok(response);
+        };
+
+        response
+            .check()
+            .map_err(|e| as_stale_write(&e).unwrap_or(SurrealOrmError::QueryRun(e)))
+    }
+}
 impl<T> Erroneous for UpdateStatement<T>
 where
     T: Serialize + DeserializeOwned + Model,
@@ -363,20 +420,128 @@ impl<T> UpdateStatementInit<T>
 where
     T: Serialize + DeserializeOwned + Model,
 {
+    /// Sets the version the record is expected to be at for models with a `#[sorm(version)]`
+    /// field. Needed for `set` and `patch`, and for `merge` without the version field.
+    /// `content` and `merge` otherwise take it from the version field of the data.
+    /// The update fails with `SurrealOrmError::StaleWrite` if the record is at another version.
+    /// Without an expected version, the record is written unchecked and its version is left
+    /// as is.
+    pub fn expect_version(mut self, version: u64) -> Self {
+        match &mut self.version {
+            Some(record_version) => record_version.expected = Some(version),
+            None => self.errors.push(format!(
+                "Only updates of a specific record of a model with a version field can expect a version. {} has no version field or the update is not of a specific record",
+                T::table()
+            )),
+        }
+        self
+    }
+
+    /// The update is written as is without checking or incrementing the version.
+    pub(crate) fn without_version_check(mut self) -> Self {
+        self.version = None;
+        self
+    }
+
+    /// Marks the records as deleted at the current time rather than deleting them, for models
+    /// of `#[sorm(soft_delete)]` tables. Records already deleted keep when they were deleted.
+    pub(crate) fn soft_delete(self, filter: Option<Filter>) -> UpdateStatement<T> {
+        // Updating a missing record by id would otherwise create it
+        self.set_deleted_at("time::now()", filter, |deleted_at| {
+            cond(Field::new("id").is_not(NONE)).and(deleted_at.is(NONE))
+        })
+    }
+
+    /// Unmarks soft deleted records, for models of `#[sorm(soft_delete)]` tables.
+    pub(crate) fn restore(self, filter: Option<Filter>) -> UpdateStatement<T> {
+        self.set_deleted_at("NONE", filter, |deleted_at| cond(deleted_at.is_not(NONE)))
+    }
+
+    fn set_deleted_at(
+        mut self,
+        deleted_at_value: &str,
+        filter: Option<Filter>,
+        scope: impl FnOnce(&Field) -> Filter,
+    ) -> UpdateStatement<T> {
+        let Some(deleted_at) = T::soft_delete_field() else {
+            self.errors.push(format!(
+                "{} is not soft deleted. Add #[sorm(soft_delete)] to its model to soft delete or restore its records",
+                T::table()
+            ));
+            return self.into();
+        };
+
+        self.set.push(format!("{deleted_at} = {deleted_at_value}"));
+        let condition = match filter {
+            Some(filter) => scope(&deleted_at).and(filter),
+            None => scope(&deleted_at),
+        };
+        UpdateStatement::from(self).where_(condition)
+    }
+
+    /// Sets the version field of the data to the version after the expected one.
+    /// The expected version is read from the data unless set with `expect_version`.
+    /// Returns None if the data is written as is.
+    fn with_next_version(&mut self, data: &impl Serialize) -> Option<sql::Value> {
+        let record_version = self.version.as_mut()?;
+        let mut data = sql::to_value(data).ok()?;
+        let path = sql::idiom(&record_version.field.build()).ok()?;
+
+        let expected = record_version.expected.or(match data.pick(&path) {
+            sql::Value::Number(version) => Some(version.as_int() as u64),
+            _ => None,
+        })?;
+        record_version.expected = Some(expected);
+        data.put(&path, sql::Value::from(expected + 1));
+
+        Some(data)
+    }
+
     /// Caution! Overrides all data even with default. Use with care. You may prefer `merge` with Updater instead e.g `UserUpdater`.
     /// Specify the full record data using the CONTENT keyword. The content must be serializable
     /// and implement Model trait.
     pub fn content(mut self, content: T) -> UpdateStatement<T> {
-        let (binding, errors) = derive_binding_and_errors_from_value(&content);
+        let (binding, errors) = match self.with_next_version(&content) {
+            Some(content) => derive_binding_and_errors_from_value(&content),
+            None => derive_binding_and_errors_from_value(&content),
+        };
         self.content = Some(binding.get_param_dollarised());
         self.bindings.push(binding);
         self.errors.extend(errors);
         self.into()
     }
 
+    /// Replaces the record like `content`, except for the given field which keeps the value
+    /// stored in the database e.g when a soft deleted record was deleted.
+    pub(crate) fn content_keeping(mut self, content: T, kept: &Field) -> UpdateStatement<T> {
+        let data = match self
+            .with_next_version(&content)
+            .map_or_else(|| sql::to_value(&content), Ok)
+        {
+            Ok(sql::Value::Object(data)) => data,
+            _ => return self.content(content),
+        };
+
+        let kept = kept.build();
+        let mut fields = sql::Object::default();
+        for (key, value) in data.0.into_iter().filter(|(key, _)| *key != kept) {
+            let binding = Binding::new(value);
+            fields.insert(key, sql::Param::from(binding.get_param().as_str()).into());
+            self.bindings.push(binding);
+        }
+        // The field is read from the record being updated
+        fields.insert(kept.clone(), sql::Idiom::from(kept).into());
+
+        self.content = Some(sql::Value::from(fields).to_string());
+        self.into()
+    }
+
     /// merge-update only specific fields by using the MERGE keyword and specifying only the fields which are to be updated.
     pub fn merge(mut self, merge: impl Serialize) -> UpdateStatement<T> {
-        let (binding, errors) = derive_binding_and_errors_from_value(&merge);
+        let (binding, errors) = match self.with_next_version(&merge) {
+            Some(merge) => derive_binding_and_errors_from_value(&merge),
+            None => derive_binding_and_errors_from_value(&merge),
+        };
         self.merge = Some(binding.get_param_dollarised());
         self.bindings.push(binding);
         self.errors.extend(errors);
@@ -387,7 +552,10 @@ where
     /// Fully replaces weapon table with completely new object and data. This will remove all fields
     /// that are not present in the new object. This is a destructive operation.
     pub fn replace(mut self, replacement: impl Serialize) -> UpdateStatement<T> {
-        let (binding, errors) = derive_binding_and_errors_from_value(&replacement);
+        let (binding, errors) = match self.with_next_version(&replacement) {
+            Some(replacement) => derive_binding_and_errors_from_value(&replacement),
+            None => derive_binding_and_errors_from_value(&replacement),
+        };
         self.replace = Some(binding.get_param_dollarised());
         self.bindings.push(binding);
         self.errors.extend(errors);
@@ -441,6 +609,14 @@ where
 
         self.bindings.extend(bindings);
         self.set.extend(settable);
+        if let Some(RecordVersion {
+            field,
+            expected: Some(_),
+            ..
+        }) = &self.version
+        {
+            self.set.push(format!("{field} += 1"));
+        }
         self.into()
     }
 
@@ -461,7 +637,15 @@ where
     /// // regex search and replace
     /// name.patch_change("@@ -1,4 +1,4 @@\n te\n-s\n+x\n t\n");
     pub fn patch(mut self, patch_op: impl Into<Vec<PatchOp>>) -> UpdateStatement<T> {
-        let patch_op: Vec<PatchOp> = patch_op.into();
+        let mut patch_op: Vec<PatchOp> = patch_op.into();
+        if let Some(RecordVersion {
+            field,
+            expected: Some(expected),
+            ..
+        }) = &self.version
+        {
+            patch_op.push(patch_replace(field, expected + 1));
+        }
         for patch_op in patch_op {
             self.bindings.extend(patch_op.get_bindings());
             self.errors.extend(patch_op.get_errors());
@@ -473,6 +657,7 @@ where
 }
 
 /// A builder for update statements.
+#[derive(Debug, Clone)]
 pub struct UpdateStatement<T>(UpdateStatementInit<T>)
 where
     T: Serialize + DeserializeOwned + Model;
@@ -481,7 +666,17 @@ impl<T> From<UpdateStatementInit<T>> for UpdateStatement<T>
 where
     T: Serialize + DeserializeOwned + Model,
 {
-    fn from(value: UpdateStatementInit<T>) -> Self {
+    fn from(mut value: UpdateStatementInit<T>) -> Self {
+        if let Some(RecordVersion {
+            expected: Some(expected),
+            param,
+            ..
+        }) = &mut value.version
+        {
+            let binding = Binding::new(*expected);
+            *param = Some(binding.get_param_dollarised());
+            value.bindings.push(binding);
+        }
         Self(value)
     }
 }
@@ -618,8 +813,30 @@ where
             query = format!("{query} PATCH [{patch_vec}]");
         }
 
-        if let Some(condition) = &statement.where_ {
-            query = format!("{query} WHERE {condition}");
+        // The record is only updated if it is still at the expected version or does not exist
+        // yet. The check is part of the update so that no other write can come in between.
+        let version_check = match &statement.version {
+            Some(RecordVersion {
+                id,
+                field,
+                expected: Some(expected),
+                param: Some(param),
+            }) => {
+                let stale_write =
+                    sql::Value::from(format!("{STALE_WRITE} {id} at version {expected}"));
+                Some(format!(
+                    "(IF {field} = {param} OR id = NONE {{ true }} ELSE {{ THROW {stale_write} }})"
+                ))
+            }
+            _ => None,
+        };
+        match (&statement.where_, version_check) {
+            (Some(condition), Some(version_check)) => {
+                query = format!("{query} WHERE ({condition}) AND {version_check}");
+            }
+            (Some(condition), None) => query = format!("{query} WHERE {condition}"),
+            (None, Some(version_check)) => query = format!("{query} WHERE {version_check}"),
+            (None, None) => {}
         }
 
         if let Some(return_type) = &statement.return_type {
@@ -0,0 +1,310 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+// Statement syntax
+// BEGIN TRANSACTION;
+// IF (SELECT VALUE id FROM @conflict_target [ WHERE @conflict_fields ]) THEN
+// 	(UPDATE @conflict_target [ REPLACE | MERGE ] @value [ WHERE @conflict_fields ])
+// ELSE
+// 	(UPDATE @id REPLACE @record)
+// END;
+// COMMIT TRANSACTION;
+
+use std::marker::PhantomData;
+
+use serde::{de::DeserializeOwned, Serialize};
+use surrealdb::sql;
+
+use crate::{
+    statements::{
+        begin_transaction, define_index::Fields, if_, select_value, update, IfElseStatement,
+    },
+    BindingsList, Buildable, Erroneous, ErrorList, Field, Filter, Model, Operatable, Parametric,
+    PartialUpdater, Queryable, ReturnType, ReturnableDefault, ReturnableStandard,
+};
+
+/// What an upserted record conflicts with.
+#[derive(Debug, Clone)]
+enum Conflict {
+    /// An existing record with the same id
+    Id,
+    /// An existing record with the same values on the conflict fields
+    Fields(Filter),
+}
+
+/// How the existing record is updated on conflict.
+#[derive(Debug, Clone)]
+enum Strategy {
+    Replace,
+    Merge,
+    MergePartial(sql::Value),
+}
+
+/// Creates the record or updates the existing record it conflicts with.
+/// Created with `SurrealCrud::upsert` or `SurrealCrud::upsert_on`.
+/// The lookup of the conflicting record and the write run in one transaction, which fails
+/// rather than writing twice when a concurrent upsert commits first. Define a unique index on
+/// the conflict fields so that concurrent upserts on fields also conflict with each other.
+/// Returns the final record.
+///
+/// # Examples
+///
+/// ```rust, ignore
+/// // Replaces the weapon with the same id if any
+/// let weapon = weapon.upsert().get_one(db.clone()).await?;
+///
+/// // Merges into the weapon with the same name if any
+/// let weapon = weapon
+///     .upsert_on(Weapon::schema().name)
+///     .merge()
+///     .get_one(db.clone())
+///     .await?;
+///
+/// // Only updates the strength of the weapon with the same name if any
+/// let weapon = weapon
+///     .upsert_on(Weapon::schema().name)
+///     .merge_partial(Weapon::partial_builder().strength(20.0).build())
+///     .get_one(db.clone())
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    id: sql::Thing,
+    record: sql::Value,
+    conflict: Conflict,
+    strategy: Strategy,
+    return_type: Option<ReturnType>,
+    query: String,
+    bindings: BindingsList,
+    errors: ErrorList,
+    __model_return_type: PhantomData<T>,
+}
+
+impl<T> UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    pub(crate) fn on_id(record: T) -> Self {
+        Self::new(record, None)
+    }
+
+    pub(crate) fn on_fields(record: T, fields: impl Into<Fields>) -> Self {
+        let fields = match fields.into() {
+            Fields::Field(field) => vec![field],
+            Fields::Fields(fields) => fields,
+        };
+        Self::new(record, Some(fields))
+    }
+
+    fn new(record: T, conflict_fields: Option<Vec<Field>>) -> Self {
+        let id = record.get_id_as_thing();
+        let mut errors = vec![];
+        let record = sql::to_value(&record).unwrap_or_else(|e| {
+            errors.push(format!("Error: Unable to serialise value. \n{}", e));
+            sql::Value::None
+        });
+
+        let conflict = match conflict_fields {
+            None => Conflict::Id,
+            Some(fields) if fields.is_empty() => {
+                errors.push(format!(
+                    "No conflict field provided for upserting into {}",
+                    T::table()
+                ));
+                Conflict::Id
+            }
+            Some(fields) => {
+                let model_fields = T::get_serializable_fields()
+                    .iter()
+                    .map(|field| field.build())
+                    .collect::<Vec<_>>();
+                let filter = fields
+                    .iter()
+                    .map(|field| {
+                        let path = field.build();
+                        // Nested conflict fields are checked by the field they are part of
+                        let root = path.split(['.', '[']).next().unwrap_or_default();
+                        if !model_fields.iter().any(|field| field == root) {
+                            errors.push(format!(
+                                "Conflict field {field} is not a field of {}",
+                                T::table()
+                            ));
+                        }
+                        // An unset optional field conflicts with the records where it is unset
+                        let value = sql::idiom(&path)
+                            .map(|path| record.pick(&path))
+                            .unwrap_or_default();
+                        Filter::new(field.equal(value))
+                    })
+                    .reduce(|filter, matches| filter.and(matches))
+                    .unwrap_or_default();
+                Conflict::Fields(filter)
+            }
+        };
+
+        Self {
+            id,
+            record,
+            conflict,
+            strategy: Strategy::Replace,
+            return_type: None,
+            query: String::new(),
+            bindings: vec![],
+            errors,
+            __model_return_type: PhantomData,
+        }
+        .compose()
+    }
+
+    /// Replaces the whole content of the existing record. This is the default.
+    pub fn replace(mut self) -> Self {
+        self.strategy = Strategy::Replace;
+        self.compose()
+    }
+
+    /// Merges the fields of the record into the existing record. Fields of the existing
+    /// record that are not part of the model are left as they are.
+    pub fn merge(mut self) -> Self {
+        self.strategy = Strategy::Merge;
+        self.compose()
+    }
+
+    /// Merges only the fields set on the partial into the existing record. The whole record is
+    /// still created if there is no conflict.
+    pub fn merge_partial(mut self, partial: T::StructPartial) -> Self
+    where
+        T: PartialUpdater,
+        T::StructPartial: Serialize,
+    {
+        match sql::to_value(&partial) {
+            Ok(partial) => self.strategy = Strategy::MergePartial(partial),
+            Err(e) => self
+                .errors
+                .push(format!("Error: Unable to serialise value. \n{}", e)),
+        };
+        self.compose()
+    }
+
+    /// The conflicting record is updated in place, so its id must not be overwritten.
+    fn without_id(value: &sql::Value) -> sql::Value {
+        let mut value = value.clone();
+        if let sql::Value::Object(object) = &mut value {
+            object.remove("id");
+        }
+        value
+    }
+
+    /// Rebuilds the query whenever the strategy or return type changes, so that the query
+    /// and its bindings always match.
+    fn compose(mut self) -> Self {
+        let on_conflict = match &self.conflict {
+            Conflict::Id => update::<T>(&self.id).without_version_check(),
+            Conflict::Fields(_) => update::<T>(T::table()),
+        };
+        let on_conflict = match &self.strategy {
+            Strategy::Replace => on_conflict.replace(Self::without_id(&self.record)),
+            Strategy::Merge => on_conflict.merge(Self::without_id(&self.record)),
+            Strategy::MergePartial(partial) => on_conflict.merge(Self::without_id(partial)),
+        };
+        let on_insert = update::<T>(&self.id)
+            .without_version_check()
+            .replace(self.record.clone());
+
+        let (conflicting, on_conflict) = match &self.conflict {
+            Conflict::Id => (
+                select_value(Field::new("id")).from(self.id.clone()),
+                on_conflict,
+            ),
+            Conflict::Fields(filter) => (
+                select_value(Field::new("id"))
+                    .from(T::table())
+                    .where_(filter.clone()),
+                on_conflict.where_(filter.clone()),
+            ),
+        };
+        let (on_conflict, on_insert) = match &self.return_type {
+            Some(return_type) => (
+                on_conflict.return_type(return_type.clone()),
+                on_insert.return_type(return_type.clone()),
+            ),
+            None => (on_conflict, on_insert),
+        };
+        let statement: IfElseStatement = if_(inlined(conflicting))
+            .then(inlined(on_conflict))
+            .else_(inlined(on_insert))
+            .end();
+        let statement = begin_transaction().query(statement).commit_transaction();
+        self.query = statement.build();
+        self.bindings = statement.get_bindings();
+        self
+    }
+}
+
+/// Inlines a statement as a parenthesised subquery. Writes only run within an IF statement
+/// when they are inlined rather than bound as parameters.
+fn inlined(statement: impl Queryable) -> Field {
+    Field::new(format!("({})", statement.build().trim_end_matches(';')))
+        .with_bindings(statement.get_bindings())
+        .with_errors(statement.get_errors())
+}
+
+impl<T> Queryable for UpsertStatement<T> where T: Serialize + DeserializeOwned + Model {}
+
+impl<T> Erroneous for UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn get_errors(&self) -> ErrorList {
+        self.errors.to_vec()
+    }
+}
+
+impl<T> Parametric for UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn get_bindings(&self) -> BindingsList {
+        self.bindings.to_vec()
+    }
+}
+
+impl<T> Buildable for UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn build(&self) -> String {
+        self.query.to_string()
+    }
+}
+
+impl<T> std::fmt::Display for UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.build())
+    }
+}
+
+impl<T> ReturnableDefault<T> for UpsertStatement<T> where T: Serialize + DeserializeOwned + Model {}
+
+impl<T> ReturnableStandard<T> for UpsertStatement<T>
+where
+    T: Serialize + DeserializeOwned + Model + Send + Sync,
+{
+    fn set_return_type(mut self, return_type: ReturnType) -> Self {
+        self.return_type = Some(return_type);
+        self.compose()
+    }
+
+    fn get_return_type(&self) -> ReturnType {
+        self.return_type.clone().unwrap_or(ReturnType::None)
+    }
+}
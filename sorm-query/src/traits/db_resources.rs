@@ -11,6 +11,16 @@ pub trait DbResources {
         HashMap::default()
     }
 
+    /// Tables whose models opted into destructive migrations with `#[sorm(allow_destructive)]`
+    fn tables_allowing_destructive_changes(&self) -> Vec<Table> {
+        vec![]
+    }
+
+    /// Old names of tables renamed with `#[sorm(old_name = "old_table")]`, keyed by the current name
+    fn tables_old_names(&self) -> HashMap<Table, Table> {
+        HashMap::default()
+    }
+
     fn analyzers(&self) -> Vec<Raw> {
         vec![]
     }
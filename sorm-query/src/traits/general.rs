@@ -5,7 +5,7 @@
  * Licensed under the MIT license
  */
 
-use crate::Field;
+use crate::{Field, SurrealOrmResult};
 
 use super::{Erroneous, Parametric};
 
@@ -43,7 +43,20 @@ where
 }
 
 /// Used for statements
-pub trait Queryable: Parametric + Buildable + Erroneous {}
+pub trait Queryable: Parametric + Buildable + Erroneous {
+    /// Checks the response of the statement for failures specific to the statement
+    /// e.g a stale write of a versioned record. Returns the response as is by default.
+    /// It lives here rather than on the statement because `Runnable` is implemented for every
+    /// `Queryable`, so this is the only place a statement can reach its own response before
+    /// `run` returns it. Only `UpdateStatement` overrides it.
+    #[doc(hidden)]
+    fn check_response(
+        &self,
+        response: surrealdb::Response,
+    ) -> SurrealOrmResult<surrealdb::Response> {
+        Ok(response)
+    }
+}
 
 /// Used for filters
 pub trait Conditional: Parametric + Buildable + Erroneous {
@@ -9,22 +9,28 @@ pub(crate) mod binding;
 pub(crate) mod db_resources;
 pub(crate) mod general;
 pub(crate) mod model;
+pub(crate) mod node_union;
 pub(crate) mod operation;
 pub(crate) mod patch_op;
 pub(crate) mod pickable;
 pub(crate) mod raw;
 pub(crate) mod setter;
 pub(crate) mod statements;
+pub(crate) mod surreal_enum;
+pub(crate) mod surreal_field_type;
 pub(crate) mod table_resources;
 
 pub use binding::*;
 pub use db_resources::*;
 pub use general::*;
 pub use model::*;
+pub use node_union::*;
 pub use operation::*;
 pub use patch_op::*;
 pub use pickable::*;
 pub use raw::*;
 pub use setter::*;
 pub use statements::*;
+pub use surreal_enum::*;
+pub use surreal_field_type::*;
 pub use table_resources::*;
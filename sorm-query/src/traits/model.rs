@@ -9,14 +9,15 @@ use crate::{
     count,
     statements::{
         create::{create, CreateStatement},
+        define_index::Fields,
         delete::{delete, DeleteStatementMini},
         select::{select, SelectStatementCount},
-        select_value,
         update::{update, UpdateStatement},
+        upsert::UpsertStatement,
         SelectStatementMini,
     },
-    Alias, All, Conditional, Field, Filter, NodeClause, Raw, SurrealId, SurrealOrmResult,
-    SurrealSimpleId, SurrealUlid, SurrealUuid, Table, ValueLike,
+    Alias, All, Buildable, Conditional, Field, Filter, NodeClause, Raw, SurrealId, SurrealOrmError,
+    SurrealOrmResult, SurrealSimpleId, SurrealUlid, SurrealUuid, Table, ValueLike,
 };
 use serde::{de::DeserializeOwned, Serialize};
 use surrealdb::sql::{self, Thing};
@@ -30,6 +31,82 @@ pub struct FieldMetadata {
     pub definition: Vec<Raw>,
 }
 
+/// The model a record link field points to. Used to walk nested include paths
+/// hop by hop.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct LinkedModel {
+    linked_fields: fn() -> Vec<Field>,
+    linked_model: fn(&str) -> Option<LinkedModel>,
+}
+
+impl LinkedModel {
+    /// The linked model of `T`
+    pub fn of<T: Model>() -> Self {
+        Self {
+            linked_fields: T::get_linked_fields,
+            linked_model: T::get_linked_model,
+        }
+    }
+
+    /// The linked model with the given link fields and the models they point to e.g the
+    /// links of all the variants of a `NodeUnion`
+    pub fn new(
+        linked_fields: fn() -> Vec<Field>,
+        linked_model: fn(&str) -> Option<LinkedModel>,
+    ) -> Self {
+        Self {
+            linked_fields,
+            linked_model,
+        }
+    }
+
+    /// Validates every hop of a record link path e.g `author.company.address` and
+    /// returns the paths to fetch for it i.e `author`, `author.company` and
+    /// `author.company.address`. Array selectors of link_many fields e.g `[*]` are
+    /// allowed but ignored since the whole array is fetched.
+    pub(crate) fn fetch_paths(self, path: &str) -> SurrealOrmResult<Vec<String>> {
+        let unfetchable = |hops: &[sql::Part]| {
+            SurrealOrmError::FieldsUnfetchableNotARecordLink(if hops.is_empty() {
+                path.to_string()
+            } else {
+                sql::Idiom::from(hops.to_vec()).to_string()
+            })
+        };
+        let idiom = sql::idiom(path).map_err(|_| unfetchable(&[]))?;
+
+        let mut model = Some(self);
+        let mut hops = vec![];
+        let mut fetch_paths = vec![];
+        for part in idiom.iter() {
+            match part {
+                sql::Part::Field(field) => {
+                    hops.push(part.clone());
+                    let current = model.ok_or_else(|| unfetchable(&hops))?;
+                    if !(current.linked_fields)()
+                        .iter()
+                        .any(|linked| linked.build() == field.as_str())
+                    {
+                        return Err(unfetchable(&hops));
+                    }
+                    fetch_paths.push(sql::Idiom::from(hops.clone()).to_string());
+                    model = (current.linked_model)(field.as_str());
+                }
+                sql::Part::All
+                | sql::Part::First
+                | sql::Part::Last
+                | sql::Part::Index(_)
+                | sql::Part::Where(_)
+                | sql::Part::Value(_)
+                    if !hops.is_empty() => {}
+                _ => return Err(unfetchable(&hops)),
+            }
+        }
+
+        Ok(fetch_paths)
+    }
+}
+
 /// Model is a trait signifying superset of Node and Edge.
 /// i.e both are Model
 pub trait Model: Sized {
@@ -70,9 +147,51 @@ pub trait Model: Sized {
     /// Get model's fields definitions statements as a list
     fn define_fields() -> Vec<Raw>;
 
+    /// Get model's index definitions statements declared with the `index`, `unique` and
+    /// `search` attributes
+    fn define_indexes() -> Vec<Raw> {
+        vec![]
+    }
+
+    /// Get model's event definitions statements declared with the `event` attribute
+    fn define_events() -> Vec<Raw> {
+        vec![]
+    }
+
     /// Get old name of field
     fn get_field_meta() -> Vec<FieldMetadata>;
 
+    /// The model a record link field points to, used for validating nested
+    /// include paths. Returns None for fields that are not record links.
+    fn get_linked_model(_field: &str) -> Option<LinkedModel> {
+        None
+    }
+
+    /// The field marked with `#[sorm(version)]` used for optimistic concurrency control.
+    /// Updates of a record by id check it against the version the record was read at
+    /// and increment it.
+    fn version_field() -> Option<Field> {
+        None
+    }
+
+    /// The `deleted_at` field of a `#[sorm(soft_delete)]` table. The delete helpers set it
+    /// instead of deleting records, and the find and count helpers skip records that have it.
+    fn soft_delete_field() -> Option<Field> {
+        None
+    }
+
+    /// Whether generated migrations may remove or narrow the model's fields
+    /// without failing the `require-ack` policy. Set with `#[sorm(allow_destructive)]`.
+    fn allow_destructive() -> bool {
+        false
+    }
+
+    /// Previous name of the model's table. Generated migrations copy the data of
+    /// the old table into the new one. Set with `#[sorm(old_name = "old_table")]`.
+    fn old_table_name() -> Option<Table> {
+        None
+    }
+
     /// Create a new SurrealId from a string
     fn create_thing(id: impl Into<sql::Id>) -> Thing {
         Thing::from((Self::table().to_string(), id.into()))
@@ -112,8 +231,29 @@ pub trait Model: Sized {
 /// DB convenience helper methods.
 pub trait SurrealCrud: Sized + Serialize + DeserializeOwned + Model {
     /// Creates or updates a model/table in the database.
+    /// The record of a soft deleted table keeps when it was deleted, so that saving a soft
+    /// deleted record does not restore it.
     fn save(self) -> UpdateStatement<Self> {
-        update::<Self>(self.get_id_as_thing()).content(self)
+        let id = self.get_id_as_thing();
+        match Self::soft_delete_field() {
+            Some(deleted_at) => update::<Self>(id).content_keeping(self, &deleted_at),
+            None => update::<Self>(id).content(self),
+        }
+    }
+
+    /// Creates the record or updates the existing record with the same id.
+    /// Replaces the existing record by default. Use `merge` or `merge_partial` on the
+    /// returned statement to merge into it instead.
+    fn upsert(self) -> UpsertStatement<Self> {
+        UpsertStatement::on_id(self)
+    }
+
+    /// Creates the record or updates the existing record with the same values on the given
+    /// fields e.g the fields of a unique index.
+    /// Replaces the existing record by default. Use `merge` or `merge_partial` on the
+    /// returned statement to merge into it instead.
+    fn upsert_on(self, conflict_fields: impl Into<Fields>) -> UpsertStatement<Self> {
+        UpsertStatement::on_fields(self, conflict_fields)
     }
 
     /// Finds a record by id.
@@ -128,34 +268,58 @@ pub trait SurrealCrud: Sized + Serialize + DeserializeOwned + Model {
 
     /// Count filtered records.
     fn count_where(filter: impl Conditional + Clone) -> SelectStatementCount {
-        let selection = select_value(Field::new("count")).from(
+        SelectStatementCount::of::<Self>(
             select(count!(Filter::new(filter)))
                 .from(Self::table())
                 .group_all(),
-        );
-        selection.into()
+        )
     }
 
     /// Count all records.
     fn count_all() -> SelectStatementCount {
-        let selection = select_value(Field::new("count"))
-            .from(select(count!()).from(Self::table()).group_all());
-        selection.into()
+        SelectStatementCount::of::<Self>(select(count!()).from(Self::table()).group_all())
     }
 
     /// Delete the current record by instance.
+    /// Soft deletes it if the table is soft deleted.
     fn delete(&self) -> DeleteStatementMini<Self> {
-        delete::<Self>(self.get_id_as_thing()).into()
+        Self::delete_by_id(self.get_id_as_thing())
     }
 
     /// Deletes a record by id.
+    /// Soft deletes it if the table is soft deleted.
     fn delete_by_id(id: impl Into<Thing>) -> DeleteStatementMini<Self> {
-        delete::<Self>(id.into()).into()
+        let id = id.into();
+        match Self::soft_delete_field() {
+            Some(_) => DeleteStatementMini::soft(update::<Self>(id).soft_delete(None)),
+            None => delete::<Self>(id).into(),
+        }
     }
 
     /// Deletes records by filtering.
+    /// Soft deletes them if the table is soft deleted.
     fn delete_where(filter: impl Conditional + Clone) -> DeleteStatementMini<Self> {
-        delete::<Self>(Self::table()).where_(filter).into()
+        match Self::soft_delete_field() {
+            Some(_) => DeleteStatementMini::soft(
+                update::<Self>(Self::table()).soft_delete(Some(Filter::new(filter))),
+            ),
+            None => delete::<Self>(Self::table()).where_(filter).into(),
+        }
+    }
+
+    /// Restores the current soft deleted record by instance.
+    fn restore(&self) -> UpdateStatement<Self> {
+        Self::restore_by_id(self.get_id_as_thing())
+    }
+
+    /// Restores a soft deleted record by id.
+    fn restore_by_id(id: impl Into<Thing>) -> UpdateStatement<Self> {
+        update::<Self>(id.into()).restore(None)
+    }
+
+    /// Restores soft deleted records by filtering.
+    fn restore_where(filter: impl Conditional + Clone) -> UpdateStatement<Self> {
+        update::<Self>(Self::table()).restore(Some(Filter::new(filter)))
     }
 }
 
@@ -0,0 +1,138 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use serde::{de::DeserializeOwned, Serialize};
+use surrealdb::sql;
+
+use crate::{LinkedModel, Model, Node, SchemaGetter, Table};
+
+/// A type that the record link fields i.e `LinkOne` and `LinkMany` can point to.
+/// Implemented for every `Node` and for the enums of nodes deriving `NodeUnion`.
+pub trait Linkable: Serialize + SchemaGetter {
+    /// The id that can be appended to a `LinkMany` field pointing to the type
+    type LinkId;
+
+    /// The tables of the records a link can point to e.g `record<car | bike>`
+    fn link_tables() -> Vec<Table>;
+
+    /// The id of the linked record
+    fn link_id(&self) -> sql::Thing;
+
+    /// The model a link points to, used for validating nested include paths
+    #[doc(hidden)]
+    fn linked_model() -> LinkedModel;
+}
+
+impl<T: Node> Linkable for T {
+    type LinkId = <T as Model>::Id;
+
+    fn link_tables() -> Vec<Table> {
+        vec![T::table()]
+    }
+
+    fn link_id(&self) -> sql::Thing {
+        self.get_id_as_thing()
+    }
+
+    fn linked_model() -> LinkedModel {
+        LinkedModel::of::<T>()
+    }
+}
+
+/// An enum of nodes allowing a `LinkOne` or `LinkMany` field to point to records of
+/// different tables. Usually derived with `#[derive(NodeUnion)]` on an enum whose variants
+/// each wrap a node e.g
+/// ```rust, ignore
+/// #[derive(NodeUnion, Debug, Clone)]
+/// pub enum Vehicle {
+///     Car(Car),
+///     Bike(Bike),
+/// }
+/// ```
+///
+/// A fetched record is deserialized into the variant of the node of the record's table.
+pub trait NodeUnion: Linkable + DeserializeOwned {
+    /// The tables of the nodes of the variants
+    fn variant_tables() -> Vec<Table>;
+
+    /// The table of the node held by the variant
+    fn variant_table(&self) -> Table;
+
+    /// The table of a fetched record, read from its id
+    #[doc(hidden)]
+    fn record_table(record: &serde_json::Value) -> Option<String> {
+        match record.get("id")? {
+            serde_json::Value::String(id) => sql::thing(id).ok().map(|thing| thing.tb),
+            id => id.get("tb")?.as_str().map(ToString::to_string),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serializer};
+    use serde_json::json;
+
+    #[derive(Deserialize)]
+    struct Vehicle;
+
+    impl Serialize for Vehicle {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_unit()
+        }
+    }
+
+    impl SchemaGetter for Vehicle {
+        type Schema = ();
+
+        fn schema() -> Self::Schema {}
+
+        fn schema_prefixed(_prefix: impl Into<crate::ValueLike>) -> Self::Schema {}
+    }
+
+    impl Linkable for Vehicle {
+        type LinkId = sql::Thing;
+
+        fn link_tables() -> Vec<Table> {
+            Self::variant_tables()
+        }
+
+        fn link_id(&self) -> sql::Thing {
+            sql::thing("car:1").unwrap()
+        }
+
+        fn linked_model() -> LinkedModel {
+            LinkedModel::new(Vec::new, |_| None)
+        }
+    }
+
+    impl NodeUnion for Vehicle {
+        fn variant_tables() -> Vec<Table> {
+            vec![Table::new("car"), Table::new("bike")]
+        }
+
+        fn variant_table(&self) -> Table {
+            Table::new("car")
+        }
+    }
+
+    #[test]
+    fn test_record_table_from_id_object() {
+        let record = json!({ "id": { "tb": "bike", "id": { "String": "b1" } }, "gears": 21 });
+
+        assert_eq!(Vehicle::record_table(&record), Some("bike".to_string()));
+    }
+
+    #[test]
+    fn test_record_table_from_id_string() {
+        let record = json!({ "id": "car:c1", "doors": 4 });
+
+        assert_eq!(Vehicle::record_table(&record), Some("car".to_string()));
+        assert_eq!(Vehicle::record_table(&json!({ "doors": 4 })), None);
+    }
+}
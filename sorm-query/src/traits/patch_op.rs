@@ -181,6 +181,11 @@ fn patch_path<T: Serialize>(field: &Field, operation_type: OpType, value: impl I
     })
 }
 
+/// Replaces the value of the field
+pub(crate) fn patch_replace<T: Serialize>(field: &Field, value: T) -> PatchOp {
+    patch_path::<T>(field, OpType::Replace, value)
+}
+
 pub(crate) fn derive_binding_and_errors_from_value<T: Serialize>(
     value: &T,
 ) -> (Binding, Vec<String>) {
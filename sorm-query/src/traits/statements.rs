@@ -1,10 +1,10 @@
 use super::{Buildable, Parametric};
 use crate::{
-    AllGetter, Field, Model, Projections, Queryable, ReturnType, SurrealOrmError, SurrealOrmResult,
-    ValueLike,
+    statements::update::as_stale_write, AliasName, Aliasable, AllGetter, Field, Model, Node,
+    Projections, Queryable, ReturnType, SurrealOrmError, SurrealOrmResult, ValueLike,
 };
 use serde::{de::DeserializeOwned, Serialize};
-use surrealdb::{Connection, Surreal};
+use surrealdb::{sql, Connection, Response, Surreal};
 
 // Create, Update, Relate, Delete
 // [ RETURN [ NONE | BEFORE | AFTER | DIFF | @projections ... ]
@@ -34,7 +34,7 @@ where
             acc.bind((val.get_param(), val.get_value()))
         });
 
-        Ok(query.await.map_err(SurrealOrmError::QueryRun)?)
+        self.check_response(query.await.map_err(SurrealOrmError::QueryRun)?)
     }
 
     /// Runs the statement against the database and returns the deserialized result.
@@ -51,10 +51,124 @@ where
             .take::<Option<T>>(0)
             .map_err(SurrealOrmError::Deserialization)?)
     }
+
+    /// Runs the statements against the database and returns the deserialized result of each
+    /// statement as the element at the same position of the tuple, e.g `(Vec<User>, Option<Account>, i64)`.
+    /// LET statements also return a result which can be declared as `()`,
+    /// while BEGIN, COMMIT and CANCEL TRANSACTION do not. A RETURN statement in a transaction
+    /// replaces the results of the statements before it.
+    ///
+    /// Examples
+    /// ```rust, ignore
+    /// let (_, users, account): ((), Vec<User>, Option<Account>) = transaction! {
+    ///     BEGIN TRANSACTION;
+    ///     LET account = create().content(account);
+    ///     select(All).from(User::table());
+    ///     select(All).from(account_id);
+    ///     COMMIT TRANSACTION;
+    /// }
+    /// .get_results(db.clone())
+    /// .await?;
+    /// ```
+    async fn get_results<R>(&self, db: Surreal<impl surrealdb::Connection>) -> SurrealOrmResult<R>
+    where
+        R: QueryResults,
+    {
+        let response = self.run(db).await?;
+        R::from_response(response)
+    }
 }
 
 impl<Q> Runnable for Q where Q: Queryable {}
 
+/// Typed results of a query of several statements. Implemented for tuples of up to 12 elements,
+/// each holding the result of the statement at the same position.
+pub trait QueryResults: Sized {
+    /// Number of statements whose results are read
+    const STATEMENTS_COUNT: usize;
+
+    /// Reads the result of each statement from the response
+    fn from_response(response: Response) -> SurrealOrmResult<Self>;
+}
+
+macro_rules! impl_query_results {
+    ($count:literal; $($index:tt => $output:ident),+) => {
+        impl<$($output),+> QueryResults for ($($output,)+)
+        where
+            $($output: DeserializeOwned,)+
+        {
+            const STATEMENTS_COUNT: usize = $count;
+
+            fn from_response(mut response: Response) -> SurrealOrmResult<Self> {
+                check_statements(&mut response, Self::STATEMENTS_COUNT)?;
+                Ok(($(take_statement::<$output>(&mut response, $index)?,)+))
+            }
+        }
+    };
+}
+
+impl_query_results!(1; 0 => A);
+impl_query_results!(2; 0 => A, 1 => B);
+impl_query_results!(3; 0 => A, 1 => B, 2 => C);
+impl_query_results!(4; 0 => A, 1 => B, 2 => C, 3 => D);
+impl_query_results!(5; 0 => A, 1 => B, 2 => C, 3 => D, 4 => E);
+impl_query_results!(6; 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F);
+impl_query_results!(7; 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G);
+impl_query_results!(8; 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H);
+impl_query_results!(9; 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H, 8 => I);
+impl_query_results!(10; 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H, 8 => I, 9 => J);
+impl_query_results!(11; 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H, 8 => I, 9 => J, 10 => K);
+impl_query_results!(12; 0 => A, 1 => B, 2 => C, 3 => D, 4 => E, 5 => F, 6 => G, 7 => H, 8 => I, 9 => J, 10 => K, 11 => L);
+
+/// Takes the errors of a response and returns the statement that failed first, if any.
+/// A failed statement cancels a transaction without failing the request, and every
+/// other statement then errors as not executed, so those only count when nothing else failed.
+pub fn take_first_failure(response: &mut Response) -> Option<(usize, surrealdb::Error)> {
+    response.take_errors().into_iter().min_by_key(|(index, e)| {
+        let not_executed = matches!(
+            e,
+            surrealdb::Error::Db(surrealdb::error::Db::QueryNotExecuted)
+        );
+        (not_executed, *index)
+    })
+}
+
+pub(crate) fn check_statements(response: &mut Response, expected: usize) -> SurrealOrmResult<()> {
+    let returned = response.num_statements();
+
+    if let Some((index, e)) = take_first_failure(response) {
+        return Err(as_stale_write(&e).unwrap_or(SurrealOrmError::StatementFailed(index, e)));
+    }
+
+    if returned != expected {
+        return Err(SurrealOrmError::StatementCountMismatch(expected, returned));
+    }
+
+    Ok(())
+}
+
+fn take_statement<T>(response: &mut Response, index: usize) -> SurrealOrmResult<T>
+where
+    T: DeserializeOwned,
+{
+    let value = response
+        .take::<sql::Value>(index)
+        .map_err(|e| SurrealOrmError::StatementFailed(index, e))?;
+
+    match sql::from_value::<T>(value.clone()) {
+        Ok(output) => Ok(output),
+        Err(e) => match value {
+            // Statements like SELECT return a list even for a single record,
+            // so it is also read as the record e.g into an `Option<Account>`
+            sql::Value::Array(mut records) if records.len() <= 1 => {
+                sql::from_value(records.pop().unwrap_or(sql::Value::None))
+                    .map_err(|_| SurrealOrmError::StatementDeserialization(index, e.into()))
+            }
+            _ => Err(SurrealOrmError::StatementDeserialization(index, e.into())),
+        },
+    }
+}
+
 /// A trait that represents a statement that can be run against the database and return a single
 #[async_trait::async_trait]
 pub trait ReturnableStandard<T>
@@ -258,6 +372,67 @@ where
         self.load_links(T::get_link_self_fields())
     }
 
+    /// Sets the return type to projections and fetches the nodes of the relation fields
+    /// i.e fields of type `Relate<V>`, so that they are returned as fetched values.
+    /// The relations are specified by their aliases e.g `Student::aliases().writtenBooks`.
+    fn load_relations(self, relations: Vec<impl Into<AliasName>>) -> SurrealOrmResult<Self>
+    where
+        T: Node,
+    {
+        let relations = relations
+            .into_iter()
+            .map(|r| r.into().to_string())
+            .collect::<Vec<_>>();
+        let relations_aliased = T::get_fields_relations_aliased()
+            .into_iter()
+            .map(|alias| (alias.clone().get_alias_name().to_string(), alias))
+            .collect::<Vec<_>>();
+
+        let unfetchable = relations
+            .iter()
+            .filter(|r| !relations_aliased.iter().any(|(name, _)| name == *r))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !unfetchable.is_empty() {
+            return Err(SurrealOrmError::AliasesUnfetchableNotARelation(
+                unfetchable.join(", "),
+            ));
+        }
+
+        let projections = ReturnType::Projections(
+            vec![ValueLike::from(Field::new("*"))]
+                .into_iter()
+                .chain(
+                    relations_aliased
+                        .into_iter()
+                        .filter(|(name, _)| relations.contains(name))
+                        // `->writes->book.* AS writtenBooks` fetches the related books in place of their ids
+                        .map(|(name, alias)| {
+                            Field::new(format!("{}.*", alias.get_aliased()))
+                                .with_bindings(alias.get_bindings())
+                                .__as__(AliasName::new(name))
+                                .into()
+                        }),
+                )
+                .collect::<Vec<_>>()
+                .into(),
+        );
+
+        Ok(self.set_return_type(projections))
+    }
+
+    /// Sets the return type to projections and fetches the nodes of all the relation fields.
+    fn load_all_relations(self) -> SurrealOrmResult<Self>
+    where
+        T: Node,
+    {
+        let relations = T::get_fields_relations_aliased()
+            .into_iter()
+            .map(|alias| alias.get_alias_name())
+            .collect::<Vec<_>>();
+        self.load_relations(relations)
+    }
+
     /// Runs the statement against the database and returns the one result.
     async fn return_one(&self, db: Surreal<impl Connection>) -> SurrealOrmResult<Option<T>> {
         let response = self.run(db).await?;
@@ -0,0 +1,184 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use serde::{de::DeserializeOwned, Serialize};
+use surrealdb::sql;
+
+use crate::{FieldType, Filter, SurrealFieldType};
+
+/// A Rust enum stored in a model field. Usually derived with `#[derive(SurrealEnum)]`.
+///
+/// Following serde, unit variants are stored as strings and the variants carrying data as
+/// objects keyed by the variant name, unless the enum is tagged with `#[serde(tag = "...")]`
+/// in which case every variant is an object holding its name in the tag field.
+/// The derive also implements `SurrealFieldType` with the type and assertion of the variants.
+pub trait SurrealEnum: SurrealFieldType + Serialize + DeserializeOwned {
+    /// The serialized names of the unit variants
+    fn unit_variants() -> Vec<&'static str>;
+
+    /// The serialized names of the variants carrying data
+    fn data_variants() -> Vec<&'static str>;
+
+    /// The field holding the variant name of an internally or adjacently tagged enum
+    fn tag() -> Option<&'static str> {
+        None
+    }
+
+    /// The database type of the enum. `string` when all the variants are units,
+    /// `object` when they all carry data or the enum is tagged and `string | object` otherwise.
+    fn variants_field_type() -> FieldType {
+        if Self::tag().is_some() {
+            return FieldType::Object;
+        }
+
+        match (
+            Self::unit_variants().is_empty(),
+            Self::data_variants().is_empty(),
+        ) {
+            (_, true) => FieldType::String,
+            (true, false) => FieldType::Object,
+            (false, false) => FieldType::Union(vec![FieldType::String, FieldType::Object]),
+        }
+    }
+
+    /// The condition restricting `$value` to the variants of the enum e.g
+    /// `$value INSIDE ['active', 'retired']`
+    fn variants_assertion() -> Filter {
+        let variant_names = |names: Vec<&'static str>| {
+            sql::Value::from(names.into_iter().map(sql::Value::from).collect::<Vec<_>>())
+        };
+
+        if let Some(tag) = Self::tag() {
+            let variants = Self::unit_variants()
+                .into_iter()
+                .chain(Self::data_variants())
+                .collect::<Vec<_>>();
+            return format!("$value.{tag} INSIDE {}", variant_names(variants)).into();
+        }
+
+        let unit_variants = Self::unit_variants();
+        let data_variants = Self::data_variants();
+        let units_assertion = format!("$value INSIDE {}", variant_names(unit_variants.clone()));
+        let data_assertion = format!(
+            "type::is::object($value) AND object::len($value) = 1 AND object::keys($value) ALLINSIDE {}",
+            variant_names(data_variants.clone())
+        );
+
+        let assertion = match (unit_variants.is_empty(), data_variants.is_empty()) {
+            (_, true) => units_assertion,
+            (true, false) => data_assertion,
+            (false, false) => format!("({units_assertion}) OR ({data_assertion})"),
+        };
+        assertion.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::Buildable;
+
+    macro_rules! impl_surreal_field_type_for_enum {
+        ($($enum:ident),+) => {
+            $(
+                impl SurrealFieldType for $enum {
+                    fn field_type() -> FieldType {
+                        Self::variants_field_type()
+                    }
+
+                    fn assertion() -> Option<Filter> {
+                        Some(Self::variants_assertion())
+                    }
+                }
+            )+
+        };
+    }
+
+    impl_surreal_field_type_for_enum!(Rank, Status, Shape);
+
+    #[derive(Serialize, Deserialize)]
+    enum Rank {
+        Cadet,
+        Captain,
+    }
+
+    impl SurrealEnum for Rank {
+        fn unit_variants() -> Vec<&'static str> {
+            vec!["Cadet", "Captain"]
+        }
+
+        fn data_variants() -> Vec<&'static str> {
+            vec![]
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    enum Status {
+        Active,
+        Retired { year: u16 },
+    }
+
+    impl SurrealEnum for Status {
+        fn unit_variants() -> Vec<&'static str> {
+            vec!["Active"]
+        }
+
+        fn data_variants() -> Vec<&'static str> {
+            vec!["Retired"]
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(tag = "kind")]
+    enum Shape {
+        Circle { radius: f64 },
+        Point,
+    }
+
+    impl SurrealEnum for Shape {
+        fn unit_variants() -> Vec<&'static str> {
+            vec!["Point"]
+        }
+
+        fn data_variants() -> Vec<&'static str> {
+            vec!["Circle"]
+        }
+
+        fn tag() -> Option<&'static str> {
+            Some("kind")
+        }
+    }
+
+    #[test]
+    fn test_unit_enum() {
+        assert_eq!(Rank::variants_field_type().to_string(), "string");
+        assert_eq!(
+            Rank::variants_assertion().build(),
+            "$value INSIDE ['Cadet', 'Captain']"
+        );
+    }
+
+    #[test]
+    fn test_enum_with_data_variants() {
+        assert_eq!(Status::variants_field_type().to_string(), "string | object");
+        assert_eq!(
+            Status::variants_assertion().build(),
+            "($value INSIDE ['Active']) OR (type::is::object($value) AND object::len($value) = 1 AND object::keys($value) ALLINSIDE ['Retired'])"
+        );
+    }
+
+    #[test]
+    fn test_tagged_enum() {
+        assert_eq!(Shape::variants_field_type().to_string(), "object");
+        assert_eq!(
+            Shape::variants_assertion().build(),
+            "$value.kind INSIDE ['Point', 'Circle']"
+        );
+    }
+}
@@ -0,0 +1,323 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    rc::Rc,
+    sync::Arc,
+};
+
+use surrealdb::sql;
+
+use crate::{cond, value, Buildable, FieldType, Filter, GeometryType, Operatable, ToRaw, NONE};
+
+/// Maps a rust type to the database type of the fields holding it. The `Node`, `Edge` and
+/// `Object` derives use it to infer the type of fields that do not specify one with `ty`.
+///
+/// It can be derived for newtypes with `#[derive(SurrealFieldType)]` or implemented
+/// for any other type e.g
+/// ```
+/// use sorm_query::{FieldType, SurrealFieldType};
+///
+/// struct Temperature(f64);
+///
+/// impl SurrealFieldType for Temperature {
+///     fn field_type() -> FieldType {
+///         FieldType::Float
+///     }
+/// }
+///
+/// assert_eq!(<Option<Temperature>>::field_type().to_string(), "option<float>");
+/// ```
+pub trait SurrealFieldType {
+    /// The database type of the values of the type as serialized
+    fn field_type() -> FieldType;
+
+    /// The assertion restricting the field to the valid values of the type, if any
+    fn assertion() -> Option<Filter> {
+        None
+    }
+}
+
+macro_rules! impl_surreal_field_type {
+    ($field_type:expr => $($ty:ty),+ $(,)?) => {
+        $(
+            impl SurrealFieldType for $ty {
+                fn field_type() -> FieldType {
+                    $field_type
+                }
+            }
+        )+
+    };
+}
+
+impl_surreal_field_type!(FieldType::Bool => bool);
+impl_surreal_field_type!(
+    FieldType::Int => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+impl_surreal_field_type!(FieldType::Float => f32, f64);
+impl_surreal_field_type!(FieldType::String => String, str, char, sql::Strand);
+impl_surreal_field_type!(FieldType::Number => sql::Number);
+impl_surreal_field_type!(FieldType::Datetime => sql::Datetime);
+impl_surreal_field_type!(FieldType::Duration => std::time::Duration, sql::Duration);
+impl_surreal_field_type!(FieldType::Uuid => sql::Uuid, uuid::Uuid);
+impl_surreal_field_type!(FieldType::Bytes => sql::Bytes);
+#[cfg(feature = "bytes")]
+impl_surreal_field_type!(FieldType::Bytes => bytes::Bytes);
+impl_surreal_field_type!(FieldType::Object => sql::Object, serde_json::Map<String, serde_json::Value>);
+impl_surreal_field_type!(FieldType::Array(Box::new(FieldType::Any), None) => sql::Array);
+impl_surreal_field_type!(FieldType::Record(vec![]) => sql::Thing);
+impl_surreal_field_type!(FieldType::Any => sql::Value, serde_json::Value);
+impl_surreal_field_type!(FieldType::Geometry(vec![GeometryType::Feature]) => sql::Geometry);
+// These are serialized as strings
+impl_surreal_field_type!(
+    FieldType::String => chrono::NaiveDate, chrono::NaiveDateTime, chrono::NaiveTime
+);
+#[cfg(feature = "rust_decimal")]
+impl_surreal_field_type!(FieldType::String => rust_decimal::Decimal);
+
+impl<Tz: chrono::TimeZone> SurrealFieldType for chrono::DateTime<Tz> {
+    fn field_type() -> FieldType {
+        FieldType::Datetime
+    }
+}
+
+macro_rules! impl_surreal_field_type_geometry {
+    ($($geometry_type:ident => $geo:ident),+ $(,)?) => {
+        $(
+            impl<T: geo::CoordNum> SurrealFieldType for geo::$geo<T> {
+                fn field_type() -> FieldType {
+                    FieldType::Geometry(vec![GeometryType::$geometry_type])
+                }
+            }
+        )+
+    };
+}
+
+impl_surreal_field_type_geometry!(
+    Point => Point,
+    LineString => LineString,
+    Polygon => Polygon,
+    MultiPoint => MultiPoint,
+    MultiLine => MultiLineString,
+    MultiPolygon => MultiPolygon,
+    Collection => GeometryCollection,
+    Feature => Geometry,
+);
+
+impl<T: SurrealFieldType> SurrealFieldType for Option<T> {
+    fn field_type() -> FieldType {
+        FieldType::Option(Box::new(T::field_type()))
+    }
+
+    fn assertion() -> Option<Filter> {
+        T::assertion().map(|assertion| cond(value().is(NONE)).or(assertion))
+    }
+}
+
+/// Asserts every item of an array or set with the assertion of the item type,
+/// rebinding `$value` to each item so that the item assertion applies as is
+fn items_assertion<T: SurrealFieldType>() -> Option<Filter> {
+    T::assertion().map(|assertion| {
+        Filter::from(format!(
+            "array::len($value[WHERE {{ LET $value = $this; RETURN !({}); }}]) = 0",
+            assertion.to_raw().build()
+        ))
+    })
+}
+
+macro_rules! impl_surreal_field_type_array {
+    ($($ty:ident),+ $(,)?) => {
+        $(
+            impl<T: SurrealFieldType> SurrealFieldType for $ty<T> {
+                fn field_type() -> FieldType {
+                    FieldType::Array(Box::new(T::field_type()), None)
+                }
+
+                fn assertion() -> Option<Filter> {
+                    items_assertion::<T>()
+                }
+            }
+        )+
+    };
+}
+
+impl_surreal_field_type_array!(Vec, VecDeque);
+
+impl<T: SurrealFieldType, const N: usize> SurrealFieldType for [T; N] {
+    fn field_type() -> FieldType {
+        FieldType::Array(Box::new(T::field_type()), Some(N as u64))
+    }
+
+    fn assertion() -> Option<Filter> {
+        items_assertion::<T>()
+    }
+}
+
+impl<T: SurrealFieldType, S> SurrealFieldType for HashSet<T, S> {
+    fn field_type() -> FieldType {
+        FieldType::Set(Box::new(T::field_type()), None)
+    }
+
+    fn assertion() -> Option<Filter> {
+        items_assertion::<T>()
+    }
+}
+
+impl<T: SurrealFieldType> SurrealFieldType for BTreeSet<T> {
+    fn field_type() -> FieldType {
+        FieldType::Set(Box::new(T::field_type()), None)
+    }
+
+    fn assertion() -> Option<Filter> {
+        items_assertion::<T>()
+    }
+}
+
+impl<K, V, S> SurrealFieldType for HashMap<K, V, S> {
+    fn field_type() -> FieldType {
+        FieldType::Object
+    }
+}
+
+impl<K, V> SurrealFieldType for BTreeMap<K, V> {
+    fn field_type() -> FieldType {
+        FieldType::Object
+    }
+}
+
+// Smart pointers and references are serialized as the value they point to
+macro_rules! impl_surreal_field_type_pointer {
+    ($($ty:ty),+ $(,)?) => {
+        $(
+            impl<T: SurrealFieldType + ?Sized> SurrealFieldType for $ty {
+                fn field_type() -> FieldType {
+                    T::field_type()
+                }
+
+                fn assertion() -> Option<Filter> {
+                    T::assertion()
+                }
+            }
+        )+
+    };
+}
+
+impl_surreal_field_type_pointer!(&T, &mut T, Box<T>, Rc<T>, Arc<T>);
+
+impl<T: SurrealFieldType + ToOwned + ?Sized> SurrealFieldType for Cow<'_, T> {
+    fn field_type() -> FieldType {
+        T::field_type()
+    }
+
+    fn assertion() -> Option<Filter> {
+        T::assertion()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Buildable;
+
+    struct Grade;
+
+    impl SurrealFieldType for Grade {
+        fn field_type() -> FieldType {
+            FieldType::String
+        }
+
+        fn assertion() -> Option<Filter> {
+            Some(Filter::from("$value INSIDE ['A', 'B']".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_primitives() {
+        assert_eq!(bool::field_type().to_string(), "bool");
+        assert_eq!(u8::field_type().to_string(), "int");
+        assert_eq!(f64::field_type().to_string(), "float");
+        assert_eq!(<&str>::field_type().to_string(), "string");
+        assert!(String::assertion().is_none());
+    }
+
+    #[test]
+    fn test_ecosystem_types() {
+        assert_eq!(sql::Uuid::field_type().to_string(), "uuid");
+        assert_eq!(uuid::Uuid::field_type().to_string(), "uuid");
+        assert_eq!(sql::Array::field_type().to_string(), "array<any>");
+        assert_eq!(chrono::NaiveDate::field_type().to_string(), "string");
+        assert_eq!(
+            chrono::DateTime::<chrono::Utc>::field_type().to_string(),
+            "datetime"
+        );
+        assert_eq!(std::time::Duration::field_type().to_string(), "duration");
+        assert_eq!(
+            geo::Point::<f64>::field_type().to_string(),
+            "geometry<point>"
+        );
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn test_bytes() {
+        assert_eq!(bytes::Bytes::field_type().to_string(), "bytes");
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn test_decimal() {
+        assert_eq!(rust_decimal::Decimal::field_type().to_string(), "string");
+    }
+
+    #[test]
+    fn test_collections_and_pointers() {
+        assert_eq!(Vec::<u64>::field_type().to_string(), "array<int>");
+        assert_eq!(<[f64; 2]>::field_type().to_string(), "array<float, 2>");
+        assert_eq!(HashSet::<String>::field_type().to_string(), "set<string>");
+        assert_eq!(HashMap::<String, u64>::field_type().to_string(), "object");
+        assert_eq!(
+            Option::<Vec<Arc<String>>>::field_type().to_string(),
+            "option<array<string>>"
+        );
+        assert_eq!(Box::<i32>::field_type().to_string(), "int");
+    }
+
+    #[test]
+    fn test_assertion_of_optional_type() {
+        assert_eq!(
+            Option::<Box<Grade>>::assertion().unwrap().build(),
+            "($value IS NONE) OR ($value INSIDE ['A', 'B'])"
+        );
+        assert!(Option::<String>::assertion().is_none());
+    }
+
+    #[test]
+    fn test_assertion_of_collection_items() {
+        let items_assertion =
+            "array::len($value[WHERE { LET $value = $this; RETURN !($value INSIDE ['A', 'B']); }]) = 0";
+        assert_eq!(Vec::<Grade>::assertion().unwrap().build(), items_assertion);
+        assert_eq!(<[Grade; 2]>::assertion().unwrap().build(), items_assertion);
+        assert_eq!(
+            BTreeSet::<Grade>::assertion().unwrap().build(),
+            items_assertion
+        );
+        assert_eq!(
+            Option::<VecDeque<Grade>>::assertion().unwrap().build(),
+            format!("($value IS NONE) OR ({items_assertion})")
+        );
+        assert_eq!(
+            Vec::<Vec<Grade>>::assertion().unwrap().build(),
+            format!(
+                "array::len($value[WHERE {{ LET $value = $this; RETURN !({items_assertion}); }}]) = 0"
+            )
+        );
+        assert!(Vec::<String>::assertion().is_none());
+        assert!(HashSet::<u64>::assertion().is_none());
+    }
+}
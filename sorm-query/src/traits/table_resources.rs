@@ -1,22 +1,21 @@
 use crate::{Field, Model, Raw, Table};
 
 /// Provides the ability to gather all the resources
-/// of a table in a single place. Fields definitions,
-/// and table definitions are using automatically
-/// generated methods from the `Model` trait.
-/// Events and indexes definitions are manually
-/// implemented using the statements or query macro
+/// of a table in a single place. Table, fields, indexes
+/// and events definitions are using automatically
+/// generated methods from the `Model` trait, and
+/// can be overridden using the statements or query macro
 /// provided by the crate.
 pub trait TableResources
 where
     Self: Model,
 {
     fn events_definitions() -> Vec<Raw> {
-        vec![]
+        Self::define_events()
     }
 
     fn indexes_definitions() -> Vec<Raw> {
-        vec![]
+        Self::define_indexes()
     }
 
     fn fields_definitions() -> Vec<Raw> {
@@ -58,6 +57,26 @@ macro_rules! create_table_resources {
             meta
         }
 
+        fn tables_allowing_destructive_changes(&self) -> ::std::vec::Vec<$crate::Table> {
+            let mut tables = ::std::vec::Vec::<$crate::Table>::new();
+            $(
+                if <$struct_table as $crate::Model>::allow_destructive() {
+                    tables.push(<$struct_table as $crate::Model>::table());
+                }
+            )*
+            tables
+        }
+
+        fn tables_old_names(&self) -> ::std::collections::HashMap<$crate::Table, $crate::Table> {
+            let mut old_names = ::std::collections::HashMap::<$crate::Table, $crate::Table>::new();
+            $(
+                if let ::std::option::Option::Some(old_name) = <$struct_table as $crate::Model>::old_table_name() {
+                    old_names.insert(<$struct_table as $crate::Model>::table(), old_name);
+                }
+            )*
+            old_names
+        }
+
 
     };
 }
@@ -19,6 +19,7 @@ pub struct Alias {
     bindings: BindingsList,
     errors: ErrorList,
     graph_string: String,
+    aliased: String,
 }
 
 impl Erroneous for Alias {
@@ -44,6 +45,11 @@ impl Alias {
     pub fn get_alias_name(self) -> AliasName {
         self.name
     }
+
+    /// The aliased statement or field/column without the alias
+    pub(crate) fn get_aliased(&self) -> &str {
+        &self.aliased
+    }
 }
 
 /// The name of an alias.
@@ -109,13 +115,15 @@ where
     /// ```
     fn __as__(&self, alias: impl Into<AliasName>) -> Alias {
         let alias: AliasName = alias.into();
-        let graph_string = format!("{} AS {}", self.build_aliasable(), &alias);
+        let aliased = self.build_aliasable();
+        let graph_string = format!("{} AS {}", aliased, &alias);
 
         Alias {
             name: alias,
             bindings: self.get_bindings(),
             errors: self.get_errors(),
             graph_string,
+            aliased,
         }
     }
 
@@ -5,14 +5,14 @@
  * Licensed under the MIT license
  */
 
-use crate::{Model, Node, SurrealId};
+use crate::{Linkable, Model, Node, SurrealId};
 use serde::{Deserialize, Serialize};
 use surrealdb::sql;
 
 /// A reference to foreign node which can either be an ID or a fetched value itself or null.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(untagged)]
-pub enum Reference<V: Model> {
+pub enum Reference<V: Linkable> {
     /// the id of the foreign node. The defualt if foreign node is not fetched
     Id(sql::Thing),
     // Id(SurrealId<V>),
@@ -24,7 +24,7 @@ pub enum Reference<V: Model> {
 
 impl<V> Reference<V>
 where
-    V: Node,
+    V: Linkable,
 {
     /// .
     ///
@@ -61,7 +61,7 @@ where
     }
 }
 
-impl<V: Node> Default for Reference<V> {
+impl<V: Linkable> Default for Reference<V> {
     fn default() -> Self {
         Self::Null
     }
@@ -69,7 +69,10 @@ impl<V: Node> Default for Reference<V> {
 
 macro_rules! implement_deref_for_link {
     ($reference_ty:ty; $target:ty) => {
-        impl<V: Node> ::std::ops::Deref for $reference_ty {
+        implement_deref_for_link!($reference_ty; $target; Node);
+    };
+    ($reference_ty:ty; $target:ty; $bound:ident) => {
+        impl<V: $bound> ::std::ops::Deref for $reference_ty {
             type Target = $target;
 
             fn deref(&self) -> &Self::Target {
@@ -77,7 +80,7 @@ macro_rules! implement_deref_for_link {
             }
         }
 
-        impl<V: Node> ::std::ops::DerefMut for $reference_ty {
+        impl<V: $bound> ::std::ops::DerefMut for $reference_ty {
             fn deref_mut(&mut self) -> &mut Self::Target {
                 &mut self.0
             }
@@ -87,13 +90,16 @@ macro_rules! implement_deref_for_link {
 
 macro_rules! implement_bidirectional_conversion {
     ($from:ty, $to:ty) => {
-        impl<V: Node> std::convert::From<$from> for $to {
+        implement_bidirectional_conversion!($from, $to, Node);
+    };
+    ($from:ty, $to:ty, $bound:ident) => {
+        impl<V: $bound> std::convert::From<$from> for $to {
             fn from(value: $from) -> Self {
                 value.0
             }
         }
 
-        impl<V: Node> std::convert::From<$to> for $from {
+        impl<V: $bound> std::convert::From<$to> for $from {
             fn from(value: $to) -> Self {
                 Self(value)
             }
@@ -103,18 +109,21 @@ macro_rules! implement_bidirectional_conversion {
 
 macro_rules! impl_from_model_for_ref_type {
     ($surreal_node_generics:ty, $reference_type:ty) => {
-        impl<V: Node> std::convert::From<$surreal_node_generics> for $reference_type {
+        impl_from_model_for_ref_type!($surreal_node_generics, $reference_type, Node);
+    };
+    ($surreal_node_generics:ty, $reference_type:ty, $bound:ident) => {
+        impl<V: $bound> std::convert::From<$surreal_node_generics> for $reference_type {
             fn from(model: $surreal_node_generics) -> Self {
                 // let id = model.get_id::<SurrealId<$surreal_node_generics>>();
-                let id: sql::Thing = model.get_id_as_thing();
+                let id: sql::Thing = model.link_id();
                 let reference = Reference::Id(id.clone());
                 Self(reference.into())
             }
         }
 
-        impl<V: Node + Clone> std::convert::From<&$surreal_node_generics> for $reference_type {
+        impl<V: $bound + Clone> std::convert::From<&$surreal_node_generics> for $reference_type {
             fn from(model: &$surreal_node_generics) -> Self {
-                let id: sql::Thing = model.clone().get_id_as_thing();
+                let id: sql::Thing = model.clone().link_id();
                 let reference = Reference::Id(id.to_owned());
                 Self(reference.into())
             }
@@ -130,19 +139,22 @@ macro_rules! impl_from_model_for_ref_type {
 // even if they use the serde default attribute
 macro_rules! implement_custom_default_for_link {
     ($reference_ty:ty) => {
-        impl<V: Node> Default for $reference_ty {
+        implement_custom_default_for_link!($reference_ty, Node);
+    };
+    ($reference_ty:ty, $bound:ident) => {
+        impl<V: $bound> Default for $reference_ty {
             fn default() -> Self {
                 Self::null()
             }
         }
     };
 }
-impl<V: Node> std::convert::From<Vec<V>> for LinkMany<V> {
+impl<V: Linkable> std::convert::From<Vec<V>> for LinkMany<V> {
     fn from(model_vec: Vec<V>) -> Self {
         let xx = model_vec
             .into_iter()
             .map(|m| {
-                let id: sql::Thing = m.get_id_as_thing();
+                let id: sql::Thing = m.link_id();
                 Reference::Id(id.clone())
             })
             .collect::<Vec<Reference<V>>>();
@@ -151,7 +163,7 @@ impl<V: Node> std::convert::From<Vec<V>> for LinkMany<V> {
     }
 }
 
-impl<V: Node> std::convert::From<Vec<sql::Thing>> for LinkMany<V> {
+impl<V: Linkable> std::convert::From<Vec<sql::Thing>> for LinkMany<V> {
     fn from(model_vec: Vec<sql::Thing>) -> Self {
         let xx = model_vec
             .into_iter()
@@ -163,15 +175,15 @@ impl<V: Node> std::convert::From<Vec<sql::Thing>> for LinkMany<V> {
 }
 /// A reference to a foreign node which can either be an ID or a fetched value itself or null.
 #[derive(Debug, Deserialize, Serialize, Clone)]
-pub struct LinkOne<V: Node>(Reference<V>);
+pub struct LinkOne<V: Linkable>(Reference<V>);
 
-implement_deref_for_link!(LinkOne<V>; Reference<V>);
-implement_bidirectional_conversion!(LinkOne<V>, Reference<V>);
-impl_from_model_for_ref_type!(V, LinkOne<V>);
+implement_deref_for_link!(LinkOne<V>; Reference<V>; Linkable);
+implement_bidirectional_conversion!(LinkOne<V>, Reference<V>, Linkable);
+impl_from_model_for_ref_type!(V, LinkOne<V>, Linkable);
 // implement_from_for_reference_type!(Vec<V>, LinkMany<V>);
-implement_custom_default_for_link!(LinkOne<V>);
+implement_custom_default_for_link!(LinkOne<V>, Linkable);
 
-impl<V: Node> From<LinkOne<V>> for Option<sql::Thing> {
+impl<V: Linkable> From<LinkOne<V>> for Option<sql::Thing> {
     fn from(link: LinkOne<V>) -> Self {
         match link.0 {
             Reference::Id(id) => Some(id),
@@ -191,7 +203,7 @@ where
     }
 }
 
-impl<V: Node> LinkOne<V> {
+impl<V: Linkable> LinkOne<V> {
     /// returns nothing. Useful for satisfying types when instantiating a struct
     /// and you dont want the field be serialized
     pub fn null() -> LinkOne<V> {
@@ -239,7 +251,10 @@ impl_from_model_for_ref_type!(V, LinkSelf<V>);
 
 macro_rules! impl_utils_for_ref_vec {
     ($ref_vec:ident) => {
-        impl<V: Node> $ref_vec<V> {
+        impl_utils_for_ref_vec!($ref_vec, Node);
+    };
+    ($ref_vec:ident, $bound:ident) => {
+        impl<V: $bound> $ref_vec<V> {
             /// Returns an empty vector
             pub fn null() -> Self {
                 $ref_vec(::std::vec![])
@@ -283,9 +298,7 @@ macro_rules! impl_utils_for_ref_vec {
                 self.0
                     .iter()
                     .map(|m| match m {
-                        Reference::FetchedValue(fetched_value) => {
-                            Some(fetched_value.get_id_as_thing())
-                        }
+                        Reference::FetchedValue(fetched_value) => Some(fetched_value.link_id()),
                         Reference::Id(id) => Some(id.to_owned()),
                         Reference::Null => None,
                     })
@@ -299,9 +312,7 @@ macro_rules! impl_utils_for_ref_vec {
                 self.0
                     .iter()
                     .filter_map(|m| match m {
-                        Reference::FetchedValue(fetched_value) => {
-                            Some(fetched_value.get_id_as_thing())
-                        }
+                        Reference::FetchedValue(fetched_value) => Some(fetched_value.link_id()),
                         Reference::Id(id) => Some(id.to_owned()),
                         Reference::Null => None,
                     })
@@ -314,9 +325,7 @@ macro_rules! impl_utils_for_ref_vec {
                 self.0
                     .iter()
                     .filter_map(|m| match m {
-                        Reference::FetchedValue(fetched_value) => {
-                            Some(fetched_value.get_id_as_thing())
-                        }
+                        Reference::FetchedValue(fetched_value) => Some(fetched_value.link_id()),
                         Reference::Id(_id) => None,
                         Reference::Null => None,
                     })
@@ -330,9 +339,9 @@ macro_rules! impl_utils_for_ref_vec {
 /// Returns either the foreign values if fetched, id keys of the foreign Field if not fetched,
 /// empty Vec if not available
 #[derive(Debug, Deserialize, Serialize, Clone)]
-pub struct LinkMany<V: Node>(Vec<Reference<V>>);
+pub struct LinkMany<V: Linkable>(Vec<Reference<V>>);
 
-impl<V: Node> IntoIterator for LinkMany<V> {
+impl<V: Linkable> IntoIterator for LinkMany<V> {
     type Item = Reference<V>;
     type IntoIter = std::vec::IntoIter<Self::Item>;
 
@@ -341,7 +350,7 @@ impl<V: Node> IntoIterator for LinkMany<V> {
     }
 }
 
-impl<V: Node> From<LinkMany<V>> for Vec<Option<sql::Thing>> {
+impl<V: Linkable> From<LinkMany<V>> for Vec<Option<sql::Thing>> {
     fn from(link: LinkMany<V>) -> Self {
         link.0
             .into_iter()
@@ -355,10 +364,10 @@ impl<V: Node> From<LinkMany<V>> for Vec<Option<sql::Thing>> {
 
 // impl<V: Node> From<Vec<V>> for LinkMany<V> {}
 
-implement_custom_default_for_link!(LinkMany<V>);
-implement_deref_for_link!(LinkMany<V>; Vec<Reference<V>>);
-impl_utils_for_ref_vec!(LinkMany);
-implement_bidirectional_conversion!(LinkMany<V>, Vec<Reference<V>>);
+implement_custom_default_for_link!(LinkMany<V>, Linkable);
+implement_deref_for_link!(LinkMany<V>; Vec<Reference<V>>; Linkable);
+impl_utils_for_ref_vec!(LinkMany, Linkable);
+implement_bidirectional_conversion!(LinkMany<V>, Vec<Reference<V>>, Linkable);
 
 /// reference to a foreign node in a many-to-many relationship via an edge
 /// This is not stored in the database and is merely a readonly field
@@ -1,6 +1,7 @@
 use crate::LinkMany;
 use crate::LinkOne;
 use crate::LinkSelf;
+use crate::Linkable;
 use crate::Node;
 use crate::Object;
 // pub use crate::assert_fields;
@@ -245,15 +246,15 @@ impl<T> IsArray for &mut [T] {}
 impl<T> IsArray for &mut Vec<T> {}
 impl IsArray for crate::sql::Array {}
 // impl<T> IsArray for T where T: Into<crate::sql::Array> {}
-impl<N: Node> IsArray for LinkMany<N> {}
-impl<V: Node> IsLinkMany for LinkMany<V> {}
-impl<V: Node> IsLink for LinkMany<V> {}
+impl<N: Linkable> IsArray for LinkMany<N> {}
+impl<V: Linkable> IsLinkMany for LinkMany<V> {}
+impl<V: Linkable> IsLink for LinkMany<V> {}
 
 impl<V: Node> IsLinkSelf for LinkSelf<V> {}
 impl<V: Node> IsLink for LinkSelf<V> {}
 
-impl<V: Node> IsLinkOne for LinkOne<V> {}
-impl<V: Node> IsLink for LinkOne<V> {}
+impl<V: Linkable> IsLinkOne for LinkOne<V> {}
+impl<V: Linkable> IsLink for LinkOne<V> {}
 /// Validate that type is an array at compile time
 /// Array can be a Vec or a slice
 pub fn assert_type_is_array<T: IsArray>() {
@@ -375,7 +376,7 @@ pub fn assert_value_is_bool<T: IsBool>(_value: T) {}
 pub trait IsThing {}
 
 impl<T> IsThing for T where T: Into<crate::sql::Thing> {}
-impl<N: Node> IsThing for LinkOne<N> {}
+impl<N: Linkable> IsThing for LinkOne<N> {}
 impl<N: Node> IsThing for LinkSelf<N> {}
 
 /// Validate that type is a Thing at compile time
@@ -6,10 +6,13 @@
  */
 
 use sorm::{
-    statements::{insert, select, select_value},
+    statements::{begin_transaction, bulk_update, insert, select, select_value, update},
     *,
 };
-use sorm_models::{space_ship, weapon, SpaceShip, Weapon};
+use sorm_models::{
+    crew_member, fleet, space_ship, test_stuff, weapon, CrewMember, Fleet, Mission, SpaceShip,
+    TestStuff, Weapon,
+};
 use surrealdb::{
     engine::local::{Db, Mem},
     Surreal,
@@ -77,6 +80,311 @@ async fn test_save() -> SurrealOrmResult<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_save_versioned() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    let fleet = Fleet {
+        name: "Armada".into(),
+        ..Default::default()
+    };
+
+    let created = fleet.save().get_one(db.clone()).await?;
+    assert_eq!(created.version, 1);
+
+    let renamed = Fleet {
+        name: "Grand Armada".into(),
+        ..created.clone()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+    assert_eq!(renamed.version, 2);
+
+    // Saving the record read before the rename is rejected
+    let stale = Fleet {
+        name: "Lost Armada".into(),
+        ..created.clone()
+    }
+    .save()
+    .get_one(db.clone())
+    .await;
+    assert!(matches!(stale, Err(SurrealOrmError::StaleWrite(_, 1))));
+
+    let saved = Fleet::find_by_id(created.id.clone())
+        .get_one(db.clone())
+        .await?;
+    assert_eq!(saved.name, "Grand Armada");
+    assert_eq!(saved.version, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_versioned_with_expected_version() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    let fleet::Schema { name, .. } = Fleet::schema();
+
+    let fleet = Fleet {
+        name: "Armada".into(),
+        ..Default::default()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+
+    let statement = update::<Fleet>(fleet.id.clone())
+        .expect_version(fleet.version)
+        .set(name.equal_to("Grand Armada"));
+    assert!(statement.to_raw().build().contains("version += 1"));
+    assert!(statement.to_raw().build().contains(&format!(
+        "WHERE (IF version = 1 OR id = NONE {{ true }} ELSE {{ THROW 'stale write of {} at version 1' }})",
+        fleet.id.to_thing()
+    )));
+
+    let updated = statement.get_one(db.clone()).await?;
+    assert_eq!(updated.version, 2);
+
+    let patched = update::<Fleet>(fleet.id.clone())
+        .expect_version(updated.version)
+        .patch([name.patch_replace("Armada")])
+        .get_one(db.clone())
+        .await?;
+    assert_eq!(patched.version, 3);
+
+    let stale = update::<Fleet>(fleet.id.clone())
+        .expect_version(updated.version)
+        .set(name.equal_to("Lost Armada"))
+        .get_one(db.clone())
+        .await;
+    assert!(matches!(stale, Err(SurrealOrmError::StaleWrite(_, 2))));
+
+    let saved = Fleet::find_by_id(fleet.id.clone())
+        .get_one(db.clone())
+        .await?;
+    assert_eq!(saved.name, "Armada");
+    assert_eq!(saved.version, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stale_write_is_reported_from_transactions_and_bulk_updates() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    let fleet::Schema { name, .. } = Fleet::schema();
+
+    let fleet = Fleet {
+        name: "Armada".into(),
+        ..Default::default()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+    update::<Fleet>(fleet.id.clone())
+        .expect_version(fleet.version)
+        .set(name.equal_to("Grand Armada"))
+        .run(db.clone())
+        .await?;
+    let stale_id = fleet.id.to_thing().to_string();
+
+    let transaction = begin_transaction()
+        .query(
+            update::<Fleet>(fleet.id.clone())
+                .expect_version(fleet.version)
+                .set(name.equal_to("Lost Armada")),
+        )
+        .commit_transaction()
+        .get_results::<(Option<Fleet>,)>(db.clone())
+        .await;
+    assert!(
+        matches!(&transaction, Err(SurrealOrmError::StaleWrite(id, 1)) if *id == stale_id),
+        "{transaction:?}"
+    );
+
+    let report = bulk_update(vec![Fleet {
+        name: "Lost Armada".into(),
+        ..fleet.clone()
+    }])
+    .run(db.clone())
+    .await;
+    let chunk = &report.chunks[0].result;
+    assert!(
+        matches!(chunk, Err(SurrealOrmError::StaleWrite(id, 1)) if *id == stale_id),
+        "{chunk:?}"
+    );
+
+    let saved = Fleet::find_by_id(fleet.id.clone())
+        .get_one(db.clone())
+        .await?;
+    assert_eq!(saved.name, "Grand Armada");
+    assert_eq!(saved.version, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_versioned_without_expected_version_is_unchecked() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    let fleet::Schema { name, .. } = Fleet::schema();
+
+    let fleet = Fleet {
+        name: "Armada".into(),
+        ..Default::default()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+    let renamed = Fleet {
+        name: "Grand Armada".into(),
+        ..fleet.clone()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+    assert_eq!(renamed.version, 2);
+
+    // Written over the rename although read before it, and the version is left as is
+    let statement = update::<Fleet>(fleet.id.clone()).set(name.equal_to("Lost Armada"));
+    assert!(!statement.to_raw().build().contains("version"));
+
+    let updated = statement.get_one(db.clone()).await?;
+    assert_eq!(updated.name, "Lost Armada");
+    assert_eq!(updated.version, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_upsert_on_id() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    let weapon = Weapon {
+        id: Weapon::create_simple_id(),
+        name: "Laser".into(),
+        strength: 5.0,
+        ..Default::default()
+    };
+
+    let created = weapon.clone().upsert().get_one(db.clone()).await?;
+    assert_eq!(created.id.to_thing(), weapon.id.to_thing());
+    assert_eq!(created.strength, 5.0);
+
+    let replaced = Weapon {
+        name: "Laser Mk2".into(),
+        ..weapon.clone()
+    }
+    .upsert()
+    .get_one(db.clone())
+    .await?;
+    assert_eq!(replaced.id.to_thing(), weapon.id.to_thing());
+    assert_eq!(replaced.name, "Laser Mk2");
+
+    // Only the strength is updated on conflict
+    let merged = Weapon {
+        name: "Ignored".into(),
+        ..weapon.clone()
+    }
+    .upsert()
+    .merge_partial(Weapon::partial_builder().strength(50.0).build())
+    .get_one(db.clone())
+    .await?;
+    assert_eq!(merged.name, "Laser Mk2");
+    assert_eq!(merged.strength, 50.0);
+
+    let count = Weapon::count_all().get(db.clone()).await?;
+    assert_eq!(count, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_upsert_on_unique_fields() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    db.query("DEFINE INDEX weapon_name ON TABLE weapon FIELDS name UNIQUE;")
+        .await
+        .unwrap();
+    let weapon::Schema { name, .. } = Weapon::schema();
+
+    let laser = Weapon {
+        name: "Laser".into(),
+        strength: 5.0,
+        ..Default::default()
+    };
+    let created = laser
+        .clone()
+        .upsert_on(arr![&name])
+        .get_one(db.clone())
+        .await?;
+    assert_eq!(created.id.to_thing(), laser.id.to_thing());
+
+    // A new weapon with the same name updates the existing one and keeps its id
+    let statement = Weapon {
+        name: "Laser".into(),
+        strength: 10.0,
+        ..Default::default()
+    }
+    .upsert_on(arr![&name])
+    .merge();
+    assert!(statement.to_raw().build().contains("UPDATE weapon MERGE"));
+
+    let merged = statement.get_one(db.clone()).await?;
+    assert_eq!(merged.id.to_thing(), laser.id.to_thing());
+    assert_eq!(merged.strength, 10.0);
+
+    let count = Weapon::count_all().get(db.clone()).await?;
+    assert_eq!(count, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_upsert_on_unknown_field_fails() {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    let result = Weapon::default()
+        .upsert_on(Field::new("nonexistent"))
+        .return_one(db.clone())
+        .await;
+
+    assert!(matches!(result, Err(SurrealOrmError::QueryBuilder(_))));
+}
+
+#[tokio::test]
+async fn test_upsert_on_unset_optional_field() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    let test_stuff::Schema { amt2, .. } = TestStuff::schema();
+
+    let first = TestStuff {
+        amt2: None,
+        count: 1,
+        ..Default::default()
+    };
+    let statement = first.clone().upsert_on(arr![&amt2]);
+    assert!(statement.to_raw().build().starts_with("BEGIN TRANSACTION;"));
+
+    let created = statement.get_one(db.clone()).await?;
+    assert_eq!(created.id.to_thing(), first.id.to_thing());
+
+    // The record without amt2 is the conflicting one
+    let merged = TestStuff {
+        amt2: None,
+        count: 2,
+        ..Default::default()
+    }
+    .upsert_on(arr![&amt2])
+    .merge()
+    .get_one(db.clone())
+    .await?;
+    assert_eq!(merged.id.to_thing(), first.id.to_thing());
+    assert_eq!(merged.count, 2);
+
+    let count = TestStuff::count_all().get(db.clone()).await?;
+    assert_eq!(count, 1);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_find_by_id() -> SurrealOrmResult<()> {
     let db = Surreal::new::<Mem>(()).await.unwrap();
@@ -324,3 +632,218 @@ async fn test_delete_where() -> SurrealOrmResult<()> {
     assert_eq!(found_spaceships.len(), 0);
     Ok(())
 }
+
+async fn create_crew(db: Surreal<Db>) -> Vec<CrewMember> {
+    let crew = (1..=4)
+        .map(|rank| CrewMember {
+            name: format!("crew-{rank}"),
+            rank,
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    insert(crew).return_many(db).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_soft_delete() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    let crew = create_crew(db.clone()).await;
+
+    let statement = crew[0].delete();
+    assert!(statement.to_raw().build().starts_with("UPDATE"));
+    statement.run(db.clone()).await?;
+    CrewMember::delete_by_id(crew[1].id.clone())
+        .run(db.clone())
+        .await?;
+
+    let found = CrewMember::find_by_id(crew[0].id.clone())
+        .return_one(db.clone())
+        .await?;
+    assert!(found.is_none());
+
+    let found = CrewMember::find_by_id(crew[0].id.clone())
+        .with_deleted()
+        .return_one(db.clone())
+        .await?;
+    assert_eq!(found.unwrap().name, "crew-1");
+
+    // The soft deleted records are still stored along with when they were deleted
+    let deleted_at: Vec<chrono::DateTime<chrono::Utc>> = db
+        .query("SELECT VALUE deleted_at FROM crew_member WHERE deleted_at != NONE")
+        .await
+        .unwrap()
+        .take(0)
+        .unwrap();
+    assert_eq!(deleted_at.len(), 2);
+
+    assert_eq!(CrewMember::count_all().get(db.clone()).await?, 2);
+    assert_eq!(
+        CrewMember::count_all()
+            .with_deleted()
+            .get(db.clone())
+            .await?,
+        4
+    );
+    assert_eq!(
+        CrewMember::count_all()
+            .only_deleted()
+            .get(db.clone())
+            .await?,
+        2
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_soft_delete_where_and_restore() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    create_crew(db.clone()).await;
+    let crew_member::Schema { rank, .. } = CrewMember::schema();
+
+    CrewMember::delete_where(rank.gt(2)).run(db.clone()).await?;
+
+    let active = CrewMember::find_where(rank.gte(1))
+        .order_by(rank.asc())
+        .return_many(db.clone())
+        .await?;
+    assert_eq!(
+        active.iter().map(|c| c.rank).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+
+    let deleted = CrewMember::find_where(rank.gte(1))
+        .only_deleted()
+        .order_by(rank.asc())
+        .return_many(db.clone())
+        .await?;
+    assert_eq!(
+        deleted.iter().map(|c| c.rank).collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+    assert_eq!(
+        CrewMember::count_where(rank.gte(2)).get(db.clone()).await?,
+        1
+    );
+
+    let restored = deleted[0].restore().return_one(db.clone()).await?;
+    assert_eq!(restored.unwrap().rank, 3);
+    CrewMember::restore_where(rank.gt(3))
+        .run(db.clone())
+        .await?;
+
+    assert_eq!(CrewMember::count_all().get(db.clone()).await?, 4);
+    assert_eq!(
+        CrewMember::count_all()
+            .only_deleted()
+            .get(db.clone())
+            .await?,
+        0
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_save_keeps_soft_deleted_record_deleted() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    let crew = create_crew(db.clone()).await;
+    crew[0].delete().run(db.clone()).await?;
+
+    let deleted = CrewMember::find_by_id(crew[0].id.clone())
+        .with_deleted()
+        .get_one(db.clone())
+        .await?;
+    let statement = CrewMember { rank: 9, ..deleted }.save();
+    let query = statement.to_raw().build();
+    assert!(query.contains(" CONTENT "), "{query}");
+    assert!(query.contains("deleted_at: deleted_at"), "{query}");
+    statement.run(db.clone()).await?;
+
+    let found = CrewMember::find_by_id(crew[0].id.clone())
+        .return_one(db.clone())
+        .await?;
+    assert!(found.is_none());
+
+    let saved = CrewMember::find_by_id(crew[0].id.clone())
+        .only_deleted()
+        .get_one(db.clone())
+        .await?;
+    assert_eq!(saved.rank, 9);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_soft_delete_missing_record_is_not_created() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    CrewMember::delete_by_id(CrewMember::create_simple_id())
+        .run(db.clone())
+        .await?;
+
+    assert_eq!(
+        CrewMember::count_all()
+            .with_deleted()
+            .get(db.clone())
+            .await?,
+        0
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_only_deleted_without_soft_delete_fails() {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    let result = SpaceShip::find_where(Field::new("name").like("spaceship"))
+        .only_deleted()
+        .return_many(db.clone())
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(SurrealOrmError::QueryBuilder(e))
+            if e.contains("Only tables with #[sorm(soft_delete)] have soft deleted records to query")
+    ));
+}
+
+#[tokio::test]
+async fn test_save_with_timestamps() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    for definition in Mission::define_fields() {
+        db.query(definition.build()).await.unwrap();
+    }
+
+    let mission = Mission {
+        name: "Apollo".into(),
+        ..Default::default()
+    };
+    let created = mission.clone().save().get_one(db.clone()).await?;
+    // The timestamps written by the client are ignored
+    assert!(created.created_at > mission.created_at);
+
+    let renamed = Mission {
+        name: "Artemis".into(),
+        created_at: mission.created_at,
+        ..created.clone()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+    assert_eq!(renamed.created_at, created.created_at);
+    assert!(renamed.updated_at > created.updated_at);
+
+    let merged = update::<Mission>(created.id.clone())
+        .merge(Mission::partial_builder().name("Gemini".into()).build())
+        .get_one(db.clone())
+        .await?;
+    assert_eq!(merged.name, "Gemini");
+    assert_eq!(merged.created_at, created.created_at);
+    assert!(merged.updated_at > renamed.updated_at);
+
+    Ok(())
+}
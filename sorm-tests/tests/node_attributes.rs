@@ -6,10 +6,11 @@
  */
 
 use pretty_assertions::assert_eq;
-use sorm::*;
+use sorm::{statements::select, *};
 use sorm_models::{
-    Alien, AlienWithExplicitAttributes, StudentWithDefineAttr, StudentWithDefineFnAttr,
-    StudentWithGranularAttributes,
+    hangar, layover, Alien, AlienWithExplicitAttributes, Astronaut, CallSign, Craft, DutyStatus,
+    FlightLog, Hangar, Mission, Pilot, PilotRankChange, Shuttle, SpaceShip, StudentWithDefineAttr,
+    StudentWithDefineFnAttr, StudentWithGranularAttributes,
 };
 use surrealdb::{engine::local::Mem, Surreal};
 
@@ -123,3 +124,404 @@ async fn test_node_attributes_with_define_fn_attribute() -> SurrealOrmResult<()>
 
     Ok(())
 }
+
+#[test]
+fn test_node_attributes_with_timestamps() {
+    assert_eq!(
+        Mission::define_fields()
+            .iter()
+            .map(|x| x.to_raw().build())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "DEFINE FIELD id ON TABLE mission TYPE record<mission>;
+DEFINE FIELD name ON TABLE mission TYPE string;
+DEFINE FIELD createdAt ON TABLE mission TYPE datetime VALUE $before OR time::now();
+DEFINE FIELD updatedAt ON TABLE mission TYPE datetime VALUE time::now();"
+    );
+}
+
+#[test]
+fn test_node_attributes_with_indexes() {
+    let index_definitions = Pilot::define_indexes()
+        .iter()
+        .map(|x| x.to_raw().build())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        index_definitions.join("\n"),
+        "DEFINE INDEX email_idx ON TABLE pilot FIELDS email UNIQUE;
+DEFINE INDEX callSign_idx ON TABLE pilot FIELDS callSign;
+DEFINE INDEX bio_search ON TABLE pilot FIELDS bio SEARCH ANALYZER ascii BM25(1.2,0.75) HIGHLIGHTS;
+DEFINE INDEX call_sign_rank_idx ON TABLE pilot FIELDS callSign, rank UNIQUE;"
+    );
+
+    // Picked up by the migrator through the table resources
+    assert_eq!(
+        Pilot::indexes_definitions()
+            .iter()
+            .map(|x| x.build())
+            .collect::<Vec<_>>(),
+        index_definitions
+    );
+}
+
+#[tokio::test]
+async fn test_node_attributes_with_indexes_are_enforced() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    db.query("DEFINE ANALYZER ascii TOKENIZERS class FILTERS ascii;")
+        .await
+        .unwrap();
+    for definition in Pilot::define_indexes() {
+        db.query(definition.build()).await.unwrap().check().unwrap();
+    }
+
+    let pilot = Pilot {
+        email: "maverick@topgun.com".into(),
+        call_sign: "Maverick".into(),
+        rank: 3,
+        bio: "Fighter pilot".into(),
+        ..Default::default()
+    };
+    pilot.clone().save().get_one(db.clone()).await?;
+
+    let duplicate = Pilot {
+        id: Pilot::create_simple_id(),
+        call_sign: "Goose".into(),
+        ..pilot
+    }
+    .save()
+    .get_one(db.clone())
+    .await;
+    assert!(duplicate.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_node_attributes_with_events() {
+    let event_definitions = Pilot::define_events()
+        .iter()
+        .map(|x| x.to_raw().build())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        event_definitions.join("\n"),
+        "DEFINE EVENT rank_changed ON TABLE pilot WHEN ($event = 'UPDATE') AND ($before.rank != $after.rank) \
+THEN CREATE pilot_rank_change SET callSign = $after.callSign, oldRank = $before.rank, newRank = $after.rank;
+DEFINE EVENT pilot_removed ON TABLE pilot WHEN $event = 'DELETE' THEN DELETE pilot_rank_change WHERE callSign = $before.callSign;"
+    );
+
+    // Picked up by the migrator through the table resources
+    assert_eq!(
+        Pilot::events_definitions()
+            .iter()
+            .map(|x| x.build())
+            .collect::<Vec<_>>(),
+        event_definitions
+    );
+}
+
+#[tokio::test]
+async fn test_node_attributes_with_events_are_triggered() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    for definition in Pilot::define_events() {
+        db.query(definition.build()).await.unwrap().check().unwrap();
+    }
+
+    let pilot = Pilot {
+        email: "maverick@topgun.com".into(),
+        call_sign: "Maverick".into(),
+        rank: 3,
+        bio: "Fighter pilot".into(),
+        ..Default::default()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+
+    let pilot = Pilot { rank: 4, ..pilot }
+        .save()
+        .get_one(db.clone())
+        .await?;
+    // Changes that leave the rank untouched are not logged
+    Pilot {
+        bio: "Instructor".into(),
+        ..pilot.clone()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+
+    let rank_changes = select(All)
+        .from(PilotRankChange::table())
+        .return_many::<PilotRankChange>(db.clone())
+        .await?;
+    assert_eq!(rank_changes.len(), 1);
+    assert_eq!(rank_changes[0].call_sign, "Maverick");
+    assert_eq!(rank_changes[0].old_rank, 3);
+    assert_eq!(rank_changes[0].new_rank, 4);
+
+    // Removing the pilot clears their rank changes
+    pilot.delete().run(db.clone()).await?;
+
+    let rank_changes = select(All)
+        .from(PilotRankChange::table())
+        .return_many::<PilotRankChange>(db.clone())
+        .await?;
+    assert!(rank_changes.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_node_attributes_with_enum_fields() {
+    assert_eq!(
+        Astronaut::define_fields()
+            .iter()
+            .map(|x| x.to_raw().build())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "DEFINE FIELD id ON TABLE astronaut TYPE record<astronaut>;
+DEFINE FIELD name ON TABLE astronaut TYPE string;
+DEFINE FIELD status ON TABLE astronaut TYPE string | object \
+ASSERT ($value INSIDE ['active', 'on_leave']) OR (type::is::object($value) AND object::len($value) = 1 AND object::keys($value) ALLINSIDE ['retired']);
+DEFINE FIELD previousStatus ON TABLE astronaut TYPE option<string | object> \
+ASSERT ($value IS NONE) OR (($value INSIDE ['active', 'on_leave']) OR (type::is::object($value) AND object::len($value) = 1 AND object::keys($value) ALLINSIDE ['retired']));"
+    );
+}
+
+#[tokio::test]
+async fn test_node_attributes_with_enum_fields_are_asserted() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    for definition in Astronaut::define_fields() {
+        db.query(definition.build()).await.unwrap().check().unwrap();
+    }
+
+    let retired = Astronaut {
+        name: "Iceman".into(),
+        status: DutyStatus::Retired { year: 2020 },
+        previous_status: Some(DutyStatus::Active),
+        ..Default::default()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+    assert_eq!(retired.status, DutyStatus::Retired { year: 2020 });
+    assert_eq!(retired.previous_status, Some(DutyStatus::Active));
+
+    Astronaut {
+        name: "Goose".into(),
+        status: DutyStatus::OnLeave,
+        ..Default::default()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+
+    let invalid = db
+        .query("CREATE astronaut SET name = 'Viper', status = 'missing';")
+        .await
+        .unwrap()
+        .check();
+    assert!(invalid.is_err());
+
+    let on_leave = select(All)
+        .from(Astronaut::table())
+        .where_(Astronaut::schema().status.equal(DutyStatus::OnLeave))
+        .return_many::<Astronaut>(db.clone())
+        .await?;
+    assert_eq!(on_leave.len(), 1);
+    assert_eq!(on_leave[0].name, "Goose");
+
+    Ok(())
+}
+
+#[test]
+fn test_node_attributes_with_custom_field_types() {
+    assert_eq!(
+        FlightLog::define_fields()
+            .iter()
+            .map(|x| x.to_raw().build())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "DEFINE FIELD id ON TABLE flight_log TYPE record<flight_log>;
+DEFINE FIELD callSign ON TABLE flight_log TYPE string;
+DEFINE FIELD flightDate ON TABLE flight_log TYPE string;
+DEFINE FIELD fuelCost ON TABLE flight_log TYPE string;
+DEFINE FIELD crewSize ON TABLE flight_log TYPE int;
+DEFINE FIELD pilotStatus ON TABLE flight_log TYPE string | object \
+ASSERT ($value INSIDE ['active', 'on_leave']) OR (type::is::object($value) AND object::len($value) = 1 AND object::keys($value) ALLINSIDE ['retired']);
+DEFINE FIELD pastStatuses ON TABLE flight_log TYPE array<string | object> \
+ASSERT array::len($value[WHERE { LET $value = $this; RETURN !(($value INSIDE ['active', 'on_leave']) OR (type::is::object($value) AND object::len($value) = 1 AND object::keys($value) ALLINSIDE ['retired'])); }]) = 0;
+DEFINE FIELD layover ON TABLE flight_log TYPE int;
+DEFINE FIELD checkpoints ON TABLE flight_log TYPE object;
+DEFINE FIELD trackingId ON TABLE flight_log TYPE uuid;"
+    );
+}
+
+#[tokio::test]
+async fn test_node_attributes_with_custom_field_types_are_stored() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    for definition in FlightLog::define_fields() {
+        db.query(definition.build()).await.unwrap().check().unwrap();
+    }
+
+    let flight_log = FlightLog {
+        call_sign: CallSign("Maverick".into()),
+        flight_date: chrono::NaiveDate::from_ymd_opt(1986, 5, 16).unwrap(),
+        fuel_cost: "1250.75".parse().unwrap(),
+        crew_size: Box::new(2),
+        pilot_status: Box::new(DutyStatus::OnLeave),
+        past_statuses: vec![DutyStatus::Active, DutyStatus::Retired { year: 1984 }],
+        layover: layover::Duration(45),
+        checkpoints: [("miramar".to_string(), 3)].into(),
+        tracking_id: sql::Uuid::new_v4(),
+        ..Default::default()
+    };
+    let saved = flight_log.clone().save().get_one(db.clone()).await?;
+
+    assert_eq!(saved.call_sign, flight_log.call_sign);
+    assert_eq!(saved.flight_date, flight_log.flight_date);
+    assert_eq!(saved.fuel_cost, flight_log.fuel_cost);
+    assert_eq!(saved.crew_size, flight_log.crew_size);
+    assert_eq!(saved.pilot_status, flight_log.pilot_status);
+    assert_eq!(saved.past_statuses, flight_log.past_statuses);
+    assert_eq!(saved.layover, flight_log.layover);
+    assert_eq!(saved.checkpoints, flight_log.checkpoints);
+    assert_eq!(saved.tracking_id, flight_log.tracking_id);
+
+    // Every item is checked against the variants of the enum
+    let invalid_status = db
+        .query(format!(
+            "UPDATE {} SET pastStatuses = ['active', 'grounded']",
+            saved.id.to_thing()
+        ))
+        .await
+        .unwrap()
+        .check();
+    assert!(invalid_status.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_node_attributes_with_node_union_links() {
+    assert_eq!(
+        Hangar::define_fields()
+            .iter()
+            .map(|x| x.to_raw().build())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        "DEFINE FIELD id ON TABLE hangar TYPE record<hangar>;
+DEFINE FIELD name ON TABLE hangar TYPE string;
+DEFINE FIELD dockedCraft ON TABLE hangar TYPE record<space_ship|shuttle>;
+DEFINE FIELD visitingCrafts ON TABLE hangar TYPE array<record<space_ship|shuttle>>;"
+    );
+
+    assert_eq!(
+        Hangar::schema()
+            .dockedCraft()
+            .shuttle()
+            .seats
+            .to_raw()
+            .build(),
+        "dockedCraft.seats"
+    );
+    assert_eq!(
+        Hangar::schema()
+            .visitingCrafts(All)
+            .space_ship()
+            .name
+            .to_raw()
+            .build(),
+        "visitingCrafts[*].name"
+    );
+}
+
+#[tokio::test]
+async fn test_node_union_links_are_fetched_into_their_variants() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    for definition in Hangar::define_fields() {
+        db.query(definition.build()).await.unwrap().check().unwrap();
+    }
+
+    let space_ship = SpaceShip {
+        name: "Millennium Falcon".into(),
+        ..Default::default()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+    let shuttle = Shuttle {
+        name: "Tydirium".into(),
+        seats: 8,
+        ..Default::default()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+
+    let hangar = Hangar {
+        name: "Docking Bay 94".into(),
+        docked_craft: LinkOne::from(Craft::from(shuttle.clone())),
+        visiting_crafts: LinkMany::from(vec![
+            Craft::from(space_ship.clone()),
+            Craft::from(shuttle.clone()),
+        ]),
+        ..Default::default()
+    }
+    .save()
+    .get_one(db.clone())
+    .await?;
+    assert_eq!(
+        hangar.docked_craft.get_id(),
+        Some(&shuttle.get_id_as_thing())
+    );
+    assert_eq!(hangar.visiting_crafts.keys_truthy().len(), 2);
+
+    let hangar::Schema {
+        dockedCraft,
+        visitingCrafts,
+        ..
+    } = Hangar::schema();
+    let fetched = select(All)
+        .from(Hangar::table())
+        .fetch(arr![dockedCraft, visitingCrafts])
+        .return_one::<Hangar>(db.clone())
+        .await?
+        .unwrap();
+
+    assert!(matches!(
+        fetched.docked_craft.value(),
+        Some(Craft::Shuttle(docked)) if docked.seats == 8
+    ));
+    let visiting_crafts = fetched.visiting_crafts.values_truthy();
+    assert!(matches!(
+        visiting_crafts[0],
+        Craft::SpaceShip(visiting) if visiting.name == "Millennium Falcon"
+    ));
+    assert!(matches!(
+        visiting_crafts[1],
+        Craft::Shuttle(visiting) if visiting.name == "Tydirium"
+    ));
+
+    // Records of tables outside the union are rejected
+    let invalid = db
+        .query("CREATE hangar SET name = 'Echo Base', dockedCraft = alien:tauntaun, visitingCrafts = [];")
+        .await
+        .unwrap()
+        .check();
+    assert!(invalid.is_err());
+
+    Ok(())
+}
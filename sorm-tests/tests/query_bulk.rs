@@ -0,0 +1,128 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use chrono::Utc;
+use pretty_assertions::assert_eq;
+use sorm::{
+    statements::{bulk_insert, bulk_relate, bulk_update, relate, select},
+    *,
+};
+use sorm_models::{Book, Student, StudentWritesBook, Weapon};
+use surrealdb::{engine::local::Mem, Surreal};
+
+fn weapons(count: usize) -> Vec<Weapon> {
+    (1..=count)
+        .map(|i| Weapon {
+            name: format!("Weapon {}", i),
+            strength: i as f64,
+            created: Utc::now(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_bulk_insert_and_update_in_chunks() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    let weapons = weapons(7);
+    let report = bulk_insert(weapons.clone())
+        .chunk_size(3)
+        .concurrency(2)
+        .run(db.clone())
+        .await;
+
+    assert!(report.is_success());
+    assert_eq!(
+        report.chunks.iter().map(|c| c.size).collect::<Vec<_>>(),
+        vec![3, 3, 1]
+    );
+    assert_eq!(
+        report.ids(),
+        weapons.iter().map(|w| w.id.to_thing()).collect::<Vec<_>>()
+    );
+
+    let updated = weapons
+        .into_iter()
+        .map(|w| Weapon {
+            strength: w.strength * 10.0,
+            ..w
+        })
+        .collect::<Vec<_>>();
+    let report = bulk_update(updated).chunk_size(4).run(db.clone()).await;
+    assert!(report.is_success());
+    assert_eq!(report.ids().len(), 7);
+
+    let mut strengths = select(All)
+        .from(Weapon::table())
+        .return_many::<Weapon>(db.clone())
+        .await?
+        .into_iter()
+        .map(|w| w.strength)
+        .collect::<Vec<_>>();
+    strengths.sort_by(|a, b| a.total_cmp(b));
+    assert_eq!(strengths, vec![10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_insert_rolls_back_only_failed_chunks() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    db.query("DEFINE FIELD strength ON TABLE weapon ASSERT $value < 5;")
+        .await
+        .unwrap();
+
+    // Weapons 5 and 6 are too strong, so the last chunk fails
+    let report = bulk_insert(weapons(6)).chunk_size(2).run(db.clone()).await;
+
+    assert!(!report.is_success());
+    assert_eq!(
+        report.failed().map(|c| c.index).collect::<Vec<_>>(),
+        vec![2]
+    );
+    assert_eq!(report.succeeded().count(), 2);
+    assert_eq!(report.ids().len(), 4);
+
+    // The failed chunk is not partially written
+    let count = select(All)
+        .from(Weapon::table())
+        .return_many::<Weapon>(db.clone())
+        .await?
+        .len();
+    assert_eq!(count, 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bulk_relate_in_chunks() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    let student_id = Student::create_id("oyelowo".to_string());
+    let relations = (1..=5).map(|i| {
+        let book_id = Book::create_id(format!("book{i}"));
+        relate(Student::with(&student_id).writes__(E).book(&book_id))
+            .content(StudentWritesBook::default())
+    });
+
+    let report = bulk_relate(relations).chunk_size(2).run(db.clone()).await;
+
+    assert!(report.is_success());
+    assert_eq!(report.chunks.len(), 3);
+    assert_eq!(report.ids().len(), 5);
+    assert!(report
+        .ids()
+        .iter()
+        .all(|id| id.tb == StudentWritesBook::table().to_string()));
+
+    Ok(())
+}
@@ -0,0 +1,111 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use chrono::Utc;
+use futures::StreamExt;
+use pretty_assertions::assert_eq;
+use sorm::{
+    statements::{create, delete, live, select, update, LiveEvent, LivePatch},
+    *,
+};
+use sorm_models::{weapon, Weapon};
+use surrealdb::{engine::local::Mem, Surreal};
+
+#[tokio::test]
+async fn test_live_select_streams_typed_events() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    let weapon::Schema { strength, .. } = Weapon::schema();
+
+    let mut weapons = live(
+        select(All)
+            .from(Weapon::table())
+            .where_(strength.greater_than(5)),
+    )
+    .stream::<Weapon>(db.clone())
+    .await?;
+
+    let weak = Weapon {
+        name: "Stick".to_string(),
+        strength: 1.0,
+        created: Utc::now(),
+        ..Default::default()
+    };
+    let laser = Weapon {
+        name: "Laser".to_string(),
+        strength: 10.0,
+        created: Utc::now(),
+        ..Default::default()
+    };
+    create().content(weak).get_one(db.clone()).await?;
+    let laser = create().content(laser).get_one(db.clone()).await?;
+    update::<Weapon>(&laser.id)
+        .set(strength.increment_by(5f64))
+        .run(db.clone())
+        .await?;
+    delete::<Weapon>(&laser.id).run(db.clone()).await?;
+
+    // Changes to records not matching the condition are not notified
+    match weapons.next().await.unwrap()? {
+        LiveEvent::Create(created) => {
+            assert_eq!(created.id, laser.id);
+            assert_eq!(created.strength, 10.0);
+        }
+        event => panic!("Expected a create event. Got {event:?}"),
+    };
+    match weapons.next().await.unwrap()? {
+        LiveEvent::Update(updated) => assert_eq!(updated.strength, 15.0),
+        event => panic!("Expected an update event. Got {event:?}"),
+    };
+    assert_eq!(
+        weapons.next().await.unwrap()?,
+        LiveEvent::Delete(laser.id.to_thing())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_live_select_diff_streams_patches() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    let weapon::Schema { strength, .. } = Weapon::schema();
+
+    let mut changes = live(select(All).from(Weapon::table()))
+        .diff()
+        .stream(db.clone())
+        .await?;
+
+    let laser = Weapon {
+        name: "Laser".to_string(),
+        strength: 10.0,
+        created: Utc::now(),
+        ..Default::default()
+    };
+    let laser = create().content(laser).get_one(db.clone()).await?;
+    update::<Weapon>(&laser.id)
+        .set(strength.equal_to(20f64))
+        .run(db.clone())
+        .await?;
+
+    match changes.next().await.unwrap()? {
+        LiveEvent::Create(patches) => assert!(matches!(
+            patches.as_slice(),
+            [LivePatch::Replace { path, .. }] if path == "/"
+        )),
+        event => panic!("Expected a create event. Got {event:?}"),
+    };
+    assert_eq!(
+        changes.next().await.unwrap()?,
+        LiveEvent::Update(vec![LivePatch::Replace {
+            path: "/strength".to_string(),
+            value: serde_json::json!(20.0),
+        }])
+    );
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+/*
+ * Author: Oyelowo Oyedayo
+ * Email: oyelowo.oss@gmail.com
+ * Copyright (c) 2023 Oyelowo Oyedayo
+ * Licensed under the MIT license
+ */
+
+use chrono::Utc;
+use pretty_assertions::assert_eq;
+use sorm::{
+    statements::{insert, select, Cursor, Page},
+    *,
+};
+use sorm_models::{weapon, Weapon};
+use surrealdb::{engine::local::Mem, Surreal};
+
+fn strengths(page: &Page<Weapon>) -> Vec<f64> {
+    page.items.iter().map(|w| w.strength).collect()
+}
+
+#[tokio::test]
+async fn test_keyset_pagination_walks_forward_and_backward() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    let weapon::Schema { strength, .. } = Weapon::schema();
+
+    // Strengths repeat so that the record id breaks the ties
+    let weapons = (1..=7)
+        .map(|i| Weapon {
+            name: format!("Weapon {}", i),
+            strength: (i % 3) as f64,
+            created: Utc::now(),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    insert(weapons).run(db.clone()).await?;
+
+    let paginate = || {
+        select(All)
+            .from(Weapon::table())
+            .paginate::<Weapon>(strength.desc(), 3)
+    };
+
+    let first = paginate().get_page(db.clone()).await?;
+    assert_eq!(strengths(&first), vec![2.0, 2.0, 1.0]);
+    assert!(first.has_more);
+    assert!(first.prev_cursor.is_none());
+
+    let second = paginate()
+        .cursor(first.next_cursor.clone())
+        .get_page(db.clone())
+        .await?;
+    assert_eq!(strengths(&second), vec![1.0, 1.0, 0.0]);
+    assert!(second.has_more);
+
+    let last = paginate()
+        .cursor(second.next_cursor.clone())
+        .get_page(db.clone())
+        .await?;
+    assert_eq!(strengths(&last), vec![0.0]);
+    assert!(!last.has_more);
+    assert!(last.next_cursor.is_none());
+
+    let all_ids = [&first, &second, &last]
+        .iter()
+        .flat_map(|page| page.items.iter().map(|w| w.id.to_string()))
+        .collect::<std::collections::HashSet<_>>();
+    assert_eq!(all_ids.len(), 7);
+
+    // Walking back returns the same pages
+    let back = paginate()
+        .cursor(last.prev_cursor.clone())
+        .get_page(db.clone())
+        .await?;
+    assert_eq!(
+        back.items.iter().map(|w| &w.id).collect::<Vec<_>>(),
+        second.items.iter().map(|w| &w.id).collect::<Vec<_>>()
+    );
+    assert!(back.has_more);
+    assert!(back.next_cursor.is_some());
+
+    let back = paginate()
+        .cursor(back.prev_cursor)
+        .get_page(db.clone())
+        .await?;
+    assert_eq!(
+        back.items.iter().map(|w| &w.id).collect::<Vec<_>>(),
+        first.items.iter().map(|w| &w.id).collect::<Vec<_>>()
+    );
+    assert!(!back.has_more);
+    assert!(back.prev_cursor.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_keyset_pagination_of_model_select() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+    let weapon::Schema { strength, .. } = Weapon::schema();
+
+    let weapons = (1..=5)
+        .map(|i| Weapon {
+            name: format!("Weapon {}", i),
+            strength: i as f64,
+            created: Utc::now(),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    insert(weapons).run(db.clone()).await?;
+
+    let page = Weapon::find_where(strength.gt(1))
+        .paginate(strength.asc(), 2)
+        .get_page(db.clone())
+        .await?;
+    assert_eq!(strengths(&page), vec![2.0, 3.0]);
+
+    let page = Weapon::find_where(strength.gt(1))
+        .paginate(strength.asc(), 2)
+        .cursor(page.next_cursor)
+        .get_page(db.clone())
+        .await?;
+    assert_eq!(strengths(&page), vec![4.0, 5.0]);
+    assert!(!page.has_more);
+
+    let invalid = Weapon::find_where(strength.gt(1))
+        .paginate(strength.asc(), 2)
+        .cursor(Cursor::from("not-a-cursor"))
+        .get_page(db.clone())
+        .await;
+    assert!(matches!(invalid, Err(SurrealOrmError::InvalidCursor(_))));
+
+    Ok(())
+}
@@ -12,8 +12,8 @@ use pretty_assertions::assert_eq;
 use sorm::statements::{create, insert, order, relate, select, select_value};
 use sorm::*;
 use sorm_models::{
-    company, like, user, writes, AlienVisitsPlanet, Blog, Book, Company, CompanyLikeUser, Student,
-    StudentLiksBook, StudentWritesBlog, StudentWritesBook, User,
+    company, like, user, writes, Alien, AlienVisitsPlanet, Blog, Book, Company, CompanyLikeUser,
+    Planet, Student, StudentLiksBook, StudentWritesBlog, StudentWritesBook, User,
 };
 use std::time::Duration;
 use surrealdb::sql;
@@ -512,3 +512,74 @@ async fn relate_query_with_sub_query() -> surreal_orm::SurrealOrmResult<()> {
     insta::assert_debug_snapshot!(result);
     Ok(())
 }
+
+#[tokio::test]
+async fn can_eagerly_load_relations() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    let alien = Alien {
+        id: Alien::create_simple_id(),
+        name: "Oyelowo".to_string(),
+        age: 20,
+        created: chrono::Utc::now(),
+        line_string: geo::line_string![(x: 40.02, y: 116.34), (x: 40.03, y: 116.35)],
+        life_expectancy: Duration::from_secs(100),
+        territory_area: geo::polygon![(x: 40.02, y: 116.34), (x: 40.02, y: 116.35), (x: 40.03, y: 116.35), (x: 40.02, y: 116.34)],
+        home: geo::point!(x: 40.02, y: 116.34),
+        tags: vec!["tag1".into()],
+        ally: LinkSelf::null(),
+        weapon: LinkOne::null(),
+        space_ships: LinkMany::null(),
+        planets_to_visit: Relate::null(),
+    };
+    let alien_id = alien.id.clone();
+    create().content(alien).run(db.clone()).await?;
+
+    for name in ["Mars", "Venus"] {
+        let planet = Planet::<u64> {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let planet_id = planet.id.clone();
+        create().content(planet).run(db.clone()).await?;
+        relate::<AlienVisitsPlanet>(Alien::with(&alien_id).visits__(E).planet(&planet_id))
+            .run(db.clone())
+            .await?;
+    }
+
+    let alien = Alien::find_by_id(alien_id.clone())
+        .return_one(db.clone())
+        .await?
+        .unwrap();
+    assert_eq!(alien.planets_to_visit.values_truthy_count(), 0);
+
+    let alien = Alien::find_by_id(alien_id.clone())
+        .load_relations(vec![Alien::aliases().planetsToVisit])?
+        .return_one(db.clone())
+        .await?
+        .unwrap();
+    let mut planet_names = alien
+        .planets_to_visit
+        .values_truthy()
+        .into_iter()
+        .map(|p| p.name.clone())
+        .collect::<Vec<_>>();
+    planet_names.sort();
+    assert_eq!(planet_names, vec!["Mars", "Venus"]);
+
+    let alien = Alien::find_by_id(alien_id.clone())
+        .load_all_relations()?
+        .return_one(db.clone())
+        .await?
+        .unwrap();
+    assert_eq!(alien.planets_to_visit.values_truthy_count(), 2);
+
+    let unknown = Alien::find_by_id(alien_id).load_relations(vec!["unknownRelation"]);
+    assert!(matches!(
+        unknown,
+        Err(SurrealOrmError::AliasesUnfetchableNotARelation(_))
+    ));
+
+    Ok(())
+}
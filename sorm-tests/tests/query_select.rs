@@ -8,10 +8,11 @@
 use chrono::Utc;
 use pretty_assertions::assert_eq;
 use sorm::{
-    statements::{insert, order, select, select_value},
+    statements::{create, insert, order, select, select_value},
     *,
 };
-use sorm_models::{weapon, SpaceShip, Weapon};
+use sorm_models::{weapon, Alien, SpaceShip, Weapon};
+use std::time::Duration;
 use surrealdb::{engine::local::Mem, Surreal};
 
 #[tokio::test]
@@ -123,3 +124,74 @@ async fn test_subquery_in_select_statement_with_order_functions() -> SurrealOrmR
     assert!(statement[0].id.to_string().starts_with("weapon:"));
     Ok(())
 }
+
+fn alien(name: &str, weapon: LinkOne<Weapon>, ally: LinkSelf<Alien>) -> Alien {
+    Alien {
+        id: Alien::create_simple_id(),
+        name: name.to_string(),
+        age: 20,
+        created: Utc::now(),
+        line_string: geo::line_string![(x: 40.02, y: 116.34), (x: 40.03, y: 116.35)],
+        life_expectancy: Duration::from_secs(100),
+        territory_area: geo::polygon![(x: 40.02, y: 116.34), (x: 40.02, y: 116.35), (x: 40.03, y: 116.35), (x: 40.02, y: 116.34)],
+        home: geo::point!(x: 40.02, y: 116.34),
+        tags: vec![],
+        ally,
+        weapon,
+        space_ships: LinkMany::null(),
+        planets_to_visit: Relate::null(),
+    }
+}
+
+#[tokio::test]
+async fn test_include_nested_record_links() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    let laser = create()
+        .content(Weapon {
+            name: "Laser".to_string(),
+            created: Utc::now(),
+            ..Default::default()
+        })
+        .get_one(db.clone())
+        .await?;
+    let ally = create()
+        .content(alien("Ally", LinkOne::from(laser), LinkSelf::null()))
+        .get_one(db.clone())
+        .await?;
+    let oyelowo = create()
+        .content(alien("Oyelowo", LinkOne::null(), LinkSelf::from(ally)))
+        .get_one(db.clone())
+        .await?;
+
+    let include =
+        Alien::find_by_id(oyelowo.id.clone()).include(arr![Alien::schema().ally().weapon()])?;
+    assert_eq!(
+        include.to_raw().build(),
+        format!("SELECT * FROM {} FETCH ally, ally.weapon;", oyelowo.id)
+    );
+
+    let fetched = include.return_one(db.clone()).await?.unwrap();
+    let fetched_ally = fetched.ally.value().unwrap();
+    assert_eq!(fetched_ally.name, "Ally");
+    assert_eq!(fetched_ally.weapon.value().unwrap().name, "Laser");
+
+    let fetched = select(All)
+        .from(Alien::table())
+        .where_(Alien::schema().name.equal("Oyelowo"))
+        .include::<Alien>(arr![Alien::schema().ally().spaceShips(All)])?
+        .return_one::<Alien>(db.clone())
+        .await?
+        .unwrap();
+    assert_eq!(fetched.ally.value().unwrap().name, "Ally");
+    assert!(fetched.ally.value().unwrap().weapon.value().is_none());
+
+    let not_a_link = Alien::find_by_id(oyelowo.id).include(arr![Alien::schema().ally().name]);
+    assert!(matches!(
+        not_a_link,
+        Err(SurrealOrmError::FieldsUnfetchableNotARecordLink(path)) if path == "ally.name"
+    ));
+
+    Ok(())
+}
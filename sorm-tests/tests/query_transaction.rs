@@ -221,3 +221,119 @@ async fn test_transaction_cancellation_increment_and_decrement_update() -> Surre
     assert_eq!(accounts.len(), 0);
     Ok(())
 }
+
+#[tokio::test]
+async fn test_transaction_get_results_deserializes_each_statement() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    let id1 = &Account::create_id("one".into());
+    let id2 = &Account::create_id("two".into());
+    let acc = Account::schema();
+
+    let (_, created, updated, accounts, missing): (
+        (),
+        Option<Account>,
+        Vec<Account>,
+        Vec<Account>,
+        Option<Account>,
+    ) = transaction! {
+        BEGIN TRANSACTION;
+
+        let balance = create_only().content(Balance {
+            id: Balance::create_id("balance1".into()),
+            amount: 300.00,
+        });
+
+        create_only().content(Account {
+            id: id1.clone(),
+            balance: 135_605.16,
+        });
+
+        update::<Account>(id1).set(acc.balance.increment_by(balance.with_path::<Balance>(E).amount));
+
+        select(All).from(id1..=id2);
+
+        select(All).from(id2);
+
+        COMMIT TRANSACTION;
+    }
+    .get_results(db.clone())
+    .await?;
+
+    assert_eq!(created.expect("Account not created").balance, 135_605.16);
+    assert_eq!(updated.len(), 1);
+    assert_eq!(updated[0].balance, 135_905.16);
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].id.to_string(), "account:one");
+    assert!(missing.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transaction_get_results_reports_failed_statement() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    let id1 = &Account::create_id("one".into());
+    let account = Account {
+        id: id1.clone(),
+        balance: 135_605.16,
+    };
+
+    let results = begin_transaction()
+        .query(create_only().content(account.clone()))
+        .query(create_only().content(account))
+        .query(select(All).from(id1))
+        .commit_transaction()
+        .get_results::<(Option<Account>, Option<Account>, Vec<Account>)>(db.clone())
+        .await;
+
+    assert!(matches!(
+        results,
+        Err(SurrealOrmError::StatementFailed(1, _))
+    ));
+
+    let accounts = select(All)
+        .from(id1)
+        .return_many::<Account>(db.clone())
+        .await?;
+    assert!(accounts.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_chain_get_results_requires_an_output_per_statement() -> SurrealOrmResult<()> {
+    let db = Surreal::new::<Mem>(()).await.unwrap();
+    db.use_ns("test").use_db("test").await.unwrap();
+
+    let id1 = &Account::create_id("one".into());
+    create_only()
+        .content(Account {
+            id: id1.clone(),
+            balance: 135_605.16,
+        })
+        .run(db.clone())
+        .await?;
+
+    let query = chain(select(All).from(Account::table())).chain(select(All).from(id1));
+
+    let results = query.get_results::<(Vec<Account>,)>(db.clone()).await;
+    assert!(matches!(
+        results,
+        Err(SurrealOrmError::StatementCountMismatch(1, 2))
+    ));
+
+    let (accounts, account) = query
+        .get_results::<(Vec<Account>, Option<Account>)>(db.clone())
+        .await?;
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(
+        account.expect("Account not found").id.to_string(),
+        "account:one"
+    );
+
+    Ok(())
+}
@@ -200,6 +200,15 @@ pub use sorm_derive::Node;
 #[doc = include_str!("docs/object_field_attributes.md")]
 pub use sorm_derive::Object;
 
+#[doc = include_str!("docs/node_union_description.md")]
+pub use sorm_derive::NodeUnion;
+
+#[doc = include_str!("docs/surreal_enum_description.md")]
+pub use sorm_derive::SurrealEnum;
+
+#[doc = include_str!("docs/surreal_field_type_description.md")]
+pub use sorm_derive::SurrealFieldType;
+
 pub use sorm_derive::pick;
 pub use sorm_derive::Pickable;
 